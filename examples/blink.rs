@@ -9,7 +9,7 @@ extern crate sensd;
 extern crate serde;
 
 use sensd::action::IOCommand;
-use sensd::io::{IOKind, IdType, RawValue, Input, Device};
+use sensd::io::{IOKind, IdType, RawValue, Output, Device};
 use sensd::storage::{Group, Persistent};
 use std::ops::{DerefMut, Neg};
 
@@ -40,14 +40,14 @@ fn init(name: &str) -> Group {
 ///
 /// Use of `Group::add_devices()` is demonstrated.
 fn setup_devices(poller: &mut Group) {
-    poller.push_input(
-        Input::new(
+    poller.push_output(
+        Output::new(
             "Mock Output",
             OUTPUT_ID,
             IOKind::Light,
-        ).set_command(
-            IOCommand::Output(|val| Ok(println!("\n{}\n", val)))
-        )
+        ).with_command(
+            IOCommand::output(|val| Ok(println!("\n{}\n", val)))
+        ).unwrap()
     );
 }
 
@@ -69,7 +69,7 @@ fn main() {
         {
             let mut binding = wrapped_device.try_lock().unwrap();
             binding.deref_mut()
-                .write(value)
+                .write(value.clone())
                 .expect("Error while calling `::write()` on output device");
         }
 
@@ -0,0 +1,147 @@
+//! A complete input → action → output control loop.
+//!
+//! # Description
+//!
+//! A simulated pH probe slowly drifts upward. Two subscribers are attached to the probe:
+//!
+//! - A [`actions::Threshold`] with hysteresis which switches an alarm light while pH is too high.
+//! - A [`actions::PID`] controller which turns on a dosing pump and schedules a
+//!   [`sensd::action::Routine`] to turn the pump back off after a calculated duration.
+//!
+//! While the pump is on, pH is lowered. The event loop polls the `Group` and calls
+//! `Group::attempt_routines()` as often as possible so that scheduled writes are executed on time.
+//!
+//! # Note
+//!
+//! ## █▓▒░ Simulated Hardware
+//!
+//! Simulated hardware state is stored in atomic statics which avoids the use of unsafe code. Since
+//! [`IOCommand`] closures may capture state, an `Arc` shared between commands would work as well.
+extern crate chrono;
+extern crate sensd;
+
+use chrono::Duration;
+use sensd::action::{Action, actions, IOCommand, Trigger};
+use sensd::io::{Device, IdType, Input, IOKind, Output, RawValue};
+use sensd::storage::Group;
+
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+const PROBE_ID: IdType = 0;
+const PUMP_ID: IdType = 0;
+const ALARM_ID: IdType = 1;
+
+/// █▓▒░ Polling interval of `Group`
+const INTERVAL_MS: i64 = 500;
+
+/// █▓▒░ Maximum time to sleep between iterations of event loop
+///
+/// This is intentionally shorter than `INTERVAL_MS` so that scheduled routines are executed close to
+/// their scheduled time. When the next poll is due sooner, the loop sleeps for less.
+const FREQUENCY: std::time::Duration = std::time::Duration::from_millis(25);
+
+/// Number of polling cycles before exiting
+const CYCLES: usize = 30;
+
+const SETPOINT: f32 = 7.0;
+const ALARM_THRESHOLD: f32 = 7.4;
+const ALARM_HYSTERESIS: f32 = 0.2;
+
+/// Maximum dose duration in seconds
+const DOSE_LIMIT: f32 = 1.0;
+
+/// Change in pH per polling cycle
+const DRIFT: f32 = 0.03;
+const DOSE_RATE: f32 = 0.08;
+
+/// Simulated pH, stored as bits of an `f32`
+static PH: AtomicU32 = AtomicU32::new(0x40e00000);  // 7.0
+static PUMP_ON: AtomicBool = AtomicBool::new(false);
+static ALARM_ON: AtomicBool = AtomicBool::new(false);
+
+fn read_ph() -> f32 {
+    f32::from_bits(PH.load(Ordering::Relaxed))
+}
+
+/// █▓▒░ Advance simulation by a single polling cycle
+fn simulate() {
+    let mut ph = read_ph() + DRIFT;
+    if PUMP_ON.load(Ordering::Relaxed) {
+        ph -= DOSE_RATE;
+    }
+    PH.store(ph.to_bits(), Ordering::Relaxed);
+}
+
+/// █▓▒░ Build devices, wire subscribers, and add devices to `Group`.
+fn setup(group: &mut Group) {
+    let pump = Output::new("dosing pump", PUMP_ID, IOKind::Flow)
+        .with_command(IOCommand::output(|value| {
+            PUMP_ON.store(value == RawValue::Binary(true), Ordering::Relaxed);
+            Ok(())
+        })).unwrap()
+        .init_log();
+    let alarm = Output::new("alarm light", ALARM_ID, IOKind::Light)
+        .with_command(IOCommand::output(|value| {
+            ALARM_ON.store(value == RawValue::Binary(true), Ordering::Relaxed);
+            Ok(())
+        })).unwrap()
+        .init_log();
+
+    let pump = group.push_output(pump).outputs.get(&PUMP_ID).unwrap().clone();
+    let alarm = group.push_output(alarm).outputs.get(&ALARM_ID).unwrap().clone();
+
+    let mut probe = Input::new("pH probe", PROBE_ID, IOKind::PH)
+        .with_command(IOCommand::input(|| RawValue::Float(read_ph()))).unwrap()
+        .init_log()
+        .init_publisher();
+
+    let publisher = probe.publisher_mut().as_mut().unwrap();
+    let handler = publisher.handler_ref();
+
+    publisher.subscribe(
+        actions::Threshold::new("pH alarm", RawValue::Float(ALARM_THRESHOLD), Trigger::GT)
+            .set_hysteresis(RawValue::Float(ALARM_HYSTERESIS))
+            .set_output(alarm)
+            .into_boxed()
+    );
+    publisher.subscribe(
+        actions::PID::new("pH controller", SETPOINT, DOSE_LIMIT)
+            // pH is lowered by dosing, therefore gain is negative
+            .set_p(-2.0, DOSE_LIMIT)
+            .set_handler(handler)
+            .set_output(pump)
+            .into_boxed()
+    );
+
+    group.push_input(probe);
+}
+
+fn main() {
+    let mut group = Group::with_interval("control_loop", Duration::milliseconds(INTERVAL_MS));
+    setup(&mut group);
+
+    println!("█▓▒░ Beginning loop ░▒▓█\n");
+
+    let mut cycles = 0;
+    while cycles < CYCLES {
+        if let Ok(errors) = group.poll() {
+            for error in errors {
+                eprintln!("█▓▒░ ERROR: {}", error);
+            }
+            println!("pH: {:.2}\tpump: {}\talarm: {}",
+                     read_ph(),
+                     PUMP_ON.load(Ordering::Relaxed),
+                     ALARM_ON.load(Ordering::Relaxed));
+
+            simulate();
+            cycles += 1;
+        }
+
+        group.attempt_routines();
+
+        let until_poll = group.time_until_poll().to_std().unwrap_or_default();
+        std::thread::sleep(until_poll.min(FREQUENCY));
+    }
+
+    println!("\nTotal events logged: {}", group.total_event_count());
+}
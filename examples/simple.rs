@@ -70,7 +70,7 @@ fn main() {
         let name = "test name";
         let id = 0;
         let kind = IOKind::PH;
-        let command = IOCommand::Input(|| RawValue::Float(1.2));
+        let command = IOCommand::input(|| RawValue::Float(1.2));
 
         // build input device
         let mut input =
@@ -78,9 +78,9 @@ fn main() {
                 name,
                 id,
                 Some(kind),
-            ).set_command(
+            ).with_command(
                 command
-            ).init_log(
+            ).unwrap().init_log(
             ).init_publisher();
 
         // setup publisher/action
@@ -100,7 +100,7 @@ fn main() {
         let name = "second sensor";
         let id = 1;
         let kind = IOKind::PH;
-        let command = IOCommand::Input(|| RawValue::Float(1.2));
+        let command = IOCommand::input(|| RawValue::Float(1.2));
 
         // build input device
         let mut input = Input::new(
@@ -108,7 +108,7 @@ fn main() {
             id,
             kind,
         )
-            .set_command(command)
+            .with_command(command).unwrap()
             .init_log()
             .init_publisher();
 
@@ -104,9 +104,9 @@ fn main() {
                 "mock temp sensor",
                 INPUT_ID,
                 IOKind::Temperature,
-            ).set_command(
-                IOCommand::Input(|| EXTERNAL_VALUE)
-            ).init_log()
+            ).with_command(
+                IOCommand::input(|| EXTERNAL_VALUE.clone())
+            ).unwrap().init_log()
         }
     );
 
@@ -116,9 +116,9 @@ fn main() {
             "test mock cooling device",
             OUTPUT_ID,
             IOKind::Temperature,
-        ).set_command(
-            IOCommand::Output(|val| Ok(println!("\nSimulated HW Output: {}\n", val)))
-        ).init_log()
+        ).with_command(
+            IOCommand::output(|val| Ok(println!("\nSimulated HW Output: {}\n", val)))
+        ).unwrap().init_log()
     );
 
     build_actions(&mut poller);
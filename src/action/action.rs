@@ -1,8 +1,15 @@
 use crate::io::{IOEvent, Output, RawValue};
 use std::ops::DerefMut;
+use std::sync::TryLockError;
 use crate::helpers::Def;
 
-pub type BoxedAction = Box<dyn Action>;
+/// Owned [`Action`] trait object
+///
+/// Actions must be [`Send`] so that they may be evaluated on a background thread by
+/// [`crate::action::Publisher::subscribe_queued()`]. This is a breaking change for implementors
+/// which hold non-[`Send`] state (ie: [`std::rc::Rc`]); such state should be replaced by its
+/// thread-safe counterpart (ie: [`std::sync::Arc`]).
+pub type BoxedAction = Box<dyn Action + Send>;
 
 /// Trait that enables actions to be performed based on incoming data.
 ///
@@ -19,6 +26,21 @@ pub trait Action {
     /// - `data`: Raw incoming data from input device.
     fn evaluate(&mut self, data: &IOEvent);
 
+    /// Evaluate multiple events at once
+    ///
+    /// By default, [`Action::evaluate()`] is called on each event in order. Actions which operate
+    /// on a window of data (ie: to compute a trend) should override this to process the batch as
+    /// a whole.
+    ///
+    /// # Parameters
+    ///
+    /// - `events`: Raw incoming data from input device in chronological order
+    fn evaluate_batch(&mut self, events: &[IOEvent]) {
+        for event in events {
+            self.evaluate(event);
+        }
+    }
+
     /// Builder function for setting `output` field.
     ///
     /// # Parameters
@@ -37,6 +59,9 @@ pub trait Action {
 
     /// Setter function for output device field
     ///
+    /// If output device is currently locked elsewhere (ie: by another thread), a warning is
+    /// logged and `value` is not written. A poisoned lock is recovered.
+    ///
     /// # Parameters
     ///
     /// - `value`: Binary value to send to device
@@ -49,18 +74,25 @@ pub trait Action {
         let output = self.output()
             .expect("Action has no associated output device");
 
-        let mut binding = output.try_lock().unwrap();
+        let mut binding = match output.try_lock() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => {
+                log::warn!("Output for '{}' is busy. Skipping write of {}", self.name(), value);
+                return;
+            }
+        };
         let device = binding.deref_mut();
 
         device.write(value)
             .expect("Unexpected error when writing to output device.");
     }
 
-    /// Print notification to stdout.
+    /// Emit notification at `info` level via the [`log`] facade.
     ///
     /// This should be controlled by an internal option flag.
     fn notify(&self, msg: &str) {
-        println!("{}", msg);
+        log::info!("{}", msg);
     }
 
     /// Consume [`Self`] and wrap in a [`Box`] so it can be coerced into an [`Action`] trait object.
@@ -0,0 +1,240 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::action::{Action, BoxedAction};
+use crate::action::trigger::Trigger;
+use crate::helpers::Def;
+use crate::io::{IOEvent, Output, RawValue};
+
+/// Safety interlock which holds output in a safe state until reset
+///
+/// When incoming data exceeds `limit`, `safe_state` is written to output and the latch is set.
+/// While latched, all incoming data is ignored, even once the condition has cleared. The latch is
+/// only cleared by [`Latch::reset()`] or [`LatchHandle::reset()`].
+///
+/// Since actions are boxed when subscribed to a [`crate::action::Publisher`], a [`LatchHandle`]
+/// should be retrieved by [`Latch::handle()`] beforehand so that the latch can be inspected and
+/// reset afterwards.
+///
+/// # Example
+///
+/// ```
+/// use sensd::action::{Action, actions, IOCommand, Trigger};
+/// use sensd::io::{Device, IOEvent, Output, RawValue};
+///
+/// let heater = Output::default()
+///     .with_command(IOCommand::output(|_| Ok(()))).unwrap()
+///     .into_deferred();
+/// let mut latch = actions::Latch::new("over-temperature", RawValue::Float(40.0), Trigger::GT,
+///                                     RawValue::Binary(false))
+///     .set_output(heater);
+/// let handle = latch.handle();
+///
+/// latch.evaluate(&IOEvent::new(RawValue::Float(41.0)));
+/// assert!(handle.is_latched());
+///
+/// handle.reset();
+/// assert!(!latch.is_latched());
+/// ```
+pub struct Latch {
+    name: String,
+    limit: RawValue,
+    trigger: Trigger,
+
+    /// Value written to output when latch is set
+    safe_state: RawValue,
+    output: Option<Def<Output>>,
+
+    /// Shared with all [`LatchHandle`]s
+    latched: Arc<AtomicBool>,
+}
+
+/// Shared reference to state of a [`Latch`]
+///
+/// # See Also
+///
+/// - [`Latch::handle()`]
+#[derive(Clone)]
+pub struct LatchHandle(Arc<AtomicBool>);
+
+impl LatchHandle {
+    /// Check if associated [`Latch`] has been set
+    pub fn is_latched(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Clear associated [`Latch`] so that incoming data is evaluated again
+    ///
+    /// The output is not written to. It remains in the safe state until the next event is evaluated.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Release)
+    }
+}
+
+impl Latch {
+    /// Constructor for [`Latch`]
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: name of action
+    /// - `limit`: Value that sets latch when exceeded
+    /// - `trigger`: Defines the relationship between `limit` and external value
+    /// - `safe_state`: Value written to output when latch is set
+    ///
+    /// # Returns
+    ///
+    /// Initialized [`Latch`] action without `output` set, which is not latched.
+    ///
+    /// **Note**: [`Action::set_output()`] builder method should be chained after initialization.
+    pub fn new<N>(name: N, limit: RawValue, trigger: Trigger, safe_state: RawValue) -> Self
+    where
+        N: Into<String>
+    {
+        Self {
+            name: name.into(),
+            limit,
+            trigger,
+            safe_state,
+            output: None,
+            latched: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Getter for value which sets latch when exceeded
+    pub fn limit(&self) -> RawValue {
+        self.limit.clone()
+    }
+
+    /// Getter for value written to output when latch is set
+    pub fn safe_state(&self) -> RawValue {
+        self.safe_state.clone()
+    }
+
+    /// Check if latch has been set
+    pub fn is_latched(&self) -> bool {
+        self.latched.load(Ordering::Acquire)
+    }
+
+    /// Clear latch so that incoming data is evaluated again
+    ///
+    /// # See Also
+    ///
+    /// - [`LatchHandle::reset()`] for resetting after action has been boxed
+    pub fn reset(&mut self) {
+        self.latched.store(false, Ordering::Release)
+    }
+
+    /// Get a shared reference to latch state
+    ///
+    /// # Returns
+    ///
+    /// [`LatchHandle`] which remains valid after action is boxed
+    pub fn handle(&self) -> LatchHandle {
+        LatchHandle(self.latched.clone())
+    }
+}
+
+impl Action for Latch {
+    #[inline]
+    fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Evaluate external data
+    ///
+    /// If not latched and incoming data exceeds `limit`, the latch is set and `safe_state` is
+    /// written to output. Incoming data is ignored while latched.
+    ///
+    /// # Panics
+    ///
+    /// If latch is set and there is no associated output
+    fn evaluate(&mut self, data: &IOEvent) {
+        if self.is_latched() || !self.trigger.exceeded(&data.value, &self.limit) {
+            return;
+        }
+
+        self.latched.store(true, Ordering::Release);
+
+        let msg = format!("{} latched: {} {} {}", self.name, data.value, &self.trigger, self.limit);
+        self.notify(msg.as_str());
+
+        self.write(self.safe_state());
+    }
+
+    fn set_output(mut self, device: Def<Output>) -> Self
+    where
+        Self: Sized,
+    {
+        self.output = Some(device);
+
+        self
+    }
+
+    #[inline]
+    fn output(&self) -> Option<Def<Output>> {
+        self.output.clone()
+    }
+
+    #[inline]
+    fn into_boxed(self) -> BoxedAction {
+        Box::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::actions::Latch;
+    use crate::action::{Action, IOCommand, Trigger};
+    use crate::io::{Device, DeviceGetters, IOEvent, Output, RawValue};
+
+    #[test]
+    /// Ensure that latch holds safe state after condition clears, until reset
+    fn stays_latched() {
+        let output = Output::default()
+            .with_command(IOCommand::output(|_| Ok(()))).unwrap()
+            .into_deferred();
+        let mut latch = Latch::new("", RawValue::Float(10.0), Trigger::GT, RawValue::Binary(false))
+            .set_output(output.clone());
+        let state = || output.try_lock().unwrap().state().clone();
+
+        latch.evaluate(&IOEvent::new(RawValue::Float(9.0)));
+        assert!(!latch.is_latched());
+        assert_eq!(None, state());
+
+        latch.evaluate(&IOEvent::new(RawValue::Float(11.0)));
+        assert!(latch.is_latched());
+        assert_eq!(Some(RawValue::Binary(false)), state());
+
+        // output is overridden externally, but latch ignores subsequent events
+        output.try_lock().unwrap().write(RawValue::Binary(true)).unwrap();
+        for value in [9.0, 12.0, 5.0] {
+            latch.evaluate(&IOEvent::new(RawValue::Float(value)));
+            assert!(latch.is_latched());
+            assert_eq!(Some(RawValue::Binary(true)), state());
+        }
+
+        latch.reset();
+        assert!(!latch.is_latched());
+        latch.evaluate(&IOEvent::new(RawValue::Float(11.0)));
+        assert!(latch.is_latched());
+        assert_eq!(Some(RawValue::Binary(false)), state());
+    }
+
+    #[test]
+    /// Ensure that handle can reset latch after action is boxed
+    fn handle() {
+        let output = Output::default()
+            .with_command(IOCommand::output(|_| Ok(()))).unwrap()
+            .into_deferred();
+        let latch = Latch::new("", RawValue::Int(0), Trigger::LT, RawValue::Binary(false))
+            .set_output(output);
+        let handle = latch.handle();
+
+        let mut boxed = latch.into_boxed();
+        boxed.evaluate(&IOEvent::new(RawValue::Int(-1)));
+        assert!(handle.is_latched());
+
+        handle.reset();
+        assert!(!handle.is_latched());
+    }
+}
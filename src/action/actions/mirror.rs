@@ -0,0 +1,190 @@
+use crate::action::{Action, BoxedAction};
+use crate::helpers::Def;
+use crate::io::{IOEvent, Output, RawValue};
+
+/// Echo incoming data directly to an output device
+///
+/// Every [`IOEvent`] passed to [`Mirror::evaluate()`] has its value written to the associated
+/// [`Output`]. This is the most direct [`crate::action::Publisher`] to [`Output`] data path and is
+/// useful when an output should simply follow an input (eg: a manual switch driving a relay).
+///
+/// When `inverted` is set, [`RawValue::Binary`] values are negated before being written. All other
+/// variants are mirrored as-is.
+///
+/// # Example
+///
+/// ```
+/// use sensd::action::{Action, actions::Mirror};
+/// use sensd::action::IOCommand;
+/// use sensd::io::{Device, DeviceGetters, IOEvent, Output, RawValue};
+///
+/// let output = Output::default()
+///     .with_command(IOCommand::output(|_| Ok(()))).unwrap()
+///     .into_deferred();
+///
+/// let mut action = Mirror::new("", true)
+///     .set_output(output.clone());
+///
+/// action.evaluate(&IOEvent::new(RawValue::Binary(true)));
+///
+/// assert_eq!(Some(RawValue::Binary(false)), *output.try_lock().unwrap().state());
+/// ```
+pub struct Mirror {
+    name: String,
+    inverted: bool,
+
+    output: Option<Def<Output>>,
+}
+
+impl Mirror {
+    /// Constructor for [`Mirror`]
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: name of action
+    /// - `inverted`: negate binary values before writing to output
+    ///
+    /// # Returns
+    ///
+    /// Initialized [`Mirror`] action without `output` set.
+    ///
+    /// **Note**: [`Action::set_output()`] builder method should be chained after initialization.
+    pub fn new<N>(name: N, inverted: bool) -> Self
+    where
+        N: Into<String>
+    {
+        Self {
+            name: name.into(),
+            inverted,
+            output: None,
+        }
+    }
+
+    /// Constructor that accepts `output` parameter
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: name of action
+    /// - `inverted`: negate binary values before writing to output
+    /// - `output`: Output device
+    ///
+    /// # Returns
+    ///
+    /// Initialized [`Mirror`] action with `output` set.
+    pub fn with_output<N>(name: N, inverted: bool, output: Def<Output>) -> Self
+    where
+        N: Into<String>
+    {
+        Self::new(name, inverted).set_output(output)
+    }
+
+    #[inline]
+    /// Getter for `inverted` flag
+    pub fn inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Apply inversion to incoming value
+    ///
+    /// # Returns
+    ///
+    /// Negated value if `inverted` is set and value is binary. Otherwise, value is unchanged.
+    fn mirrored(&self, value: RawValue) -> RawValue {
+        match (self.inverted, &value) {
+            (true, RawValue::Binary(inner)) => RawValue::Binary(!inner),
+            _ => value,
+        }
+    }
+}
+
+impl Action for Mirror {
+    #[inline]
+    fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Write incoming value to output device
+    ///
+    /// # Panics
+    ///
+    /// - If no output has been set
+    fn evaluate(&mut self, data: &IOEvent) {
+        let value = self.mirrored(data.value.clone());
+        self.write(value);
+    }
+
+    fn set_output(mut self, device: Def<Output>) -> Self
+    where
+        Self: Sized,
+    {
+        self.output = Some(device);
+        self
+    }
+
+    #[inline]
+    fn output(&self) -> Option<Def<Output>> {
+        self.output.clone()
+    }
+
+    #[inline]
+    fn into_boxed(self) -> BoxedAction {
+        Box::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::actions::Mirror;
+    use crate::action::{Action, IOCommand};
+    use crate::io::{Device, DeviceGetters, IOEvent, Output, RawValue};
+
+    fn dummy_command() -> IOCommand {
+        IOCommand::output(|_| Ok(()))
+    }
+
+    #[test]
+    fn evaluate_mirrors_value() {
+        let output = Output::default().with_command(dummy_command()).unwrap().into_deferred();
+        let mut action = Mirror::with_output("", false, output.clone());
+
+        for value in [true, false, true] {
+            action.evaluate(&IOEvent::new(RawValue::Binary(value)));
+            assert_eq!(Some(RawValue::Binary(value)), *output.try_lock().unwrap().state());
+        }
+    }
+
+    #[test]
+    fn evaluate_inverts_binary() {
+        let output = Output::default().with_command(dummy_command()).unwrap().into_deferred();
+        let mut action = Mirror::with_output("", true, output.clone());
+
+        for value in [true, false, true] {
+            action.evaluate(&IOEvent::new(RawValue::Binary(value)));
+            assert_eq!(Some(RawValue::Binary(!value)), *output.try_lock().unwrap().state());
+        }
+
+        // non-binary values are not inverted
+        let value = RawValue::Float(1.5);
+        action.evaluate(&IOEvent::new(value.clone()));
+        assert_eq!(Some(value), *output.try_lock().unwrap().state());
+    }
+
+    #[test]
+    fn evaluate_busy_output() {
+        let output = Output::default().with_command(dummy_command()).unwrap().into_deferred();
+        let mut action = Mirror::with_output("", false, output.clone());
+
+        // write is skipped while output is locked elsewhere
+        let guard = output.try_lock().unwrap();
+        action.evaluate(&IOEvent::new(RawValue::Binary(true)));
+        drop(guard);
+        assert_eq!(None, *output.try_lock().unwrap().state());
+    }
+
+    #[test]
+    #[should_panic]
+    fn evaluate_wo_output() {
+        let mut action = Mirror::new("", false);
+        action.evaluate(&IOEvent::new(RawValue::Binary(true)));
+    }
+}
@@ -1,5 +1,9 @@
+mod latch;
+mod mirror;
 mod pid;
 mod threshold;
 
+pub use latch::{Latch, LatchHandle};
+pub use mirror::Mirror;
 pub use self::pid::PID;
 pub use threshold::Threshold;
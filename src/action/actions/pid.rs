@@ -488,8 +488,7 @@ impl Action for PID {
     }
 
     fn evaluate(&mut self, data: &IOEvent) {
-        let measurement = data.value;
-        if let RawValue::Float(value) = measurement {
+        if let RawValue::Float(value) = data.value {
 
             let duration =
                 self.calculate(value);
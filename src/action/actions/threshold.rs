@@ -5,9 +5,12 @@ use crate::helpers::Def;
 
 /// Bang-bang (on-off) controller
 ///
-/// If threshold is exceeded, a notification is printed and output is actuated until next polling cycle
-/// where input value is below threshold. In the future, upper and lower thresholds will be added for
-/// finer control.
+/// If threshold is exceeded, a notification is emitted and output is actuated until next polling cycle
+/// where input value is below threshold.
+///
+/// An optional hysteresis band prevents chatter when the input hovers near `threshold`. Once actuated,
+/// the output is only de-actuated when input no longer exceeds `threshold` offset by the band. See
+/// [`Threshold::set_hysteresis()`].
 ///
 /// Unlike the [`crate::action::actions::PID`] subscriber, [`Threshold`] is unable create a
 /// [`crate::action::Routine`].
@@ -21,13 +24,16 @@ use crate::helpers::Def;
 /// valve that decreases fill level. Two separate [`Threshold`] could be used for controlling
 /// this system based off of input from the level sensor. Depending on polling frequency there might be
 /// some variance between threshold value and the input value when actuation stops.
-// TODO: add upper/lower threshold
 pub struct Threshold {
     name: String,
     threshold: RawValue,
+    hysteresis: Option<RawValue>,
 
     trigger: Trigger,
     output: Option<Def<Output>>,
+
+    /// Last state written to output
+    active: bool,
 }
 
 impl Threshold {
@@ -66,8 +72,10 @@ impl Threshold {
         Self {
             name: name.into(),
             threshold,
+            hysteresis: None,
             trigger,
             output: None,
+            active: false,
         }
     }
 
@@ -126,10 +134,66 @@ impl Threshold {
     /// let output = Output::default().into_deferred();
     /// let action = actions::Threshold::new("", threshold, Trigger::GT);
     ///
-    /// assert_eq!(threshold, action.threshold())
+    /// assert_eq!(RawValue::Float(1.0), action.threshold())
     /// ```
     pub fn threshold(&self) -> RawValue {
-        self.threshold
+        self.threshold.clone()
+    }
+
+    /// Builder method for setting hysteresis band
+    ///
+    /// Once the output has been actuated, it is only de-actuated when input no longer exceeds
+    /// the "release" threshold: `threshold - delta` for [`Trigger::GT`] and [`Trigger::GTE`], or
+    /// `threshold + delta` for [`Trigger::LT`] and [`Trigger::LTE`].
+    ///
+    /// # Parameters
+    ///
+    /// - `delta`: Width of hysteresis band. Must be the same [`RawValue`] variant as `threshold`.
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `Self` to enable method chaining
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::RawValue;
+    /// use sensd::action::{actions, Trigger};
+    ///
+    /// let action = actions::Threshold::new("", RawValue::Float(10.0), Trigger::GT)
+    ///     .set_hysteresis(RawValue::Float(1.0));
+    ///
+    /// assert_eq!(Some(RawValue::Float(1.0)), action.hysteresis());
+    /// ```
+    pub fn set_hysteresis(mut self, delta: RawValue) -> Self {
+        self.hysteresis = Some(delta);
+        self
+    }
+
+    #[inline]
+    /// Getter for hysteresis band
+    ///
+    /// # Returns
+    ///
+    /// `None` if no hysteresis is used, otherwise width of band
+    pub fn hysteresis(&self) -> Option<RawValue> {
+        self.hysteresis.clone()
+    }
+
+    /// Threshold used to de-actuate output when output is active
+    ///
+    /// # Returns
+    ///
+    /// `threshold` offset by hysteresis band in the direction opposite to `trigger`. If no
+    /// hysteresis is set, `threshold` is returned as-is.
+    fn release_threshold(&self) -> RawValue {
+        match self.hysteresis() {
+            None => self.threshold(),
+            Some(delta) => match self.trigger {
+                Trigger::GT | Trigger::GTE => self.threshold() - delta,
+                Trigger::LT | Trigger::LTE => self.threshold() + delta,
+            },
+        }
     }
 
     #[inline]
@@ -163,13 +227,21 @@ impl Action for Threshold {
     /// incoming data exceeds threshold, output device is actuated. Otherwise, output device is
     /// deactivated.
     ///
+    /// While output is active, incoming data is compared against the release threshold instead
+    /// (see [`Threshold::set_hysteresis()`]).
+    ///
     /// # Notes
     ///
     /// - This function is inline because it is used in iterator loops
     /// - Any error returned by [`Self::write()`] is silenced.
     fn evaluate(&mut self, data: &IOEvent) {
-        let input = data.value;
-        let exceeded = self.trigger.exceeded(input, self.threshold);
+        let input = &data.value;
+        let threshold = match self.active {
+            true => self.release_threshold(),
+            false => self.threshold(),
+        };
+        let exceeded = self.trigger.exceeded(input, &threshold);
+        self.active = exceeded;
 
         match exceeded {
             true => {
@@ -230,8 +302,32 @@ impl Action for Threshold {
 #[cfg(test)]
 mod tests {
     use crate::action::actions::Threshold;
-    use crate::action::Trigger;
-    use crate::io::{Device, Output, RawValue};
+    use crate::action::{Action, IOCommand, Trigger};
+    use crate::io::{Device, DeviceGetters, IOEvent, Output, RawValue};
+
+    /// Slowly rising, then falling signal that wobbles around a threshold of 10.0
+    const SIGNAL: [f32; 16] = [
+        9.5, 9.8, 10.2, 9.9, 10.3, 10.1, 10.6, 11.0,
+        10.5, 9.7, 9.2, 9.4, 8.9, 9.3, 8.8, 9.6,
+    ];
+
+    /// Feed [`SIGNAL`] to action and count how many times output changes state
+    fn count_transitions(mut action: Threshold) -> usize {
+        let output = action.output().unwrap();
+        let mut last = None;
+        let mut transitions = 0;
+
+        for value in SIGNAL {
+            action.evaluate(&IOEvent::new(RawValue::Float(value)));
+
+            let state = output.try_lock().unwrap().state().clone();
+            if last.is_some() && last != state {
+                transitions += 1;
+            }
+            last = state;
+        }
+        transitions
+    }
 
     #[test]
     /// Ensure that `name` can be given to `new()` constructor as `String` or `&str`
@@ -253,4 +349,27 @@ mod tests {
         let name = String::from(name);
         Threshold::with_output(name, RawValue::default(), Trigger::GT, output);
     }
-}
\ No newline at end of file
+
+    #[test]
+    /// Ensure that hysteresis band prevents output from chattering
+    fn hysteresis() {
+        let output = Output::default()
+            .with_command(IOCommand::output(|_| Ok(()))).unwrap()
+            .into_deferred();
+        let action = Threshold::with_output("", RawValue::Float(10.0), Trigger::GT, output)
+            .set_hysteresis(RawValue::Float(1.0));
+
+        assert_eq!(2, count_transitions(action));
+    }
+
+    #[test]
+    /// Ensure that a bare threshold toggles on every wobble
+    fn without_hysteresis() {
+        let output = Output::default()
+            .with_command(IOCommand::output(|_| Ok(()))).unwrap()
+            .into_deferred();
+        let action = Threshold::with_output("", RawValue::Float(10.0), Trigger::GT, output);
+
+        assert!(count_transitions(action) > 2);
+    }
+}
@@ -1,23 +1,50 @@
-use crate::action::Routine;
+use crate::action::{Routine, SequenceRoutine};
 
 #[allow(unused_imports)]
 use crate::storage::Group;
 
+/// Callback invoked with a [`Routine`] which is dropped without being executed
+///
+/// # See Also
+///
+/// - [`SchedRoutineHandler::on_expired()`]
+pub type ExpiredCallback = Box<dyn Fn(&Routine) + Send + Sync>;
+
 #[derive(Default)]
 /// Wrapper for a collection of scheduled [`Routine`] instances that handles real-time execution
 /// Self-contained collection of scheduled [`Routine`]s for a single [`crate::action::Publisher`].
 ///
 /// This struct acts as a facade for an arbitrary collection (in this case, [`Vec`]).
-pub struct SchedRoutineHandler(Vec<Routine>);
+pub struct SchedRoutineHandler {
+    routines: Vec<Routine>,
+    sequences: Vec<SequenceRoutine>,
+
+    /// Called for each routine dropped by [`Routine::is_expired()`]
+    expired: Option<ExpiredCallback>,
+}
 
 impl SchedRoutineHandler {
     /// Push a new [`Routine`] to internal collection
     ///
+    /// Collection is kept ordered by [`Routine::priority()`] (highest first). Routines with equal
+    /// priority remain in insertion order.
+    ///
     /// # Parameters
     ///
     /// - `routine`: `Routine` to add to internal collection
     pub fn push(&mut self, routine: Routine) {
-        self.0.push(routine)
+        let index = self.routines
+            .partition_point(|scheduled| scheduled.priority() >= routine.priority());
+        self.routines.insert(index, routine)
+    }
+
+    /// Push a new [`SequenceRoutine`] to internal collection
+    ///
+    /// # Parameters
+    ///
+    /// - `sequence`: `SequenceRoutine` to add to internal collection
+    pub fn push_sequence(&mut self, sequence: SequenceRoutine) {
+        self.sequences.push(sequence)
     }
 
     /// Attempt to execute scheduled routines.
@@ -28,18 +55,53 @@ impl SchedRoutineHandler {
     /// should be called as often as possible, and outside of normal polling cycle,
     /// to produce a real-time response.
     ///
+    /// When several routines are due, they are executed in order of [`Routine::priority()`].
+    ///
     /// Any routines executed by [`Routine::attempt()`] are cleared from the internal container.
-    pub fn attempt_routines(&mut self) {
-        let mut executed = Vec::default();
-        for (index, routine) in self.0.iter().enumerate() {
+    /// Likewise, [`SequenceRoutine`]s are cleared once their last step has been executed.
+    ///
+    /// Routines which fail and have exceeded their maximum attempts or expiration time (see
+    /// [`Routine::is_expired()`]) are also cleared, so that a permanently failing command does not
+    /// remain queued. The callback set by [`SchedRoutineHandler::on_expired()`] is invoked for
+    /// each of them.
+    ///
+    /// # Returns
+    ///
+    /// Number of routines executed and sequences completed. Expired routines are not counted.
+    pub fn attempt_routines(&mut self) -> usize {
+        let mut executed = 0;
+
+        // remove completed and expired routines
+        let expired = &self.expired;
+        self.routines.retain_mut(|routine| {
             if routine.attempt() {
-                executed.push(index);
+                executed += 1;
+                return false;
             }
-        }
-        // remove completed routines
-        for index in executed {
-            self.0.remove(index);
-        }
+            if routine.is_expired() {
+                log::warn!("Dropping expired routine after {} attempts", routine.attempts());
+                if let Some(callback) = expired {
+                    callback(routine);
+                }
+                return false;
+            }
+            true
+        });
+
+        let pending = self.sequences.len();
+        self.sequences.retain_mut(|sequence| !sequence.attempt());
+
+        executed + pending - self.sequences.len()
+    }
+
+    /// Set callback which is invoked when a routine is dropped without being executed
+    ///
+    /// # Parameters
+    ///
+    /// - `callback`: Called with each routine removed by [`SchedRoutineHandler::attempt_routines()`]
+    ///   because of [`Routine::is_expired()`]
+    pub fn on_expired(&mut self, callback: ExpiredCallback) {
+        self.expired = Some(callback)
     }
 
     /// Getter function for internal collection
@@ -48,7 +110,16 @@ impl SchedRoutineHandler {
     ///
     /// Slice of [`Routine`]
     pub fn scheduled(&self) -> &[Routine] {
-        &self.0
+        &self.routines
+    }
+
+    /// Getter function for internal collection of sequences
+    ///
+    /// # Returns
+    ///
+    /// Slice of pending [`SequenceRoutine`]
+    pub fn sequences(&self) -> &[SequenceRoutine] {
+        &self.sequences
     }
 }
 
@@ -58,6 +129,7 @@ mod tests {
 
     use crate::{
         action::{IOCommand, Routine, SchedRoutineHandler},
+        clock::MockClock,
         helpers::Def,
         io::{DeviceMetadata, RawValue},
         storage::Log,
@@ -68,7 +140,7 @@ mod tests {
         let metadata = DeviceMetadata::default();
         let log = Def::new(Log::with_metadata(&metadata));
 
-        let command = IOCommand::Output(|_| Ok(()));
+        let command = IOCommand::output(|_| Ok(()));
 
         let timestamp = Utc::now() + Duration::microseconds(5);
         let value = RawValue::Binary(true);
@@ -85,7 +157,7 @@ mod tests {
         let metadata = DeviceMetadata::default();
         let log = Def::new(Log::with_metadata(&metadata));
 
-        let command = IOCommand::Output(|_| Ok(()));
+        let command = IOCommand::output(|_| Ok(()));
 
         let timestamp = Utc::now() + Duration::microseconds(5);
         let value = RawValue::Binary(true);
@@ -97,15 +169,41 @@ mod tests {
     }
 
     #[test]
-    /// Sometimes this fails due to race condition mentioned below (issue #95). In that case,
-    /// running the tests again should pass.
+    fn priority() {
+        use std::sync::Mutex;
+        static EXECUTED: Mutex<Vec<bool>> = Mutex::new(Vec::new());
+
+        let command = IOCommand::output(|value| {
+            if let RawValue::Binary(value) = value {
+                EXECUTED.lock().unwrap().push(value);
+            }
+            Ok(())
+        });
+        let timestamp = Utc::now();
+
+        let mut scheduled = SchedRoutineHandler::default();
+        // low priority routine is inserted first
+        scheduled.push(Routine::new(timestamp, RawValue::Binary(false), None, command.clone()));
+        scheduled.push(Routine::new(timestamp, RawValue::Binary(true), None, command)
+            .set_priority(10));
+
+        assert_eq!(10, scheduled.scheduled()[0].priority());
+
+        assert_eq!(2, scheduled.attempt_routines());
+        assert_eq!(vec![true, false], *EXECUTED.lock().unwrap());
+        assert_eq!(0, scheduled.scheduled().len());
+    }
+
+    #[test]
     fn test_attempt() {
+        let clock = MockClock::new(Utc::now());
+
         let metadata = DeviceMetadata::default();
         let log = Def::new(Log::with_metadata(&metadata));
 
-        let command = IOCommand::Output(|_| Ok(()));
+        let command = IOCommand::output(|_| Ok(()));
 
-        let timestamp = Utc::now() + Duration::microseconds(30);
+        let timestamp = clock.now() + Duration::microseconds(30);
         let value = RawValue::Binary(true);
 
         let routine = Routine::new(timestamp, value, log.clone(), command);
@@ -118,26 +216,67 @@ mod tests {
         let metadata = DeviceMetadata::default();
         let log = Def::new(Log::with_metadata(&metadata));
 
-        let command = IOCommand::Output(|_| Ok(()));
+        let command = IOCommand::output(|_| Ok(()));
 
-        // BUG: why does this operation fail with any value less than 31 microseconds? There seems
-        // to be a race condition.
-        let ts2 = Utc::now() + Duration::microseconds(120);
+        let ts2 = clock.now() + Duration::microseconds(120);
         let value = RawValue::Binary(true);
 
         let routine = Routine::new(ts2, value, log.clone(), command);
         scheduled.push(routine);
 
-        while Utc::now() < timestamp {
-            assert_eq!(2, scheduled.scheduled().into_iter().count());
-            scheduled.attempt_routines();
-        }
-        scheduled.attempt_routines();
-        while Utc::now() < ts2 {
-            assert_eq!(1, scheduled.scheduled().into_iter().count());
-            scheduled.attempt_routines();
+        assert_eq!(0, scheduled.attempt_routines());
+        assert_eq!(2, scheduled.scheduled().len());
+
+        clock.set(timestamp - Duration::microseconds(1));
+        assert_eq!(0, scheduled.attempt_routines());
+        assert_eq!(2, scheduled.scheduled().len());
+
+        clock.set(timestamp);
+        assert_eq!(1, scheduled.attempt_routines());
+        assert_eq!(1, scheduled.scheduled().len());
+
+        clock.set(ts2 - Duration::microseconds(1));
+        assert_eq!(0, scheduled.attempt_routines());
+        assert_eq!(1, scheduled.scheduled().len());
+
+        clock.advance(Duration::microseconds(1));
+        assert_eq!(1, scheduled.attempt_routines());
+        assert_eq!(0, scheduled.scheduled().len());
+    }
+
+    #[test]
+    fn expiration() {
+        use std::sync::{Arc, Mutex};
+
+        let failing = IOCommand::output(|_| Err(()));
+        let timestamp = Utc::now();
+
+        let mut scheduled = SchedRoutineHandler::default();
+        let dropped = Arc::new(Mutex::new(Vec::new()));
+        let attempts = dropped.clone();
+        scheduled.on_expired(Box::new(move |routine| attempts.lock().unwrap().push(routine.attempts())));
+
+        scheduled.push(Routine::new(timestamp, RawValue::Binary(true), None, failing.clone())
+            .set_max_attempts(3));
+        // routines without limits are retried indefinitely
+        scheduled.push(Routine::new(timestamp, RawValue::Binary(true), None, failing.clone()));
+
+        for _ in 0..2 {
+            assert_eq!(0, scheduled.attempt_routines());
+            assert_eq!(2, scheduled.scheduled().len());
         }
-        scheduled.attempt_routines();
-        assert_eq!(0, scheduled.scheduled().into_iter().count());
+        assert_eq!(0, scheduled.attempt_routines());
+        assert_eq!(1, scheduled.scheduled().len());
+        assert_eq!(vec![3], *dropped.lock().unwrap());
+        assert_eq!(None, scheduled.scheduled()[0].max_attempts());
+
+        // expired routines are dropped
+        let routine = Routine::new(timestamp, RawValue::Binary(true), None, failing)
+            .set_expiration(timestamp);
+        assert!(routine.is_expired());
+        scheduled.push(routine);
+        assert_eq!(0, scheduled.attempt_routines());
+        assert_eq!(1, scheduled.scheduled().len());
+        assert_eq!(vec![3, 0], *dropped.lock().unwrap());
     }
 }
@@ -1,33 +1,201 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use chrono::Duration;
 use crate::action::Command;
 use crate::errors::DeviceError;
-use crate::io::{IODirection, RawValue};
+use crate::io::{IODirection, IOKind, RawValue};
+
+/// Shared low-level function which reads HW input
+pub type InputFn = Arc<dyn Fn() -> RawValue + Send + Sync>;
+
+/// Shared low-level function which reads several values of differing kinds from a composite sensor
+pub type MultiInputFn = Arc<dyn Fn() -> Vec<(IOKind, RawValue)> + Send + Sync>;
+
+/// Shared low-level function which writes to HW output
+pub type OutputFn = Arc<dyn Fn(RawValue) -> Result<(), ()> + Send + Sync>;
 
 /// Command design pattern for storing low-level I/O code
 ///
 /// Should be used as an interface for HAL code and otherwise perform no other logic.
-#[derive(Clone, PartialEq)]
+///
+/// Low-level code is stored as closures so that drivers may capture their hardware handle (ie: an
+/// I2C bus or GPIO pin object). Since commands are cloned between devices and may be executed on a
+/// worker thread (see [`IOCommand::execute_timeout()`]), closures must be `Send + Sync + 'static`
+/// and are shared behind an [`Arc`]. Therefore, cloning a command does not clone captured state,
+/// and two commands are only equal if they share the same closure.
+///
+/// [`IOCommand::input()`], [`IOCommand::multi_input()`], [`IOCommand::output()`] and
+/// [`IOCommand::bidirectional()`] should be used for construction.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use sensd::action::{Command, IOCommand};
+/// use sensd::io::RawValue;
+///
+/// // captured peripheral handle
+/// let pin = Arc::new(Mutex::new(false));
+///
+/// let handle = pin.clone();
+/// let command = IOCommand::output(move |value| {
+///     *handle.lock().unwrap() = value == RawValue::Binary(true);
+///     Ok(())
+/// });
+///
+/// command.execute(RawValue::Binary(true)).unwrap();
+/// assert!(*pin.lock().unwrap());
+/// ```
+#[derive(Clone)]
 pub enum IOCommand {
     /// Low-level code to read HW input
-    Input(fn() -> RawValue),
+    Input(InputFn),
     /// Low-level code to write to HW output
     ///
     /// # Returns
     /// `Err` is returned if `RawValue` variant is incorrect. Otherwise, `Ok` is returned by
     /// default.
-    Output(fn(RawValue) -> Result<(), ()>),
+    Output(OutputFn),
+    /// Low-level code for HW that can be both read from and written to (eg: a valve that reports
+    /// position and accepts a setpoint)
+    ///
+    /// The first function reads HW state, the second writes to HW. See [`IOCommand::Input`] and
+    /// [`IOCommand::Output`] respectively.
+    Bidirectional(InputFn, OutputFn),
+    /// Low-level code to read several values from a composite sensor in a single transaction
+    /// (eg: temperature, humidity, and pressure from a BME280)
+    ///
+    /// Each value is labeled by its kind. See [`crate::io::Input::read_all()`].
+    MultiInput(MultiInputFn),
 }
 
 impl IOCommand {
+    /// Constructor for [`IOCommand::Input`]
+    ///
+    /// # Parameters
+    ///
+    /// - `read`: Low-level code which reads HW input
+    pub fn input<F>(read: F) -> Self
+    where
+        F: Fn() -> RawValue + Send + Sync + 'static
+    {
+        IOCommand::Input(Arc::new(read))
+    }
+
+    /// Constructor for [`IOCommand::MultiInput`]
+    ///
+    /// # Parameters
+    ///
+    /// - `read`: Low-level code which reads all values of a composite sensor
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::action::IOCommand;
+    /// use sensd::io::{IOKind, RawValue};
+    ///
+    /// let command = IOCommand::multi_input(|| vec![
+    ///     (IOKind::Temperature, RawValue::Float(21.5)),
+    ///     (IOKind::RelativeHumidity, RawValue::Float(48.0)),
+    /// ]);
+    ///
+    /// assert_eq!(2, command.execute_multi().unwrap().len());
+    /// ```
+    pub fn multi_input<F>(read: F) -> Self
+    where
+        F: Fn() -> Vec<(IOKind, RawValue)> + Send + Sync + 'static
+    {
+        IOCommand::MultiInput(Arc::new(read))
+    }
+
+    /// Constructor for [`IOCommand::Output`]
+    ///
+    /// # Parameters
+    ///
+    /// - `write`: Low-level code which writes to HW output
+    pub fn output<F>(write: F) -> Self
+    where
+        F: Fn(RawValue) -> Result<(), ()> + Send + Sync + 'static
+    {
+        IOCommand::Output(Arc::new(write))
+    }
+
+    /// Constructor for [`IOCommand::Bidirectional`]
+    ///
+    /// # Parameters
+    ///
+    /// - `read`: Low-level code which reads HW state
+    /// - `write`: Low-level code which writes to HW
+    pub fn bidirectional<R, W>(read: R, write: W) -> Self
+    where
+        R: Fn() -> RawValue + Send + Sync + 'static,
+        W: Fn(RawValue) -> Result<(), ()> + Send + Sync + 'static
+    {
+        IOCommand::Bidirectional(Arc::new(read), Arc::new(write))
+    }
+
+    /// Input command that always succeeds without interacting with hardware
+    ///
+    /// Intended for testing and placeholder devices.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: Value which is always returned
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::action::{Command, IOCommand};
+    /// use sensd::io::RawValue;
+    ///
+    /// let command = IOCommand::null_input(RawValue::Float(6.5));
+    ///
+    /// assert_eq!(Some(RawValue::Float(6.5)), command.execute(None).unwrap());
+    /// ```
+    pub fn null_input(value: RawValue) -> Self {
+        IOCommand::input(move || value.clone())
+    }
+
+    /// Output command that always succeeds and discards given value
+    ///
+    /// Intended for testing and placeholder devices.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::action::{Command, IOCommand};
+    /// use sensd::io::RawValue;
+    ///
+    /// let command = IOCommand::null_output();
+    ///
+    /// assert_eq!(None, command.execute(RawValue::Binary(true)).unwrap());
+    /// ```
+    pub fn null_output() -> Self {
+        IOCommand::output(|_| Ok(()))
+    }
+
+    /// Check if command can write to HW
+    ///
+    /// # Returns
+    ///
+    /// `true` for [`IOCommand::Output`] and [`IOCommand::Bidirectional`]
     pub fn is_output(&self) -> bool {
         match self {
-            Self::Input(_) => false,
-            Self::Output(_) => true,
+            Self::Input(_) | Self::MultiInput(_) => false,
+            Self::Output(_) | Self::Bidirectional(..) => true,
         }
     }
 
+    /// Check if command can read from HW
+    ///
+    /// # Returns
+    ///
+    /// `true` for [`IOCommand::Input`], [`IOCommand::MultiInput`] and [`IOCommand::Bidirectional`]
     pub fn is_input(&self) -> bool {
         match self {
-            Self::Input(_) => true,
+            Self::Input(_) | Self::MultiInput(_) | Self::Bidirectional(..) => true,
             Self::Output(_) => false,
         }
     }
@@ -37,13 +205,17 @@ impl IOCommand {
     /// Used to verify device type aligns with function intention: input with input, vice versa.
     pub fn direction(&self) -> IODirection {
         match self {
-            IOCommand::Input(_) => IODirection::In,
+            IOCommand::Input(_) | IOCommand::MultiInput(_) => IODirection::In,
             IOCommand::Output(_) => IODirection::Out,
+            IOCommand::Bidirectional(..) => IODirection::InOut,
         }
     }
 
     /// Validation to check agreement between command and external [`IODirection`]
     ///
+    /// [`IOCommand::Bidirectional`] agrees with any direction since it can be used by both
+    /// input and output devices.
+    ///
     /// # Parameters
     ///
     /// - `direction`: external direction to check against internal variant
@@ -54,37 +226,195 @@ impl IOCommand {
     /// - `Ok` if internal variant agrees with external direction
     /// - `Err` if internal variant disagrees with external direction
     pub fn agrees(&self, direction: IODirection) -> Result<(), ()> {
-        match direction == self.direction() {
+        match direction == self.direction() || self.direction() == IODirection::InOut {
             true => Ok(()),
             false => Err(())
         }
     }
 }
 
+impl IOCommand {
+    /// Execute command on a worker thread, abandoning it if it does not complete in time
+    ///
+    /// This guards the polling loop against low-level code that hangs (eg: a stalled I2C read).
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: Arbitrary value to pass to output. See [`Command::execute()`].
+    /// - `timeout`: Maximum duration to wait for command to complete. Negative values are treated
+    ///   as zero.
+    ///
+    /// # Returns
+    ///
+    /// Same as [`Command::execute()`], except that [`DeviceError::Timeout`] is returned if command
+    /// does not complete within `timeout`.
+    ///
+    /// [`DeviceError::HWFault`] is returned if the command panics on the worker thread (ie: no
+    /// value is passed to [`IOCommand::Output`]).
+    ///
+    /// # Notes
+    ///
+    /// A command that times out is not cancelled: the worker thread is detached and continues until
+    /// the low-level function returns, which leaks the thread if the function never returns. To
+    /// bound the number of detached threads to one per command, [`DeviceError::Busy`] is returned
+    /// without calling the function while a previous call of the same command (or any clone of
+    /// it) is still running.
+    pub fn execute_timeout<V>(&self, value: V, timeout: Duration) -> Result<Option<RawValue>, DeviceError>
+    where
+        V: Into<Option<RawValue>>
+    {
+        let command = self.clone();
+        let value = value.into();
+        run_timeout(self.address(), move || command.execute(value), timeout)?
+    }
+
+    /// Read all values of a composite sensor
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with values labeled by kind. [`IOCommand::Input`] and [`IOCommand::Bidirectional`]
+    ///   return a single value labeled as [`IOKind::Unassigned`].
+    /// - `Err` with [`DeviceError::ValueExpected`] if command cannot read
+    pub fn execute_multi(&self) -> Result<Vec<(IOKind, RawValue)>, DeviceError> {
+        match self {
+            Self::MultiInput(read) => Ok(read()),
+            Self::Input(read) | Self::Bidirectional(read, _) => Ok(vec![(IOKind::Unassigned, read())]),
+            Self::Output(_) => Err(DeviceError::ValueExpected {metadata: Default::default()}),
+        }
+    }
+
+    /// Execute [`IOCommand::execute_multi()`] on a worker thread
+    ///
+    /// See [`IOCommand::execute_timeout()`] for notes regarding abandoned commands.
+    pub fn execute_multi_timeout(&self, timeout: Duration) -> Result<Vec<(IOKind, RawValue)>, DeviceError> {
+        let command = self.clone();
+        run_timeout(self.address(), move || command.execute_multi(), timeout)?
+    }
+
+    /// Address of internal function, which identifies a command and all of its clones
+    fn address(&self) -> usize {
+        match self {
+            Self::Input(read) | Self::Bidirectional(read, _) => Arc::as_ptr(read) as *const () as usize,
+            Self::MultiInput(read) => Arc::as_ptr(read) as *const () as usize,
+            Self::Output(write) => Arc::as_ptr(write) as *const () as usize,
+        }
+    }
+}
+
+/// Addresses of commands whose worker thread spawned by [`run_timeout()`] is still running
+static OUTSTANDING: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+/// Marks a command as outstanding until dropped
+struct Outstanding(usize);
+
+impl Outstanding {
+    /// Mark command as outstanding
+    ///
+    /// # Returns
+    ///
+    /// `None` if command is already outstanding
+    fn acquire(address: usize) -> Option<Self> {
+        let mut outstanding = OUTSTANDING.lock().unwrap_or_else(PoisonError::into_inner);
+        if outstanding.contains(&address) {
+            return None;
+        }
+        outstanding.push(address);
+        Some(Self(address))
+    }
+}
+
+impl Drop for Outstanding {
+    fn drop(&mut self) {
+        OUTSTANDING.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|address| *address != self.0);
+    }
+}
+
+/// Run `f` on a worker thread and wait at most `timeout` for its result
+///
+/// # Parameters
+///
+/// - `address`: Identity of command, as returned by [`IOCommand::address()`]
+/// - `f`: Function to run
+/// - `timeout`: Maximum duration to wait
+///
+/// # Returns
+///
+/// A `Result` containing:
+///
+/// - `Ok` with result of `f`
+/// - `Err` with [`DeviceError::Busy`] if a previous call with `address` is still running,
+///   [`DeviceError::Timeout`] if `f` did not complete in time, or [`DeviceError::HWFault`] if
+///   `f` panicked
+fn run_timeout<F, T>(address: usize, f: F, timeout: Duration) -> Result<T, DeviceError>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let outstanding = Outstanding::acquire(address).ok_or(DeviceError::Busy)?;
+    let (sender, receiver) = channel();
+
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(f));
+        // release before sending so that a caller which received a result may call again
+        drop(outstanding);
+        if let Ok(result) = result {
+            // receiver is dropped if timeout has elapsed
+            let _ = sender.send(result);
+        }
+    });
+
+    match receiver.recv_timeout(timeout.to_std().unwrap_or_default()) {
+        Ok(result) => Ok(result),
+        Err(RecvTimeoutError::Timeout) => Err(DeviceError::Timeout),
+        Err(RecvTimeoutError::Disconnected) => Err(DeviceError::HWFault {metadata: Default::default()}),
+    }
+}
+
 impl Default for IOCommand {
     fn default() -> Self {
-        IOCommand::Output(|_| Ok(()))
+        IOCommand::null_output()
+    }
+}
+
+/// Commands are equal when they are the same variant and share the same closures
+impl PartialEq for IOCommand {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Input(x), Self::Input(y)) => Arc::ptr_eq(x, y),
+            (Self::Output(x), Self::Output(y)) => Arc::ptr_eq(x, y),
+            (Self::MultiInput(x), Self::MultiInput(y)) => Arc::ptr_eq(x, y),
+            (Self::Bidirectional(x_read, x_write), Self::Bidirectional(y_read, y_write)) => {
+                Arc::ptr_eq(x_read, y_read) && Arc::ptr_eq(x_write, y_write)
+            }
+            _ => false,
+        }
     }
 }
 
 impl Command<RawValue, DeviceError> for IOCommand {
     /// Execute internally stored function.
     ///
-    /// In summary, input command returns a value, output command accepts a value.
+    /// In summary, input command returns a value, output command accepts a value. A bidirectional
+    /// command writes when a value is given, and reads otherwise.
     ///
     /// # Parameters
     ///
-    /// - `value`: Arbitrary value to pass to output. If passed to an input, a warning is printed.
+    /// - `value`: Arbitrary value to pass to output. If passed to an input, a warning is logged.
     ///
     /// # Returns
     ///
     /// A `Result` containing:
     ///
     /// - `Ok` containing [`RawValue`] if internal function is [`IOCommand::Input`]. Otherwise, `None`
-    ///   since internal function is [`IOCommand::Output`].
+    ///   since internal function is [`IOCommand::Output`]. Only the first value read by
+    ///   [`IOCommand::MultiInput`] is returned; see [`IOCommand::execute_multi()`].
     ///
-    /// Currently, there is no scenario that returns `Err`. It is set as the return type to match
-    /// [`Input::read()`] and [`Output::write()`].
+    /// - `Err` with [`DeviceError::CommandFailed`] naming direction and value if an output
+    ///   function returns `Err`. Metadata of error is attached by the device.
     ///
     /// # Panics
     ///
@@ -105,32 +435,86 @@ impl Command<RawValue, DeviceError> for IOCommand {
             }
             Self::Output(inner) => {
                 let unwrapped_value = value.expect("No value was passed to write...");
-                let _ = inner(unwrapped_value); // TODO: handle bad result
+                inner(unwrapped_value.clone())
+                    .map_err(|_| command_failed(IODirection::Out, unwrapped_value))?;
 
                 Ok(None)
             }
+            Self::Bidirectional(read, write) => match value {
+                Some(inner) => {
+                    write(inner.clone()).map_err(|_| command_failed(IODirection::InOut, inner))?;
+                    Ok(None)
+                }
+                None => Ok(Some(read())),
+            },
+            Self::MultiInput(read) => {
+                value.is_some().then(unused_value);
+
+                Ok(read().into_iter().next().map(|(_, value)| value))
+            }
         }
     }
 }
 
-/// Print a warning on console stderr
+/// Error returned when a low-level write function fails
+fn command_failed(direction: IODirection, value: RawValue) -> DeviceError {
+    DeviceError::CommandFailed {metadata: Default::default(), direction, value}
+}
+
+/// Emit a warning via the [`log`] facade
 fn unused_value() {
     const MSG: &str = "Unused value passed when reading input...";
-    eprintln!("{}", MSG);
+    log::warn!("{}", MSG);
 }
 
 #[cfg(test)]
 mod tests {
+    use chrono::Duration;
     use crate::action::{Command, IOCommand};
-    use crate::io::{IODirection, RawValue};
+    use crate::errors::DeviceError;
+    use crate::io::{IODirection, IOKind, RawValue};
 
     #[test]
     #[should_panic]
     fn test_output_fails_wo_value() {
-        let command = IOCommand::Output(|_| Ok(()));
+        let command = IOCommand::output(|_| Ok(()));
         command.execute(None).unwrap();
     }
 
+    #[test]
+    fn command_failed() {
+        let command = IOCommand::output(|_| Err(()));
+        match command.execute(RawValue::Int(3)) {
+            Err(DeviceError::CommandFailed {direction, value, ..}) => {
+                assert_eq!(IODirection::Out, direction);
+                assert_eq!(RawValue::Int(3), value);
+            }
+            _ => panic!("expected CommandFailed"),
+        }
+
+        let command = IOCommand::bidirectional(|| RawValue::Float(0.5), |_| Err(()));
+        assert!(command.execute(None).is_ok());
+        assert!(matches!(command.execute(RawValue::Float(1.0)),
+            Err(DeviceError::CommandFailed {direction: IODirection::InOut, ..})));
+    }
+
+    #[test]
+    fn bidirectional() {
+        let command = IOCommand::bidirectional(|| RawValue::Float(0.5), |_| Ok(()));
+
+        assert!(command.is_input());
+        assert!(command.is_output());
+        assert_eq!(IODirection::InOut, command.direction());
+
+        assert!(command.agrees(IODirection::In).is_ok());
+        assert!(command.agrees(IODirection::Out).is_ok());
+        assert!(command.agrees(IODirection::InOut).is_ok());
+        assert!(IOCommand::null_input(RawValue::default()).agrees(IODirection::InOut).is_err());
+
+        assert_eq!(Some(RawValue::Float(0.5)), command.execute(None).unwrap());
+        assert_eq!(None, command.execute(RawValue::Float(1.0)).unwrap());
+    }
+
     #[test]
     fn test_default() {
         let command = IOCommand::default();
@@ -140,7 +524,7 @@ mod tests {
 
     #[test]
     fn test_agrees() {
-        let mut command = IOCommand::Output(|_| Ok(()));
+        let mut command = IOCommand::output(|_| Ok(()));
         assert_eq!((),
                    command.agrees(IODirection::Out)
                        .unwrap());
@@ -149,7 +533,7 @@ mod tests {
                        .err()
                        .unwrap());
 
-        command = IOCommand::Input(|| RawValue::default());
+        command = IOCommand::input(|| RawValue::default());
         assert_eq!((),
                    command.agrees(IODirection::In)
                        .unwrap());
@@ -158,4 +542,105 @@ mod tests {
                        .err()
                        .unwrap());
     }
+
+    #[test]
+    fn captured_state() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let reads = Arc::new(AtomicUsize::new(0));
+        let handle = reads.clone();
+        let command = IOCommand::input(move || {
+            RawValue::PosInt(handle.fetch_add(1, Ordering::SeqCst) as u32)
+        });
+
+        assert_eq!(Some(RawValue::PosInt(0)), command.execute(None).unwrap());
+
+        // clones share captured state
+        let cloned = command.clone();
+        assert!(cloned == command);
+        assert_eq!(Some(RawValue::PosInt(1)), cloned.execute(None).unwrap());
+        assert_eq!(2, reads.load(Ordering::SeqCst));
+
+        assert!(IOCommand::null_input(RawValue::default()) != IOCommand::null_input(RawValue::default()));
+    }
+
+    #[test]
+    fn multi_input() {
+        let command = IOCommand::multi_input(|| vec![
+            (IOKind::Temperature, RawValue::Float(21.5)),
+            (IOKind::Pressure, RawValue::Float(1013.0)),
+        ]);
+
+        assert!(command.is_input());
+        assert!(!command.is_output());
+        assert!(command.agrees(IODirection::In).is_ok());
+
+        assert_eq!(Some(RawValue::Float(21.5)), command.execute(None).unwrap());
+        assert_eq!(IOKind::Pressure, command.execute_multi().unwrap()[1].0);
+        assert_eq!(2, command.execute_multi_timeout(Duration::seconds(1)).unwrap().len());
+
+        assert_eq!(
+            vec![(IOKind::Unassigned, RawValue::Binary(true))],
+            IOCommand::input(|| RawValue::Binary(true)).execute_multi().unwrap());
+        assert!(IOCommand::null_output().execute_multi().is_err());
+    }
+
+    #[test]
+    fn execute_timeout() {
+        let command = IOCommand::input(|| RawValue::Binary(true));
+        assert_eq!(
+            Some(RawValue::Binary(true)),
+            command.execute_timeout(None, Duration::seconds(1)).unwrap());
+
+        let command = IOCommand::input(|| {
+            std::thread::sleep(std::time::Duration::from_millis(500));
+            RawValue::Binary(true)
+        });
+        assert!(matches!(
+            command.execute_timeout(None, Duration::milliseconds(10)),
+            Err(DeviceError::Timeout)));
+
+        // panics are returned as errors
+        let command = IOCommand::input(|| panic!("bus error"));
+        assert!(matches!(
+            command.execute_timeout(None, Duration::seconds(1)),
+            Err(DeviceError::HWFault {..})));
+    }
+
+    #[test]
+    fn execute_timeout_outstanding() {
+        use std::sync::{Arc, Mutex};
+
+        // command blocks until gate is released
+        let gate = Arc::new(Mutex::new(()));
+        let command = {
+            let gate = gate.clone();
+            IOCommand::input(move || {
+                let _guard = gate.lock();
+                RawValue::Binary(true)
+            })
+        };
+
+        let guard = gate.lock().unwrap();
+        assert!(matches!(
+            command.execute_timeout(None, Duration::milliseconds(10)),
+            Err(DeviceError::Timeout)));
+
+        // no thread is spawned while previous call is outstanding, including by clones
+        assert!(matches!(
+            command.clone().execute_timeout(None, Duration::seconds(1)),
+            Err(DeviceError::Busy)));
+        assert!(IOCommand::input(|| RawValue::Binary(false))
+            .execute_timeout(None, Duration::seconds(1)).is_ok());
+
+        drop(guard);
+        let result = loop {
+            match command.execute_timeout(None, Duration::seconds(1)) {
+                Err(DeviceError::Busy) => std::thread::yield_now(),
+                result => break result,
+            }
+        };
+        assert_eq!(Some(RawValue::Binary(true)), result.unwrap());
+    }
 }
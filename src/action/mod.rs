@@ -5,14 +5,17 @@ mod trigger;
 mod handler;
 mod io;
 mod publisher;
+mod queue;
 mod routine;
+mod sequence;
 
 pub mod actions;
 
 pub use action::{Action, BoxedAction};
 pub use command::*;
 pub use trigger::Trigger;
-pub use handler::SchedRoutineHandler;
+pub use handler::{ExpiredCallback, SchedRoutineHandler};
 pub use io::IOCommand;
-pub use publisher::Publisher;
+pub use publisher::{EventSource, Publisher};
 pub use routine::Routine;
+pub use sequence::SequenceRoutine;
@@ -1,8 +1,70 @@
 //! Implements a control system based off of evaluating incoming data.
 
+use std::collections::VecDeque;
+
+use crate::action::queue::{EventQueue, QueuedSubscription};
 use crate::action::{BoxedAction, SchedRoutineHandler};
 use crate::helpers::Def;
-use crate::io::IOEvent;
+use crate::io::{IOEvent, IOKind, RATE_TAG};
+use crate::settings::QUEUE_CAPACITY;
+
+/// Selects which events are passed to a subscriber of [`Publisher`]
+///
+/// # See Also
+///
+/// - [`Publisher::subscribe_to()`] for subscribing to a specific source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventSource {
+    /// Events of the device kind, as passed to [`Publisher::propagate()`]
+    #[default]
+    Primary,
+
+    /// Secondary values of the given kind read by a composite device, as passed to
+    /// [`Publisher::propagate_secondary()`]
+    Kind(IOKind),
+
+    /// Rate of change emitted by [`crate::io::Input::set_rate_of_change()`], tagged with
+    /// [`RATE_TAG`]
+    Rate,
+}
+
+impl EventSource {
+    /// Whether `event` is passed to subscribers of this source
+    ///
+    /// # Parameters
+    ///
+    /// - `event`: Incoming event
+    /// - `secondary`: Whether `event` is a secondary value of a composite device
+    pub(crate) fn accepts(&self, event: &IOEvent, secondary: bool) -> bool {
+        match self {
+            EventSource::Primary => !secondary,
+            EventSource::Kind(kind) => secondary && !event.has_tag(RATE_TAG) && event.kind == Some(*kind),
+            EventSource::Rate => secondary && event.has_tag(RATE_TAG),
+        }
+    }
+}
+
+/// Subscribed [`crate::action::Action`] and the source of events it receives
+///
+/// Disabled subscriptions are skipped during propagation but retain their action and source.
+pub(crate) struct Subscription {
+    pub action: BoxedAction,
+    pub source: EventSource,
+    pub enabled: bool,
+}
+
+impl Subscription {
+    pub fn new(action: BoxedAction, source: EventSource) -> Self {
+        Self { action, source, enabled: true }
+    }
+
+    /// Evaluate `event` if it is accepted by source and subscription is enabled
+    pub fn evaluate(&mut self, event: &IOEvent, secondary: bool) {
+        if self.enabled && self.source.accepts(event, secondary) {
+            self.action.evaluate(event);
+        }
+    }
+}
 
 #[derive(Default)]
 /// Handles storage and association between an [`Input`] and [`crate::action::Action`] instances
@@ -15,9 +77,36 @@ use crate::io::IOEvent;
 /// Additionally, [`Publisher`] maintains the internal collection of scheduled [`crate::action::Routine`]s
 /// for any number of output devices and provides [`Publisher::attempt_routines()`] for executing those
 /// scheduled commands at their scheduled time.
+///
+/// # Queued Subscribers
+///
+/// By default, subscribers are evaluated synchronously, which means that a slow subscriber blocks
+/// [`crate::io::Input::read()`] and therefore the polling loop. Subscribers added by
+/// [`Publisher::subscribe_queued()`] are instead evaluated on a background thread. Events are handed
+/// to the background thread through a bounded queue: when the queue is full, the oldest pending
+/// event is dropped and [`Publisher::dropped_count()`] is incremented.
+///
+/// # Event Sources
+///
+/// Composite devices (see [`crate::action::IOCommand::MultiInput`]) report several quantities per
+/// reading, all of which pass through the same [`Publisher`]. So that a subscriber only acts on
+/// the quantity it was configured for, every subscriber receives events from a single
+/// [`EventSource`]. Subscribers added by [`Publisher::subscribe()`] only receive events of the
+/// device kind, while [`Publisher::subscribe_to()`] selects another source.
+///
+/// # Event History
+///
+/// For debugging why an action did or did not fire, the most recent events passed to
+/// [`Publisher::propagate()`] may be retained by [`Publisher::set_history_capacity()`] and
+/// retrieved by [`Publisher::recent_events()`]. Unlike the device log, history is not affected by
+/// deadband suppression. History is disabled by default and does not affect propagation.
 pub struct Publisher {
-    actions: Vec<BoxedAction>,
+    subscriptions: Vec<Subscription>,
+    queue: Option<EventQueue>,
     scheduled: Def<SchedRoutineHandler>,
+    queue_capacity: Option<usize>,
+    history: VecDeque<IOEvent>,
+    history_capacity: usize,
 }
 
 impl Publisher {
@@ -35,42 +124,335 @@ impl Publisher {
     /// # Panics
     ///
     /// Panic is thrown if [`SchedRoutineHandler`] cannot be locked.
-    pub fn attempt_routines(&mut self) {
+    pub fn attempt_routines(&mut self) -> usize {
         self.scheduled.try_lock().unwrap().attempt_routines()
     }
 
     /// Get collection of subscribed [`crate::action::Action`]'s (stored as [`BoxedAction`]).
     ///
+    /// Queued subscribers are not included since they are owned by a background thread.
+    ///
     /// # Returns
     ///
-    /// Slice of all [`BoxedAction`] associated with `self`
-    pub fn subscribers(&self) -> &[BoxedAction] {
-        &self.actions
+    /// Iterator over all synchronous [`BoxedAction`] associated with `self`, regardless of
+    /// [`EventSource`]
+    pub fn subscribers(&self) -> impl ExactSizeIterator<Item = &BoxedAction> {
+        self.subscriptions.iter().map(|subscription| &subscription.action)
     }
 
     /// Add [`crate::action::Action`] to internal collection.
     ///
+    /// Subscriber only receives events of the device kind (ie: [`EventSource::Primary`]).
+    ///
     /// # Parameters
     ///
     /// - `subscriber`: [`BoxedAction`] to add to internal store.
     pub fn subscribe(&mut self, subscriber: BoxedAction) {
-        self.actions.push(subscriber)
+        self.subscribe_to(subscriber, EventSource::Primary)
+    }
+
+    /// Add [`crate::action::Action`] which receives events from a specific source
+    ///
+    /// # Parameters
+    ///
+    /// - `subscriber`: [`BoxedAction`] to add to internal store
+    /// - `source`: Events passed to `subscriber`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::action::{Action, EventSource, Publisher, Trigger};
+    /// use sensd::action::actions::Threshold;
+    /// use sensd::io::{IOKind, RawValue};
+    ///
+    /// // act on pressure reported by a composite temperature sensor
+    /// let mut publisher = Publisher::default();
+    /// let action = Threshold::new("storm", RawValue::Float(990.0), Trigger::LT);
+    /// publisher.subscribe_to(action.into_boxed(), EventSource::Kind(IOKind::Pressure));
+    /// ```
+    pub fn subscribe_to(&mut self, subscriber: BoxedAction, source: EventSource) {
+        self.subscriptions.push(Subscription::new(subscriber, source))
+    }
+
+    /// Add [`crate::action::Action`] which is evaluated on a background thread.
+    ///
+    /// If no queue exists, one is created with the capacity given to
+    /// [`Publisher::set_queue_capacity()`], or [`QUEUE_CAPACITY`] if none was given.
+    ///
+    /// # Parameters
+    ///
+    /// - `subscriber`: [`BoxedAction`] to add to background thread
+    ///
+    /// # See Also
+    ///
+    /// - [`Publisher::set_queue_capacity()`] for changing capacity of queue
+    pub fn subscribe_queued(&mut self, subscriber: BoxedAction) {
+        self.subscribe_queued_to(subscriber, EventSource::Primary)
+    }
+
+    /// Add [`crate::action::Action`] which receives events from a specific source on a background
+    /// thread
+    ///
+    /// # Parameters
+    ///
+    /// - `subscriber`: [`BoxedAction`] to add to background thread
+    /// - `source`: Events passed to `subscriber`
+    ///
+    /// # See Also
+    ///
+    /// - [`Publisher::subscribe_queued()`] for details on queued subscribers
+    pub fn subscribe_queued_to(&mut self, subscriber: BoxedAction, source: EventSource) {
+        self.queue
+            .get_or_insert_with(|| EventQueue::new(self.queue_capacity.unwrap_or(QUEUE_CAPACITY)))
+            .subscriptions()
+            .push(QueuedSubscription::new(subscriber, source))
+    }
+
+    /// Set maximum number of pending events for queued subscribers
+    ///
+    /// If a queue already exists, its capacity is changed without waiting for queued subscribers.
+    /// When more events are pending than `capacity`, the oldest are dropped and counted by
+    /// [`Publisher::dropped_count()`]. Otherwise, capacity is stored and the background thread is
+    /// not started until [`Publisher::subscribe_queued()`] is called.
+    ///
+    /// # Parameters
+    ///
+    /// - `capacity`: maximum number of pending events
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero
+    pub fn set_queue_capacity(&mut self, capacity: usize) {
+        assert!(capacity > 0, "Queue capacity must be non-zero");
+        self.queue_capacity = Some(capacity);
+
+        if let Some(queue) = &self.queue {
+            queue.set_capacity(capacity);
+        }
+    }
+
+    /// Number of events which were dropped because the queue was full
+    ///
+    /// # Returns
+    ///
+    /// Number of dropped events. This is always `0` when there are no queued subscribers.
+    pub fn dropped_count(&self) -> usize {
+        self.queue.as_ref().map_or(0, |queue| queue.dropped())
+    }
+
+    /// Remove [`crate::action::Action`] from internal collection.
+    ///
+    /// Subscribers are identified by [`crate::action::Action::name()`]. If multiple subscribers
+    /// share the same name, only the first is removed. Synchronous subscribers are searched before
+    /// queued subscribers.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of subscriber to remove
+    ///
+    /// # Returns
+    ///
+    /// `true` if a matching subscriber was removed, otherwise `false`
+    pub fn unsubscribe(&mut self, name: &str) -> bool {
+        let matches = |subscription: &Subscription| subscription.action.name() == name;
+
+        if let Some(index) = self.subscriptions.iter().position(matches) {
+            self.subscriptions.remove(index);
+            return true;
+        }
+
+        if let Some(queue) = &self.queue {
+            let mut subscriptions = queue.subscriptions();
+            if let Some(index) = subscriptions.iter().position(|subscription| subscription.name == name) {
+                subscriptions.remove(index);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Enable or disable subscribed [`crate::action::Action`] without unsubscribing
+    ///
+    /// Disabled subscribers are skipped by [`Publisher::propagate()`] and
+    /// [`Publisher::propagate_batch()`] until re-enabled. Subscribers are identified by
+    /// [`crate::action::Action::name()`] and all subscribers sharing the same name are affected.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of subscriber
+    /// - `enabled`: `false` to silence subscriber, `true` to resume propagation
+    ///
+    /// # Returns
+    ///
+    /// `true` if a matching subscriber was found, otherwise `false`
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        let mut found = false;
+        for subscription in self.subscriptions.iter_mut().filter(|s| s.action.name() == name) {
+            subscription.enabled = enabled;
+            found = true;
+        }
+
+        if let Some(queue) = &self.queue {
+            for subscription in queue.subscriptions().iter_mut().filter(|s| s.name == name) {
+                subscription.enabled = enabled;
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// Whether subscribed [`crate::action::Action`] is enabled
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of subscriber
+    ///
+    /// # Returns
+    ///
+    /// `Some` with flag of first subscriber matching `name`, or `None` if no subscriber matches
+    pub fn is_enabled(&self, name: &str) -> Option<bool> {
+        if let Some(subscription) = self.subscriptions.iter().find(|s| s.action.name() == name) {
+            return Some(subscription.enabled);
+        }
+
+        self.queue.as_ref().and_then(|queue| {
+            queue.subscriptions().iter()
+                .find(|s| s.name == name)
+                .map(|s| s.enabled)
+        })
+    }
+
+    /// Get names of all subscribed [`crate::action::Action`]'s
+    ///
+    /// # Returns
+    ///
+    /// Names of synchronous subscribers followed by queued subscribers, each in the order they
+    /// were subscribed
+    pub fn subscriber_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.subscriptions.iter()
+            .map(|subscription| subscription.action.name().clone())
+            .collect();
+        if let Some(queue) = &self.queue {
+            names.extend(queue.subscriptions().iter()
+                .map(|subscription| subscription.name.clone()));
+        }
+        names
     }
 
     /// Handle incoming data
     ///
-    /// [`crate::action::Action::evaluate()`] is called on all associated
-    /// [`crate::action::Action`] instances and incoming data is passed.
+    /// [`crate::action::Action::evaluate()`] is called on all synchronous
+    /// [`crate::action::Action`] instances subscribed to [`EventSource::Primary`] and incoming data
+    /// is passed. If there are queued subscribers, data is then added to the queue and this method
+    /// returns without waiting.
     ///
     /// # Parameters
     ///
     /// - `data`: Incoming [`IOEvent`] generated from [`crate::io::Input::read()`]
     pub fn propagate(&mut self, data: &IOEvent) {
-        for subscriber in self.actions.iter_mut() {
-            subscriber.evaluate(data);
+        self.dispatch(data, false)
+    }
+
+    /// Handle secondary value read by a composite device
+    ///
+    /// Behaves as [`Publisher::propagate()`], except that `data` is only passed to subscribers of
+    /// [`EventSource::Kind`] matching the kind of `data`, or to subscribers of
+    /// [`EventSource::Rate`] when `data` is tagged with [`RATE_TAG`].
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: Incoming [`IOEvent`] labeled by kind or tagged as rate, generated by
+    ///   [`crate::io::Input::read_all()`]
+    pub fn propagate_secondary(&mut self, data: &IOEvent) {
+        self.dispatch(data, true)
+    }
+
+    /// Pass `data` to all subscribers whose source accepts it
+    fn dispatch(&mut self, data: &IOEvent, secondary: bool) {
+        self.record(data);
+
+        for subscription in self.subscriptions.iter_mut() {
+            subscription.evaluate(data, secondary);
+        }
+
+        if let Some(queue) = &self.queue {
+            queue.push(data.clone(), secondary);
         }
     }
 
+    /// Handle multiple incoming events at once
+    ///
+    /// [`crate::action::Action::evaluate_batch()`] is called once on all synchronous
+    /// [`crate::action::Action`] instances subscribed to [`EventSource::Primary`] so that
+    /// subscribers may process the batch as a whole. Queued subscribers are evaluated on their
+    /// background thread one event at a time.
+    ///
+    /// # Parameters
+    ///
+    /// - `events`: Incoming [`IOEvent`]s in chronological order
+    pub fn propagate_batch(&mut self, events: &[IOEvent]) {
+        for event in events {
+            self.record(event);
+        }
+
+        let primary = self.subscriptions.iter_mut()
+            .filter(|subscription| subscription.source == EventSource::Primary);
+        for subscription in primary.filter(|subscription| subscription.enabled) {
+            subscription.action.evaluate_batch(events);
+        }
+
+        if let Some(queue) = &self.queue {
+            for event in events {
+                queue.push(event.clone(), false);
+            }
+        }
+    }
+
+    /// Set maximum number of events retained by [`Publisher::recent_events()`]
+    ///
+    /// When capacity is reduced, the oldest events are discarded.
+    ///
+    /// # Parameters
+    ///
+    /// - `capacity`: maximum number of retained events. `0` disables history.
+    pub fn set_history_capacity(&mut self, capacity: usize) {
+        self.history_capacity = capacity;
+        if self.history.len() > capacity {
+            self.history.drain(..self.history.len() - capacity);
+        }
+        self.history.shrink_to(capacity);
+    }
+
+    /// Getter for maximum number of events retained by [`Publisher::recent_events()`]
+    pub fn history_capacity(&self) -> usize {
+        self.history_capacity
+    }
+
+    /// Events most recently passed to [`Publisher::propagate()`] or [`Publisher::propagate_batch()`]
+    ///
+    /// # Returns
+    ///
+    /// Iterator over retained events ordered from oldest to newest. Iterator is empty when
+    /// history is disabled.
+    ///
+    /// # See Also
+    ///
+    /// - [`Publisher::set_history_capacity()`]
+    pub fn recent_events(&self) -> impl DoubleEndedIterator<Item = &IOEvent> + ExactSizeIterator {
+        self.history.iter()
+    }
+
+    /// Retain `event` in history, discarding oldest event when full
+    fn record(&mut self, event: &IOEvent) {
+        if self.history_capacity == 0 {
+            return;
+        }
+        if self.history.len() >= self.history_capacity {
+            self.history.pop_front();
+        }
+        self.history.push_back(event.clone());
+    }
+
     /// Method to get passable reference to internal handler
     ///
     /// This is used when an [`crate::action::Action`] needs to schedule
@@ -83,3 +465,311 @@ impl Publisher {
         self.scheduled.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use crate::action::actions::Mirror;
+    use crate::action::{Action, BoxedAction, Publisher};
+    use crate::helpers::Def;
+    use crate::io::{IOEvent, Output, RawValue};
+
+    /// Action which counts evaluated events once `gate` is released
+    ///
+    /// `entered` is set once evaluation has started waiting for `gate`.
+    struct GatedAction {
+        name: String,
+        gate: Arc<Mutex<()>>,
+        entered: Arc<AtomicBool>,
+        evaluated: Arc<AtomicUsize>,
+    }
+
+    impl GatedAction {
+        fn new(name: &str, evaluated: Arc<AtomicUsize>) -> Self {
+            Self { name: String::from(name), gate: Arc::default(), entered: Arc::default(), evaluated }
+        }
+    }
+
+    impl Action for GatedAction {
+        fn name(&self) -> &String {
+            &self.name
+        }
+
+        fn evaluate(&mut self, _data: &IOEvent) {
+            self.entered.store(true, Ordering::SeqCst);
+            let _gate = self.gate.lock().unwrap();
+            self.evaluated.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn set_output(self, _device: Def<Output>) -> Self {
+            self
+        }
+
+        fn output(&self) -> Option<Def<Output>> {
+            None
+        }
+
+        fn into_boxed(self) -> BoxedAction {
+            Box::new(self)
+        }
+    }
+
+    #[test]
+    fn queued_propagation() {
+        const EVENTS: usize = 6;
+
+        let evaluated = Arc::new(AtomicUsize::new(0));
+        let action = GatedAction::new("gated", evaluated.clone());
+        let gate = action.gate.clone();
+
+        let mut publisher = Publisher::default();
+        publisher.set_queue_capacity(2);
+        publisher.subscribe_queued(action.into_boxed());
+        assert_eq!(vec!["gated"], publisher.subscriber_names());
+
+        // propagation does not wait for blocked subscriber
+        let guard = gate.lock().unwrap();
+        for _ in 0..EVENTS {
+            publisher.propagate(&IOEvent::new(RawValue::Binary(true)));
+        }
+        assert_eq!(0, evaluated.load(Ordering::SeqCst));
+
+        // at most one event is held by worker and two are pending
+        let dropped = publisher.dropped_count();
+        assert!(dropped >= EVENTS - 3);
+
+        // dropping publisher processes pending events
+        drop(guard);
+        drop(publisher);
+        assert_eq!(EVENTS - dropped, evaluated.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn queue_capacity_wo_subscribers() {
+        let mut publisher = Publisher::default();
+
+        // capacity is stored without starting a queue
+        publisher.set_queue_capacity(1);
+        assert!(publisher.queue.is_none());
+
+        let evaluated = Arc::new(AtomicUsize::new(0));
+        let action = GatedAction::new("gated", evaluated.clone());
+        let gate = action.gate.clone();
+        publisher.subscribe_queued(action.into_boxed());
+
+        // stored capacity is used once queue is created
+        let guard = gate.lock().unwrap();
+        for _ in 0..3 {
+            publisher.propagate(&IOEvent::new(RawValue::Binary(true)));
+        }
+        assert!(publisher.dropped_count() >= 1);
+        drop(guard);
+    }
+
+    #[test]
+    fn manage_while_evaluating() {
+        let evaluated = Arc::new(AtomicUsize::new(0));
+        let action = GatedAction::new("gated", evaluated.clone());
+        let (gate, entered) = (action.gate.clone(), action.entered.clone());
+
+        let mut publisher = Publisher::default();
+        publisher.subscribe_queued(action.into_boxed());
+
+        let guard = gate.lock().unwrap();
+        publisher.propagate(&IOEvent::new(RawValue::Binary(true)));
+        while !entered.load(Ordering::SeqCst) {
+            std::thread::yield_now();
+        }
+
+        // subscriptions are managed without waiting for blocked subscriber
+        publisher.subscribe_queued(GatedAction::new("other", evaluated.clone()).into_boxed());
+        assert_eq!(vec!["gated", "other"], publisher.subscriber_names());
+        assert!(publisher.set_enabled("other", false));
+        assert_eq!(Some(false), publisher.is_enabled("other"));
+        assert!(publisher.unsubscribe("other"));
+        publisher.set_queue_capacity(1);
+
+        drop(guard);
+        drop(publisher);
+        assert_eq!(1, evaluated.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn queued_panic() {
+        let evaluated = Arc::new(AtomicUsize::new(0));
+
+        // `Mirror` without an output panics on evaluate
+        let mut publisher = Publisher::default();
+        publisher.subscribe_queued(Mirror::new("faulty", false).into_boxed());
+        publisher.subscribe_queued(GatedAction::new("gated", evaluated.clone()).into_boxed());
+
+        publisher.propagate(&IOEvent::new(RawValue::Binary(true)));
+        publisher.propagate(&IOEvent::new(RawValue::Binary(false)));
+
+        // worker and subscribers remain usable after panic
+        assert_eq!(vec!["faulty", "gated"], publisher.subscriber_names());
+        assert!(publisher.unsubscribe("faulty"));
+        drop(publisher);
+        assert_eq!(2, evaluated.load(Ordering::SeqCst));
+    }
+
+    /// Action which records the size of each evaluated batch
+    struct BatchAction {
+        name: String,
+        batches: Vec<usize>,
+        evaluated: Arc<AtomicUsize>,
+    }
+
+    impl Action for BatchAction {
+        fn name(&self) -> &String {
+            &self.name
+        }
+
+        fn evaluate(&mut self, _data: &IOEvent) {
+            self.evaluated.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn evaluate_batch(&mut self, events: &[IOEvent]) {
+            self.batches.push(events.len());
+            assert_eq!(vec![3], self.batches);
+        }
+
+        fn set_output(self, _device: Def<Output>) -> Self {
+            self
+        }
+
+        fn output(&self) -> Option<Def<Output>> {
+            None
+        }
+
+        fn into_boxed(self) -> BoxedAction {
+            Box::new(self)
+        }
+    }
+
+    #[test]
+    fn propagate_batch() {
+        let events = vec![IOEvent::new(RawValue::Float(1.0)); 3];
+
+        // overridden batch evaluation receives all events at once
+        let evaluated = Arc::new(AtomicUsize::new(0));
+        let action = BatchAction {
+            name: String::from("batch"),
+            batches: Vec::new(),
+            evaluated: evaluated.clone(),
+        };
+        let mut publisher = Publisher::default();
+        publisher.subscribe(action.into_boxed());
+        publisher.propagate_batch(&events);
+        assert_eq!(0, evaluated.load(Ordering::SeqCst));
+
+        // default batch evaluation calls `evaluate()` for each event
+        let evaluated = Arc::new(AtomicUsize::new(0));
+        let action = GatedAction::new("gated", evaluated.clone());
+        let mut publisher = Publisher::default();
+        publisher.subscribe(action.into_boxed());
+        publisher.propagate_batch(&events);
+        assert_eq!(3, evaluated.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn unsubscribe_queued() {
+        let mut publisher = Publisher::default();
+        publisher.subscribe(Mirror::new("inline", false).into_boxed());
+        publisher.subscribe_queued(Mirror::new("queued", false).into_boxed());
+
+        assert_eq!(vec!["inline", "queued"], publisher.subscriber_names());
+        assert_eq!(0, publisher.dropped_count());
+
+        // queued subscribers are preserved when capacity changes
+        publisher.set_queue_capacity(4);
+        assert_eq!(vec!["inline", "queued"], publisher.subscriber_names());
+
+        assert!(publisher.unsubscribe("queued"));
+        assert_eq!(vec!["inline"], publisher.subscriber_names());
+    }
+
+    #[test]
+    fn unsubscribe() {
+        let mut publisher = Publisher::default();
+        publisher.subscribe(Mirror::new("first", false).into_boxed());
+        publisher.subscribe(Mirror::new("second", false).into_boxed());
+
+        assert_eq!(vec!["first", "second"], publisher.subscriber_names());
+
+        assert!(publisher.unsubscribe("first"));
+        assert!(!publisher.unsubscribe("first"));
+        assert_eq!(vec!["second"], publisher.subscriber_names());
+
+        // removing last subscriber leaves publisher valid
+        assert!(publisher.unsubscribe("second"));
+        assert_eq!(0, publisher.subscribers().len());
+
+        // `Mirror` without an output panics on evaluate, so this checks that nothing is called
+        publisher.propagate(&IOEvent::new(RawValue::Binary(true)));
+    }
+
+    #[test]
+    fn recent_events() {
+        let mut publisher = Publisher::default();
+
+        // history is disabled by default
+        publisher.propagate(&IOEvent::new(RawValue::Int(0)));
+        assert_eq!(0, publisher.recent_events().len());
+
+        publisher.set_history_capacity(3);
+        for i in 1..=4 {
+            publisher.propagate(&IOEvent::new(RawValue::Int(i)));
+        }
+        let values: Vec<RawValue> = publisher.recent_events()
+            .map(|event| event.value.clone())
+            .collect();
+        assert_eq!(vec![RawValue::Int(2), RawValue::Int(3), RawValue::Int(4)], values);
+
+        publisher.propagate_batch(&[IOEvent::new(RawValue::Int(5))]);
+        assert_eq!(RawValue::Int(5), publisher.recent_events().next_back().unwrap().value);
+
+        // oldest events are discarded when capacity is reduced
+        publisher.set_history_capacity(1);
+        assert_eq!(1, publisher.recent_events().len());
+        assert_eq!(RawValue::Int(5), publisher.recent_events().next().unwrap().value);
+
+        publisher.set_history_capacity(0);
+        assert_eq!(0, publisher.recent_events().len());
+    }
+
+    #[test]
+    fn disabled_subscriber() {
+        let evaluated = Arc::new(AtomicUsize::new(0));
+        let action = BatchAction {
+            name: String::from("counter"),
+            batches: Vec::new(),
+            evaluated: evaluated.clone(),
+        };
+
+        let mut publisher = Publisher::default();
+        publisher.subscribe(action.into_boxed());
+        assert_eq!(Some(true), publisher.is_enabled("counter"));
+
+        publisher.propagate(&IOEvent::new(RawValue::Int(1)));
+        assert_eq!(1, evaluated.load(Ordering::SeqCst));
+
+        // disabled subscriber receives no events but remains subscribed
+        assert!(publisher.set_enabled("counter", false));
+        assert_eq!(Some(false), publisher.is_enabled("counter"));
+        publisher.propagate(&IOEvent::new(RawValue::Int(2)));
+        publisher.propagate_batch(&[IOEvent::new(RawValue::Int(3))]);
+        assert_eq!(1, evaluated.load(Ordering::SeqCst));
+        assert_eq!(vec!["counter"], publisher.subscriber_names());
+
+        // re-enabling resumes propagation
+        assert!(publisher.set_enabled("counter", true));
+        publisher.propagate(&IOEvent::new(RawValue::Int(4)));
+        assert_eq!(2, evaluated.load(Ordering::SeqCst));
+
+        assert!(!publisher.set_enabled("missing", false));
+        assert_eq!(None, publisher.is_enabled("missing"));
+    }
+}
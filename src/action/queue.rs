@@ -0,0 +1,198 @@
+//! Bounded event queue consumed by a background thread
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread::JoinHandle;
+
+use crate::action::{BoxedAction, EventSource};
+use crate::helpers::Def;
+use crate::io::IOEvent;
+
+/// State shared between [`EventQueue`] and its worker thread
+struct QueueState {
+    /// Pending events, and whether each is a secondary value of a composite device
+    events: Mutex<VecDeque<(IOEvent, bool)>>,
+    available: Condvar,
+    capacity: AtomicUsize,
+    dropped: AtomicUsize,
+    closed: AtomicBool,
+}
+
+/// Subscribed [`crate::action::Action`] which is evaluated by the worker thread of [`EventQueue`]
+///
+/// Each action is locked separately from the collection of subscriptions, and name, source, and
+/// enabled flag are stored outside of that lock. Therefore, subscriptions may be managed while
+/// the worker thread is evaluating an action.
+pub(crate) struct QueuedSubscription {
+    pub name: String,
+    pub source: EventSource,
+    pub enabled: bool,
+    action: Arc<Mutex<BoxedAction>>,
+}
+
+impl QueuedSubscription {
+    pub fn new(action: BoxedAction, source: EventSource) -> Self {
+        let name = action.name().clone();
+        Self { name, source, enabled: true, action: Arc::new(Mutex::new(action)) }
+    }
+}
+
+/// Hands [`IOEvent`]s to subscribers on a background thread
+///
+/// Events are buffered in a bounded queue so that slow subscribers do not stall the caller. When
+/// the queue is full, the oldest pending event is dropped and a counter is incremented.
+///
+/// When dropped, all pending events are processed before the worker thread is joined.
+///
+/// A subscriber which panics while evaluating an event is logged and skipped, so that one faulty
+/// subscriber neither stops the worker thread nor poisons the shared collection of subscribers.
+///
+/// # See Also
+///
+/// - [`crate::action::Publisher::subscribe_queued()`] for usage
+pub(crate) struct EventQueue {
+    state: Arc<QueueState>,
+    subscriptions: Def<Vec<QueuedSubscription>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl EventQueue {
+    /// Constructor for [`EventQueue`] which spawns worker thread
+    ///
+    /// # Parameters
+    ///
+    /// - `capacity`: maximum number of pending events
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "Queue capacity must be non-zero");
+
+        let state = Arc::new(QueueState {
+            events: Mutex::new(VecDeque::with_capacity(capacity)),
+            available: Condvar::new(),
+            capacity: AtomicUsize::new(capacity),
+            dropped: AtomicUsize::new(0),
+            closed: AtomicBool::new(false),
+        });
+        let subscriptions: Def<Vec<QueuedSubscription>> = Def::default();
+
+        let worker = {
+            let state = state.clone();
+            let subscriptions = subscriptions.clone();
+            std::thread::spawn(move || Self::consume(state, subscriptions))
+        };
+
+        Self { state, subscriptions, worker: Some(worker) }
+    }
+
+    /// Worker loop which evaluates events until queue is closed and empty
+    ///
+    /// Subscriptions are only locked while collecting the actions which accept an event, so that
+    /// actions are evaluated without holding the collection of subscriptions.
+    fn consume(state: Arc<QueueState>, subscriptions: Def<Vec<QueuedSubscription>>) {
+        loop {
+            let event = {
+                let mut events = state.events.lock().unwrap_or_else(PoisonError::into_inner);
+                loop {
+                    if let Some(event) = events.pop_front() {
+                        break Some(event);
+                    }
+                    if state.closed.load(Ordering::Acquire) {
+                        break None;
+                    }
+                    events = state.available.wait(events).unwrap_or_else(PoisonError::into_inner);
+                }
+            };
+
+            match event {
+                Some((event, secondary)) => {
+                    let accepted: Vec<Arc<Mutex<BoxedAction>>> = subscriptions
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .iter()
+                        .filter(|subscription| subscription.enabled && subscription.source.accepts(&event, secondary))
+                        .map(|subscription| subscription.action.clone())
+                        .collect();
+
+                    for action in accepted {
+                        let mut action = action.lock().unwrap_or_else(PoisonError::into_inner);
+                        let result = panic::catch_unwind(AssertUnwindSafe(|| action.evaluate(&event)));
+                        if result.is_err() {
+                            log::error!("Queued subscriber '{}' panicked during evaluation", action.name());
+                        }
+                    }
+                }
+                None => return,
+            }
+        }
+    }
+
+    /// Add event to queue, dropping the oldest pending event if full
+    ///
+    /// # Parameters
+    ///
+    /// - `event`: Event to evaluate
+    /// - `secondary`: Whether `event` is a secondary value of a composite device
+    pub fn push(&self, event: IOEvent, secondary: bool) {
+        let mut events = self.state.events.lock().unwrap_or_else(PoisonError::into_inner);
+        if events.len() >= self.state.capacity.load(Ordering::Relaxed) {
+            events.pop_front();
+            self.state.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        events.push_back((event, secondary));
+        self.state.available.notify_one();
+    }
+
+    /// Change maximum number of pending events
+    ///
+    /// When more events are pending than `capacity`, the oldest are dropped.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero
+    pub fn set_capacity(&self, capacity: usize) {
+        assert!(capacity > 0, "Queue capacity must be non-zero");
+
+        let mut events = self.state.events.lock().unwrap_or_else(PoisonError::into_inner);
+        self.state.capacity.store(capacity, Ordering::Relaxed);
+        while events.len() > capacity {
+            events.pop_front();
+            self.state.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Number of events dropped because queue was full
+    pub fn dropped(&self) -> usize {
+        self.state.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Lock subscriptions evaluated by worker thread
+    ///
+    /// Does not wait for an action which is being evaluated.
+    pub fn subscriptions(&self) -> MutexGuard<'_, Vec<QueuedSubscription>> {
+        self.subscriptions.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Signal worker thread to stop once queue is empty and wait for it to finish
+    fn close(&mut self) {
+        self.state.closed.store(true, Ordering::Release);
+        {
+            // acquire lock so that worker is either waiting or has not yet checked `closed`
+            let _events = self.state.events.lock().unwrap_or_else(PoisonError::into_inner);
+            self.state.available.notify_all();
+        }
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for EventQueue {
+    fn drop(&mut self) {
+        self.close()
+    }
+}
@@ -1,5 +1,6 @@
 use std::ops::Not;
 use crate::action::{Command, IOCommand};
+use crate::clock;
 use crate::errors::ErrorType;
 use crate::helpers::Def;
 use crate::io::{IOEvent, RawValue};
@@ -33,6 +34,18 @@ pub struct Routine {
 
     /// Low-level command to execute
     command: IOCommand,
+
+    /// Routines with higher priority are executed first when several are due
+    priority: i32,
+
+    /// Number of times execution has been attempted since scheduled time
+    attempts: u32,
+
+    /// Maximum number of failed executions before routine is dropped
+    max_attempts: Option<u32>,
+
+    /// Time after which routine is dropped if not yet executed
+    expires_at: Option<DateTime<Utc>>,
 }
 
 impl Routine {
@@ -76,14 +89,113 @@ impl Routine {
             value,
             log: weak_log,
             command,
+            priority: 0,
+            attempts: 0,
+            max_attempts: None,
+            expires_at: None,
         }
     }
 
+    /// Builder method for setting execution priority
+    ///
+    /// When several routines are due in the same call to
+    /// [`crate::action::SchedRoutineHandler::attempt_routines()`], routines with a higher priority
+    /// are executed first. Routines with equal priority are executed in insertion order.
+    ///
+    /// # Parameters
+    ///
+    /// - `priority`: Execution priority. Default is `0`.
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    pub fn set_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Getter for scheduled time of execution
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    /// Getter for value passed to command
+    pub fn value(&self) -> &RawValue {
+        &self.value
+    }
+
+    /// Getter for execution priority
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Builder method for limiting number of failed executions
+    ///
+    /// By default, a routine whose command fails is retried indefinitely. Once the limit is
+    /// reached, the routine is dropped by [`crate::action::SchedRoutineHandler::attempt_routines()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `max_attempts`: Maximum number of executions to attempt
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    pub fn set_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Getter for maximum number of attempted executions
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    /// Number of times execution has been attempted
+    ///
+    /// Calls to [`Routine::attempt()`] before the scheduled time are not counted.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Builder method for setting time after which routine is no longer executed
+    ///
+    /// Once expired, the routine is dropped by
+    /// [`crate::action::SchedRoutineHandler::attempt_routines()`] instead of being executed.
+    ///
+    /// # Parameters
+    ///
+    /// - `expires_at`: Time after which routine expires
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    pub fn set_expiration(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Getter for expiration time
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+    }
+
+    /// Whether routine should be dropped without further attempts
+    ///
+    /// # Returns
+    ///
+    /// `true` if maximum number of attempts has been reached, or expiration time has passed
+    pub fn is_expired(&self) -> bool {
+        let exhausted = self.max_attempts.is_some_and(|max| self.attempts >= max);
+        let expired = self.expires_at.is_some_and(|expires_at| clock::now() > expires_at);
+        exhausted || expired
+    }
+
     /// Main polling function
     ///
     /// Acts as wrapper for [`Command::execute()`]. Checks scheduled time,
     /// then executes command. [`IOEvent`] is automatically added to device
-    /// log.
+    /// log. Every execution increments [`Routine::attempts()`]. Expired routines are not executed.
     ///
     /// # Returns
     ///
@@ -92,11 +204,12 @@ impl Routine {
     /// - `true`: if execution of [`IOCommand`] was successful indicating
     ///   instance should be dropped.
     /// - `false`: if [`IOCommand`] has not been executed. Instance should
-    ///   not be dropped yet.
-    pub fn attempt(&self) -> bool {
-        let now = Utc::now();
-        if now >= self.timestamp {
-            let result = self.execute(self.value);
+    ///   not be dropped yet unless [`Routine::is_expired()`].
+    pub fn attempt(&mut self) -> bool {
+        let now = clock::now();
+        if now >= self.timestamp && !self.is_expired() {
+            self.attempts += 1;
+            let result = self.execute(self.value.clone());
             match result {
                 Ok(event) => {
                     let event = event.unwrap();
@@ -104,7 +217,7 @@ impl Routine {
                     return true;
                 }
                 Err(e) => {
-                    eprintln!("{}", e);
+                    log::error!("Scheduled routine failed: {}", e);
                 }
             };
         };
@@ -120,7 +233,7 @@ impl Command<IOEvent, ErrorType> for Routine {
         V: Into<Option<RawValue>>
     {
         let value = value.into();
-        match self.command.execute(value) {
+        match self.command.execute(value.clone()) {
             Ok(_) => {
                 let event = IOEvent::with_timestamp(self.timestamp, value.unwrap());
                 Ok(Some(event))
@@ -144,6 +257,7 @@ impl Chronicle for Routine {
 #[cfg(test)]
 mod functionality_tests {
     use crate::action::{IOCommand, Routine};
+    use crate::clock::MockClock;
     use crate::helpers::Def;
     use crate::io::{DeviceMetadata, RawValue};
     use crate::storage::Log;
@@ -169,23 +283,26 @@ mod functionality_tests {
 
         let log = Def::new(Log::with_metadata(&metadata));
 
-        let command = IOCommand::Output(move |val| unsafe {
+        let command = IOCommand::output(move |val| unsafe {
             set_register(val);
             Ok(())
         });
 
-        let timestamp = Utc::now() + Duration::microseconds(10);
+        let clock = MockClock::new(Utc::now());
+        let timestamp = clock.now() + Duration::microseconds(10);
         let value = RawValue::Binary(true);
-        let routine = Routine::new(timestamp, value, log.clone(), command);
+        let mut routine = Routine::new(timestamp, value.clone(), log.clone(), command);
 
         unsafe {
             assert_ne!(REGISTER, value);
         }
 
-        while Utc::now() < timestamp {
-            assert_eq!(false, routine.attempt());
-        }
+        assert!(!routine.attempt());
+        clock.advance(Duration::microseconds(9));
+        assert!(!routine.attempt());
+        assert_eq!(0, routine.attempts());
 
+        clock.advance(Duration::microseconds(1));
         assert!(routine.attempt());
         unsafe {
             assert_eq!(REGISTER, value);
@@ -208,9 +325,9 @@ mod meta_tests {
     fn test_constructor_w_none() {
         let timestamp = Utc::now();
         let value = RawValue::Binary(true);
-        let command = IOCommand::Output(|_| Ok(()));
+        let command = IOCommand::output(|_| Ok(()));
 
-        let routine = Routine::new(timestamp, value, None, command);
+        let mut routine = Routine::new(timestamp, value, None, command);
 
         assert!(routine.attempt());
     }
@@ -223,9 +340,9 @@ mod meta_tests {
 
         let timestamp = Utc::now();
         let value = RawValue::Binary(true);
-        let command = IOCommand::Output(|_| Ok(()));
+        let command = IOCommand::output(|_| Ok(()));
 
-        let routine = Routine::new(timestamp, value, log.clone(), command);
+        let mut routine = Routine::new(timestamp, value, log.clone(), command);
         assert!(routine.attempt());
     }
 
@@ -234,9 +351,9 @@ mod meta_tests {
     fn validate_command() {
         let timestamp = Utc::now();
         let value = RawValue::Binary(true);
-        let command = IOCommand::Input(|| RawValue::default());
+        let command = IOCommand::input(|| RawValue::default());
 
-        let routine = Routine::new(timestamp, value, None, command);
+        let mut routine = Routine::new(timestamp, value, None, command);
         assert!(routine.attempt());
     }
 }
@@ -0,0 +1,167 @@
+use std::collections::VecDeque;
+use crate::action::{IOCommand, Routine};
+use crate::helpers::Def;
+use crate::io::RawValue;
+use crate::storage::Log;
+use chrono::{DateTime, Duration, Utc};
+
+/// A series of values written to a single output at offsets relative to a start time
+///
+/// Each step is internally represented as a [`Routine`], so logging of events is handled
+/// automatically. Steps are executed in order of their offset, one step per call to
+/// [`SequenceRoutine::attempt()`].
+///
+/// # Example
+///
+/// The primary use case is staged actuation, such as ramping a pump from off to full power over
+/// a period of time:
+///
+/// ```
+/// use chrono::{Duration, Utc};
+/// use sensd::action::{IOCommand, SequenceRoutine};
+/// use sensd::io::RawValue;
+///
+/// let steps = vec![
+///     (Duration::seconds(0), RawValue::Float(0.0)),
+///     (Duration::seconds(15), RawValue::Float(0.5)),
+///     (Duration::seconds(30), RawValue::Float(1.0)),
+/// ];
+/// let sequence = SequenceRoutine::new(Utc::now(), steps, None, IOCommand::output(|_| Ok(())));
+///
+/// assert_eq!(3, sequence.remaining());
+/// ```
+pub struct SequenceRoutine {
+    /// Pending steps in order of execution
+    steps: VecDeque<Routine>,
+}
+
+impl SequenceRoutine {
+    /// Constructor for [`SequenceRoutine`]
+    ///
+    /// # Parameters
+    ///
+    /// - `start`: Time from which step offsets are measured
+    /// - `steps`: Offsets and values to write. Steps do not need to be ordered.
+    /// - `log`: Strong reference to [`Log`] which is internally downgraded.
+    /// - `command`: Low-level output command
+    ///
+    /// # Returns
+    ///
+    /// Initialized instance with steps ordered by offset
+    ///
+    /// # Panics
+    ///
+    /// If `command` is not [`IOCommand::Output`]
+    pub fn new<L>(
+        start: DateTime<Utc>,
+        mut steps: Vec<(Duration, RawValue)>,
+        log: L,
+        command: IOCommand,
+    ) -> Self
+    where
+        L: Into<Option<Def<Log>>>,
+    {
+        let log = log.into();
+        steps.sort_by_key(|(offset, _)| *offset);
+
+        let steps = steps.into_iter()
+            .map(|(offset, value)| Routine::new(start + offset, value, log.clone(), command.clone()))
+            .collect();
+
+        Self { steps }
+    }
+
+    /// Main polling function
+    ///
+    /// Attempts the next pending step. If the step is executed, it is removed and the next step
+    /// is attempted during the following call.
+    ///
+    /// # Returns
+    ///
+    /// A `bool` that indicates:
+    ///
+    /// - `true`: if all steps have been executed indicating instance should be dropped.
+    /// - `false`: if steps are still pending.
+    pub fn attempt(&mut self) -> bool {
+        if let Some(step) = self.steps.front_mut() {
+            if step.attempt() {
+                self.steps.pop_front();
+            }
+        }
+        self.steps.is_empty()
+    }
+
+    /// Number of steps that have not been executed
+    pub fn remaining(&self) -> usize {
+        self.steps.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use crate::action::{IOCommand, SchedRoutineHandler, SequenceRoutine};
+    use crate::helpers::Def;
+    use crate::io::{DeviceMetadata, RawValue};
+    use crate::storage::Log;
+
+    #[test]
+    fn step_ordering() {
+        let log = Def::new(Log::with_metadata(&DeviceMetadata::default()));
+        let command = IOCommand::output(|_| Ok(()));
+
+        // all steps are due
+        let start = Utc::now() - Duration::seconds(1);
+        let steps = vec![
+            (Duration::milliseconds(20), RawValue::Int(2)),
+            (Duration::milliseconds(0), RawValue::Int(0)),
+            (Duration::milliseconds(10), RawValue::Int(1)),
+        ];
+        let sequence = SequenceRoutine::new(start, steps, log.clone(), command);
+
+        let mut handler = SchedRoutineHandler::default();
+        handler.push_sequence(sequence);
+        assert_eq!(1, handler.sequences().len());
+
+        for remaining in [2, 1] {
+            handler.attempt_routines();
+            assert_eq!(remaining, handler.sequences()[0].remaining());
+        }
+        handler.attempt_routines();
+
+        // sequence is removed after last step
+        assert!(handler.sequences().is_empty());
+
+        let binding = log.try_lock().unwrap();
+        let values: Vec<RawValue> = binding.events().map(|event| event.value.clone()).collect();
+        assert_eq!(vec![RawValue::Int(0), RawValue::Int(1), RawValue::Int(2)], values);
+    }
+
+    #[test]
+    fn pending_step_not_executed() {
+        let steps = vec![
+            (Duration::milliseconds(0), RawValue::Int(0)),
+            (Duration::hours(1), RawValue::Int(1)),
+        ];
+        let mut sequence = SequenceRoutine::new(Utc::now(), steps, None, IOCommand::output(|_| Ok(())));
+
+        assert!(!sequence.attempt());
+        assert!(!sequence.attempt());
+        assert_eq!(1, sequence.remaining());
+    }
+
+    #[test]
+    fn one_step_per_attempt() {
+        let start = Utc::now() - Duration::seconds(1);
+        let steps = vec![
+            (Duration::milliseconds(0), RawValue::Int(0)),
+            (Duration::milliseconds(10), RawValue::Int(1)),
+        ];
+        let mut sequence = SequenceRoutine::new(start, steps, None, IOCommand::output(|_| Ok(())));
+
+        assert!(!sequence.attempt());
+        assert_eq!(1, sequence.remaining());
+        assert!(sequence.attempt());
+        assert_eq!(0, sequence.remaining());
+    }
+}
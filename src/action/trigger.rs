@@ -25,7 +25,7 @@ impl Trigger {
     /// # Returns
     ///
     /// A `bool` if threshold is exceeded or not in relation to variant of `self`
-    pub fn exceeded(&self, value: RawValue, threshold: RawValue) -> bool {
+    pub fn exceeded(&self, value: &RawValue, threshold: &RawValue) -> bool {
         match &self {
             &Trigger::GT => value > threshold,
             &Trigger::GTE => value >= threshold,
@@ -62,15 +62,15 @@ mod tests {
         );
 
         assert_eq!(true,
-                   trigger.exceeded(smaller,  bigger)
+                   trigger.exceeded(&smaller, &bigger)
         );
 
         assert_eq!(false,
-                   trigger.exceeded(bigger, smaller)
+                   trigger.exceeded(&bigger, &smaller)
         );
 
-        assert!(trigger.exceeded(bigger, bigger));
-        assert!(trigger.exceeded(smaller, smaller));
+        assert!(trigger.exceeded(&bigger, &bigger));
+        assert!(trigger.exceeded(&smaller, &smaller));
     }
 
     #[test]
@@ -84,15 +84,15 @@ mod tests {
 
 
         assert_eq!(true,
-                   trigger.exceeded(bigger, smaller)
+                   trigger.exceeded(&bigger, &smaller)
         );
 
         assert_eq!(false,
-                   trigger.exceeded(smaller,  bigger, )
+                   trigger.exceeded(&smaller, &bigger)
         );
 
-        assert!(trigger.exceeded(bigger, bigger));
-        assert!(trigger.exceeded(smaller, smaller));
+        assert!(trigger.exceeded(&bigger, &bigger));
+        assert!(trigger.exceeded(&smaller, &smaller));
     }
     #[test]
     fn lt() {
@@ -103,11 +103,11 @@ mod tests {
         );
 
         assert_eq!(true,
-            trigger.exceeded(smaller,  bigger)
+            trigger.exceeded(&smaller, &bigger)
         );
 
         assert_eq!(false,
-                   trigger.exceeded(bigger, smaller)
+                   trigger.exceeded(&bigger, &smaller)
         );
     }
 
@@ -122,11 +122,11 @@ mod tests {
 
 
         assert_eq!(true,
-                   trigger.exceeded(bigger, smaller)
+                   trigger.exceeded(&bigger, &smaller)
         );
 
         assert_eq!(false,
-                   trigger.exceeded(smaller,  bigger, )
+                   trigger.exceeded(&smaller, &bigger)
         );
     }
 }
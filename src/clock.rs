@@ -0,0 +1,191 @@
+//! Source of current time which may be overridden for simulation
+//!
+//! Timestamps of generated [`crate::io::IOEvent`]s and the scheduling of
+//! [`crate::action::Routine`]s are based on [`now()`]. Normally this is the system clock, however
+//! [`simulate()`] overrides the current time so that recorded data may be replayed
+//! deterministically (ie: by [`crate::storage::Group::replay()`]).
+//!
+//! Simulated time is local to the calling thread. Therefore, queued subscribers added by
+//! [`crate::action::Publisher::subscribe_queued()`] always observe the system clock.
+//!
+//! [`MockClock`] allows tests to control time explicitly instead of waiting for the system clock.
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+use chrono::{DateTime, Duration, Utc};
+
+thread_local! {
+    /// Time returned by [`now()`] while simulating
+    static SIMULATED: Cell<Option<DateTime<Utc>>> = const { Cell::new(None) };
+}
+
+/// Get current time
+///
+/// # Returns
+///
+/// Simulated time if called within [`simulate()`], otherwise `Utc::now()`
+pub fn now() -> DateTime<Utc> {
+    SIMULATED.with(|simulated| simulated.get()).unwrap_or_else(Utc::now)
+}
+
+/// Whether [`now()`] currently returns simulated time
+pub fn is_simulated() -> bool {
+    SIMULATED.with(|simulated| simulated.get().is_some())
+}
+
+/// Run a closure while [`now()`] returns `time`
+///
+/// Calls may be nested. Previous time is restored when `f` returns, even if `f` panics.
+///
+/// # Parameters
+///
+/// - `time`: Time returned by [`now()`] within `f`
+/// - `f`: Closure to run
+///
+/// # Returns
+///
+/// Value returned by `f`
+///
+/// # Example
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use sensd::clock;
+///
+/// let time = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+///
+/// assert_eq!(time, clock::simulate(time, clock::now));
+/// assert!(!clock::is_simulated());
+/// ```
+pub fn simulate<F, R>(time: DateTime<Utc>, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _clock = MockClock::new(time);
+    f()
+}
+
+/// Controllable time source for deterministic tests
+///
+/// While an instance exists, [`now()`] returns the time held by the clock on the current thread
+/// instead of the system clock. Time only changes when [`MockClock::advance()`] or
+/// [`MockClock::set()`] are called, so that scheduled polls and routines can be asserted without
+/// waiting for real time to elapse. Previous time is restored when dropped.
+///
+/// Since simulated time is local to the thread, [`MockClock`] cannot be sent to another thread.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use sensd::clock::{self, MockClock};
+///
+/// let start = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+/// let clock = MockClock::new(start);
+///
+/// clock.advance(Duration::seconds(5));
+/// assert_eq!(start + Duration::seconds(5), clock::now());
+///
+/// drop(clock);
+/// assert!(!clock::is_simulated());
+/// ```
+pub struct MockClock {
+    /// Time to restore when dropped
+    previous: Option<DateTime<Utc>>,
+
+    /// Prevent clock from being sent to or shared with another thread
+    _local: PhantomData<*const ()>,
+}
+
+impl MockClock {
+    /// Constructor for [`MockClock`]
+    ///
+    /// # Parameters
+    ///
+    /// - `time`: Initial time returned by [`now()`]
+    pub fn new(time: DateTime<Utc>) -> Self {
+        let previous = SIMULATED.with(|simulated| simulated.replace(Some(time)));
+        Self { previous, _local: PhantomData }
+    }
+
+    /// Current time held by clock
+    pub fn now(&self) -> DateTime<Utc> {
+        now()
+    }
+
+    /// Jump to an arbitrary time
+    ///
+    /// # Parameters
+    ///
+    /// - `time`: Time returned by [`now()`] until clock is changed again
+    pub fn set(&self, time: DateTime<Utc>) {
+        SIMULATED.with(|simulated| simulated.set(Some(time)));
+    }
+
+    /// Move time forward
+    ///
+    /// # Parameters
+    ///
+    /// - `duration`: Amount of time to add. Negative durations move time backwards.
+    pub fn advance(&self, duration: Duration) {
+        self.set(self.now() + duration);
+    }
+}
+
+impl Drop for MockClock {
+    fn drop(&mut self) {
+        SIMULATED.with(|simulated| simulated.set(self.previous));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use crate::clock::{is_simulated, now, simulate, MockClock};
+
+    #[test]
+    fn nested() {
+        let outer = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let inner = outer + Duration::minutes(1);
+
+        simulate(outer, || {
+            assert!(is_simulated());
+            assert_eq!(inner, simulate(inner, now));
+            assert_eq!(outer, now());
+        });
+
+        assert!(!is_simulated());
+        assert!(now() > outer);
+    }
+
+    #[test]
+    fn restored_after_panic() {
+        let time = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+
+        let result = std::panic::catch_unwind(|| simulate(time, || panic!("expected")));
+        assert!(result.is_err());
+        assert!(!is_simulated());
+    }
+
+    #[test]
+    fn mock_clock() {
+        let start = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let clock = MockClock::new(start);
+        assert_eq!(start, now());
+
+        clock.advance(Duration::milliseconds(30));
+        assert_eq!(start + Duration::milliseconds(30), now());
+
+        // simulation within mock clock is restored
+        simulate(start, || assert_eq!(start, now()));
+        assert_eq!(start + Duration::milliseconds(30), clock.now());
+
+        clock.set(start - Duration::days(1));
+        assert_eq!(start - Duration::days(1), now());
+
+        drop(clock);
+        assert!(!is_simulated());
+    }
+}
@@ -2,7 +2,7 @@ use std::error::Error as _Error;
 
 use custom_error::custom_error;
 
-use crate::io::DeviceMetadata;
+use crate::io::{DeviceMetadata, IODirection, IOKind, RawValue, RawValueKind};
 
 pub type ErrorType = Box<dyn _Error>;
 
@@ -11,15 +11,68 @@ custom_error! { pub ContainerError
     ContainerEmpty = "Container is empty",
     ContainerNotEmpty = "Container is not empty",
     KeyExists{key: String} = "Device entry {key} exists",
+    KeyMissing{key: String} = "Device entry {key} does not exist",
+    KeyAmbiguous{key: String} = "Device entry {key} refers to both an input and an output",
+    Expired{key: String} = "Entry {key} is older than all retained entries",
 }
 
 custom_error! { pub DeviceError
     HWFault{metadata: DeviceMetadata} = "HW fault from {metadata}",
     NoCommand{metadata: DeviceMetadata} = "No associated command for {metadata}",
     ValueExpected{metadata: DeviceMetadata} = "Value expected from {metadata}",
+    CommandMismatch{metadata: DeviceMetadata} = "Command direction does not agree with {metadata}",
+    DirectionMismatch{metadata: DeviceMetadata, expected: IODirection} = "Expected {expected} device, got {metadata}",
+    Timeout = "Command did not complete before timeout",
+    Busy = "Previous command has not completed",
+    OutOfRange{metadata: DeviceMetadata, value: RawValue} = "Value {value} is out of range for {metadata}",
+    KindMismatch{metadata: DeviceMetadata, expected: IOKind} = "Expected {expected} device, got {metadata}",
+    ValueKindMismatch{metadata: DeviceMetadata, expected: RawValueKind, value: RawValue} = "Expected {expected} value from {metadata}, got {value}",
+    /// Low-level function of a [`crate::action::IOCommand`] returned an error
+    ///
+    /// `direction` is the direction of the failed function, which is [`IODirection::InOut`] for
+    /// [`crate::action::IOCommand::Bidirectional`]. `value` is the value that was being written.
+    CommandFailed{metadata: DeviceMetadata, direction: IODirection, value: RawValue} = "{direction} command of {metadata} failed when writing {value}",
+    /// Bounds stored in configuration are invalid for the kind of device
+    InvalidBounds{metadata: DeviceMetadata, min: f32, max: f32} = "Bounds {min} to {max} are invalid for {metadata}",
 }
 
-custom_error! { pub FilesystemError
+impl DeviceError {
+    /// Replace metadata of error with that of the device which raised it
+    ///
+    /// [`crate::action::IOCommand`] is unaware of the device it belongs to, therefore errors it
+    /// returns contain default metadata until attached by the device.
+    pub(crate) fn with_metadata(self, metadata: &DeviceMetadata) -> Self {
+        let metadata = metadata.clone();
+        match self {
+            Self::HWFault {..} => Self::HWFault {metadata},
+            Self::ValueExpected {..} => Self::ValueExpected {metadata},
+            Self::CommandFailed {direction, value, ..} => Self::CommandFailed {metadata, direction, value},
+            other => other,
+        }
+    }
+}
+
+custom_error! { pub ValueError
+    ParseError{input: String, kind: RawValueKind} = "Could not parse \"{input}\" as {kind}",
+    ConversionError{value: RawValue, unit: &'static str} = "Could not convert {value} to {unit}",
+    UnitRange{value: RawValue, unit: &'static str} = "Value {value} is out of range for {unit}",
+    UnitMismatch{unit: &'static str, kind: IOKind} = "Unit {unit} does not describe {kind} devices",
+    InvalidBounds{min: f32, max: f32} = "Lower bound {min} is greater than upper bound {max}",
+    NoUnit{kind: IOKind} = "{kind} devices have no unit",
+}
+
+custom_error! {
+    /// Errors which arise when saving or loading files
+    ///
+    /// Variants with a `source` field preserve the underlying error, which is returned by
+    /// [`std::error::Error::source()`].
+    pub FilesystemError
     SerializationError{msg: String} = "Error during serialization: {msg}",
+    JsonError{source: serde_json::Error} = "Error during serialization: {source}",
+    IoError{source: std::io::Error} = "I/O error: {source}",
     PermissionError{path: String} = "Incorrect permissions for {path}",
+    UnsupportedVersion{version: u64} = "Unsupported file format version {version}",
+    UnrecognizedFormat{msg: String} = "Unrecognized file format: {msg}",
+    SaveError{name: String, msg: String} = "Could not save \"{name}\": {msg}",
+    UnsupportedPath{path: String} = "Explicit path {path} is not supported",
 }
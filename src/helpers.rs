@@ -1,45 +1,219 @@
-use std::fs::{create_dir_all, File};
-use std::path::Path;
+use chrono::Duration;
+use std::fmt::Display;
+use std::fs::{DirBuilder, File, OpenOptions};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, MutexGuard, PoisonError, TryLockResult};
 
 use crate::errors::ErrorType;
+use crate::settings::MAX_BACKOFF_SECS;
 
 /// Return a writable `File` from a given path.
 ///
-/// If file or directory structure does not exist, then an attempt is made to create both.
-pub fn writable_or_create<P>(path: P) -> File
+/// If file or directory structure does not exist, then an attempt is made to create both. Existing
+/// files are truncated so that previous contents are overwritten.
+///
+/// # Returns
+///
+/// A `Result` containing the opened file, or an I/O error if file or directory structure could
+/// not be created
+pub fn writable_or_create<P>(path: P) -> std::io::Result<File>
 where P: AsRef<Path>
 {
-    File::options()
-        .write(true)
-        .open(path.as_ref())
-        // if an error occurs when reading, create file
-        .unwrap_or_else(move |_| {
-            match File::create(path.as_ref()) {
-                Ok(_) => (),
-                Err(_) => {
-                    let parent = path.as_ref().parent().unwrap();
-                    create_dir_all(parent).expect("Could not create directory");
-                    File::create(&path).unwrap();
-                }
-            }
-            File::options().write(true).open(path.as_ref()).unwrap()
-        })
+    writable_or_create_with_mode(path, None)
+}
+
+/// Return a writable `File` from a given path, creating file and directories with `mode`
+///
+/// Behaves as [`writable_or_create()`], except that created directories receive `mode` and a
+/// created file receives `mode` without execute bits. Permissions are still reduced by the umask
+/// of the process and permissions of existing files or directories are not changed. Therefore,
+/// [`set_permissions_recursive()`] should be used afterwards when exact permissions are required.
+/// On non-Unix platforms, `mode` is ignored.
+///
+/// # Parameters
+///
+/// - `path`: Path of file
+/// - `mode`: Unix permission bits of directories. If `None`, the defaults of the platform are used.
+///
+/// # Returns
+///
+/// A `Result` containing the opened file, or an I/O error if file or directory structure could
+/// not be created
+pub fn writable_or_create_with_mode<P>(path: P, mode: Option<u32>) -> std::io::Result<File>
+where P: AsRef<Path>
+{
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    let mut builder = DirBuilder::new();
+    builder.recursive(true);
+
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+        builder.mode(mode);
+        options.mode(mode & 0o666);
+    }
+
+    #[cfg(not(unix))]
+    let _ = mode;
+
+    if let Some(parent) = path.as_ref().parent() {
+        builder.create(parent)?;
+    }
+    options.open(path)
+}
+
+/// Call `f` until it succeeds, retrying failed attempts with exponential backoff
+///
+/// The delay before the first retry is `backoff`, and is doubled before each subsequent retry.
+/// Each delay is capped to [`MAX_BACKOFF_SECS`]. Each retry is logged at `warn` level.
+///
+/// # Parameters
+///
+/// - `context`: Description of operation used when logging retries (eg: "Save of log")
+/// - `retries`: Maximum number of retries. `0` calls `f` exactly once.
+/// - `backoff`: Delay before first retry
+/// - `f`: Fallible operation
+///
+/// # Returns
+///
+/// The first `Ok` returned by `f`, or the last `Err` once all retries are exhausted
+///
+/// # See Also
+///
+/// - [`retry_with_backoff_if()`] for only retrying some errors
+pub fn retry_with_backoff<T, E, F>(context: &str, retries: u32, backoff: Duration, f: F) -> Result<T, E>
+where
+    E: Display,
+    F: FnMut() -> Result<T, E>,
+{
+    retry_with_backoff_if(context, retries, backoff, Duration::seconds(MAX_BACKOFF_SECS), |_| true, f)
+}
+
+/// Call `f` until it succeeds or returns an error which is not `retryable`
+///
+/// Behaves as [`retry_with_backoff()`], except that the delay is capped to `max_delay` instead
+/// of [`MAX_BACKOFF_SECS`].
+///
+/// # Parameters
+///
+/// - `context`: Description of operation used when logging retries (eg: "Save of log")
+/// - `retries`: Maximum number of retries. `0` calls `f` exactly once.
+/// - `backoff`: Delay before first retry
+/// - `max_delay`: Upper bound of any single delay
+/// - `retryable`: Returns `true` if error should be retried
+/// - `f`: Fallible operation
+///
+/// # Returns
+///
+/// The first `Ok` returned by `f`, the first `Err` which is not `retryable`, or the last `Err`
+/// once all retries are exhausted
+pub fn retry_with_backoff_if<T, E, P, F>(
+    context: &str,
+    retries: u32,
+    backoff: Duration,
+    max_delay: Duration,
+    retryable: P,
+    mut f: F,
+) -> Result<T, E>
+where
+    E: Display,
+    P: Fn(&E) -> bool,
+    F: FnMut() -> Result<T, E>,
+{
+    let mut delay = backoff.min(max_delay);
+    let mut attempt = 0;
+    loop {
+        let error = match f() {
+            Err(e) if attempt < retries && retryable(&e) => e,
+            result => return result,
+        };
+        attempt += 1;
+
+        log::warn!("{} failed ({}). Retry {} of {}", context, error, attempt, retries);
+
+        std::thread::sleep(delay.to_std().unwrap_or_default());
+        // `delay` never exceeds `max_delay`, so doubling cannot overflow
+        delay = (delay * 2).min(max_delay);
+    }
+}
+
+/// Convert arbitrary text into a string that is safe to use in filenames
+///
+/// ASCII alphanumeric characters are lowercased and kept. All other characters are replaced by
+/// `_`, with repeated and surrounding underscores removed. Therefore, "Reservoir pH / #3"
+/// becomes "reservoir_ph_3".
+///
+/// # Returns
+///
+/// Sanitized [`String`]. May be empty if `text` contains no alphanumeric characters.
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    if slug.ends_with('_') {
+        slug.pop();
+    }
+    slug
 }
 
 /// Check a sequence of `Result`
 /// This used to check the returned outputs of recursive or parallel operations.
-/// This does not crash the program but instead prints any errors via `dbg!`.
+/// This does not crash the program but instead reports any errors via [`log::error!`].
 pub fn check_results<T>(results: &[Result<T, ErrorType>]) -> Result<(), ErrorType> {
     for result in results {
         match result {
-            Err(e) => eprintln!("█▓▒░ ERROR: {}", e),
+            Err(e) => log::error!("{}", e),
             _ => continue,
         };
     }
     Ok(())
 }
 
+/// Apply Unix permissions to a directory and everything within it
+///
+/// Directories are given `mode`, while files are given `mode` without execute bits (ie: `0o770`
+/// results in `0o660` for files). On non-Unix platforms, nothing is done.
+///
+/// # Parameters
+///
+/// - `path`: Directory to apply permissions to recursively
+/// - `mode`: Permission bits of directories (eg: `0o750`)
+/// - `exclude`: Directories which are skipped along with their contents
+///
+/// # Returns
+///
+/// A `Result` containing:
+///
+/// - `Ok` when permissions of all entries were set
+/// - `Err` with the first error when reading a directory or setting permissions fails
+#[cfg(unix)]
+pub fn set_permissions_recursive(path: &Path, mode: u32, exclude: &[PathBuf]) -> std::io::Result<()> {
+    use std::fs::{read_dir, set_permissions, Permissions};
+    use std::os::unix::fs::PermissionsExt;
+
+    set_permissions(path, Permissions::from_mode(mode))?;
+    for entry in read_dir(path)? {
+        let entry = entry?;
+        match entry.file_type()?.is_dir() {
+            true if exclude.contains(&entry.path()) => (),
+            true => set_permissions_recursive(&entry.path(), mode, exclude)?,
+            false => set_permissions(entry.path(), Permissions::from_mode(mode & 0o666))?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn set_permissions_recursive(_path: &Path, _mode: u32, _exclude: &[PathBuf]) -> std::io::Result<()> {
+    Ok(())
+}
+
 /// Facade for an Arc wrapped around a Mutex with generic type T.
 pub struct Def<T: Sized>(Arc<Mutex<T>>);
 impl<T> Def<T> {
@@ -54,6 +228,25 @@ impl<T> Def<T> {
     pub fn try_lock(&self) -> TryLockResult<MutexGuard<T>> {
         self.0.try_lock()
     }
+
+    /// Mutable access to inner value without locking
+    ///
+    /// Exclusive access is guaranteed at compile time by `&mut self` and at runtime by checking
+    /// that no other references (including [`std::sync::Weak`]) exist. Therefore, this is
+    /// useful in single-threaded loops where locking is pure overhead.
+    ///
+    /// # Returns
+    ///
+    /// - `Some` with mutable reference to inner value if `self` is the only reference
+    /// - `None` if `self` has been cloned
+    ///
+    /// # Panics
+    ///
+    /// If mutex is poisoned
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        Arc::get_mut(&mut self.0)
+            .map(|inner| inner.get_mut().expect("Mutex is poisoned"))
+    }
 }
 
 impl<T: Default> Default for Def<T> {
@@ -79,3 +272,48 @@ impl<T> Into<Arc<Mutex<T>>> for Def<T> {
         self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::helpers::{retry_with_backoff_if, writable_or_create_with_mode};
+
+    #[test]
+    fn retry_backoff_capped() {
+        // huge backoff is capped and does not overflow when doubled
+        let mut calls = 0;
+        let result: Result<(), &str> = retry_with_backoff_if(
+            "", 3, Duration::max_value(), Duration::milliseconds(1), |_| true,
+            || { calls += 1; Err("failed") });
+        assert!(result.is_err());
+        assert_eq!(4, calls);
+    }
+
+    #[test]
+    fn retry_only_retryable() {
+        let mut calls = 0;
+        let result: Result<(), &str> = retry_with_backoff_if(
+            "", 3, Duration::zero(), Duration::zero(), |e| *e == "transient",
+            || { calls += 1; Err(if calls == 1 { "transient" } else { "fatal" }) });
+        assert_eq!(Err("fatal"), result);
+        assert_eq!(2, calls);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn create_with_mode() {
+        use std::fs::{metadata, remove_dir_all};
+        use std::os::unix::fs::PermissionsExt;
+
+        const PATH: &str = "/tmp/sensd_tests/create_with_mode";
+        let _ = remove_dir_all(PATH);
+
+        let mode = |path: &str| metadata(path).unwrap().permissions().mode() & 0o777;
+        writable_or_create_with_mode(format!("{}/dir/file", PATH), Some(0o700)).unwrap();
+        assert_eq!(0o700, mode(&format!("{}/dir", PATH)));
+        assert_eq!(0o600, mode(&format!("{}/dir/file", PATH)));
+
+        remove_dir_all(PATH).unwrap();
+    }
+}
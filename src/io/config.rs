@@ -0,0 +1,141 @@
+use crate::io::DeviceMetadata;
+use serde::{Deserialize, Serialize};
+
+/// Serializable configuration of a device
+///
+/// Only static configuration is stored. Runtime state such as cached state, log events, and the
+/// low-level [`crate::action::IOCommand`] are not included. Therefore, a command must be attached
+/// via [`crate::io::Device::with_command()`] after rebuilding a device. Likewise, subscribers of
+/// [`crate::action::Publisher`] are not included.
+///
+/// # See Also
+///
+/// - [`crate::io::Device::to_config()`] for generating configuration from a device
+/// - [`crate::io::Device::from_config()`] for rebuilding a device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct DeviceConfig {
+    /// Device metadata
+    pub metadata: DeviceMetadata,
+
+    /// Whether device maintains a [`crate::storage::Log`]
+    pub log: bool,
+
+    /// Settings specific to [`crate::io::Input`] devices. `None` for outputs.
+    ///
+    /// Boxed so that configuration of outputs remains small.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<Box<InputConfig>>,
+}
+
+/// Serializable settings of an [`crate::io::Input`]
+///
+/// Durations are stored in milliseconds. Missing fields take their default values, which are
+/// those of a newly constructed [`crate::io::Input`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct InputConfig {
+    /// Explicit bounds of valid readings as set by [`crate::io::Input::set_range()`]
+    pub range: Option<(f32, f32)>,
+
+    /// Whether readings are validated as set by [`crate::io::Input::set_validation()`]
+    pub validate: bool,
+
+    /// Whether kind of readings is checked as set by [`crate::io::Input::set_value_kind_check()`]
+    pub check_value_kind: bool,
+
+    /// Minimum change and maximum interval as set by [`crate::io::Input::set_deadband()`]
+    pub deadband: Option<(f64, Option<i64>)>,
+
+    /// Interval which overrides that of group as set by [`crate::io::Input::set_poll_interval()`]
+    pub poll_interval_ms: Option<i64>,
+
+    /// Number of retries and initial backoff as set by [`crate::io::Input::set_retry()`]
+    pub retry: Option<(u32, i64)>,
+
+    /// Timeout of low-level command as set by [`crate::io::Input::set_timeout()`]
+    pub timeout_ms: Option<i64>,
+
+    /// Unit of time of rate of change as set by [`crate::io::Input::set_rate_of_change()`]
+    pub rate_of_change_ms: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::action::IOCommand;
+    use crate::errors::DeviceError;
+    use crate::io::{Device, DeviceConfig, DeviceGetters, DeviceSetters, Input, IODirection, IOKind, Output, RawValue};
+    use crate::storage::Chronicle;
+
+    #[test]
+    fn input_round_trip() {
+        let mut input = Input::new("input", 3, IOKind::PH).init_log();
+        input.set_description(Some(String::from("calibrated monthly")));
+        input.set_location(Some(String::from("reservoir A, north probe")));
+
+        let json = serde_json::to_string(&input.to_config()).unwrap();
+        let config: DeviceConfig = serde_json::from_str(&json).unwrap();
+        let rebuilt = Input::from_config(&config).unwrap();
+
+        assert_eq!(input, rebuilt);
+        assert_eq!(Some("reservoir A, north probe"), rebuilt.location());
+        assert!(rebuilt.has_log());
+
+        // command is attached separately
+        rebuilt.with_command(IOCommand::input(RawValue::default)).unwrap();
+    }
+
+    #[test]
+    fn input_settings_round_trip() {
+        let input = Input::new("input", 3, IOKind::PH)
+            .set_range(4.0, 9.0)
+            .set_validation(true)
+            .set_value_kind_check(true)
+            .set_deadband(0.1, Duration::minutes(5))
+            .set_poll_interval(Some(Duration::seconds(30)))
+            .set_retry(2, Duration::milliseconds(50))
+            .set_timeout(Duration::seconds(1))
+            .set_rate_of_change(Duration::minutes(1));
+
+        let json = serde_json::to_string(&input.to_config()).unwrap();
+        let config: DeviceConfig = serde_json::from_str(&json).unwrap();
+        let rebuilt = Input::from_config(&config).unwrap();
+
+        assert_eq!(Some((4.0, 9.0)), rebuilt.range());
+        assert_eq!(Some((0.1, Some(Duration::minutes(5)))), rebuilt.deadband());
+        assert_eq!(Some(Duration::seconds(30)), rebuilt.poll_interval());
+        assert_eq!(Some((2, Duration::milliseconds(50))), rebuilt.retry());
+        assert_eq!(Some(Duration::seconds(1)), rebuilt.timeout());
+        assert_eq!(Some(Duration::minutes(1)), rebuilt.rate_of_change());
+        assert_eq!(input.to_config(), rebuilt.to_config());
+
+        // settings of an input which predates them take default values
+        let legacy = r#"{"metadata":{"name":"input","id":3,"kind":"PH","direction":"In"},"log":false}"#;
+        let rebuilt = Input::from_config(&serde_json::from_str(legacy).unwrap()).unwrap();
+        assert_eq!(Input::new("input", 3, IOKind::PH).to_config(), rebuilt.to_config());
+    }
+
+    #[test]
+    fn output_round_trip() {
+        let output = Output::new("output", 4, IOKind::Flow);
+
+        let json = serde_json::to_string(&output.to_config()).unwrap();
+        let config: DeviceConfig = serde_json::from_str(&json).unwrap();
+        let rebuilt = Output::from_config(&config).unwrap();
+
+        assert_eq!(output, rebuilt);
+        assert_eq!(output.direction(), rebuilt.direction());
+        assert!(!rebuilt.has_log());
+        assert!(config.input.is_none());
+    }
+
+    #[test]
+    fn direction_mismatch() {
+        let output = Output::new("output", 4, IOKind::Flow);
+        assert!(matches!(
+            Input::from_config(&output.to_config()),
+            Err(DeviceError::DirectionMismatch {expected: IODirection::In, ..}),
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 use crate::errors::{ContainerError};
 use crate::helpers::Def;
 use crate::io::{Device, IdTraits};
-use std::collections::hash_map::{Entry, Iter, Values, ValuesMut};
+use std::collections::hash_map::{Entry, Iter, IterMut, Values, ValuesMut};
 use std::collections::HashMap;
 use std::fmt::Display;
 use std::ops::DerefMut;
@@ -16,6 +16,18 @@ where
     K: IdTraits + Display + Copy,
     D: Device + Directory,
 {
+    /// Create an empty container with space for at least `capacity` devices
+    ///
+    /// This avoids reallocation when a roster of known size is inserted.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(HashMap::with_capacity(capacity))
+    }
+
+    /// Number of devices container can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
     pub fn values(&self) -> Values<K, Def<D>> {
         self.0.values()
     }
@@ -28,6 +40,14 @@ where
         self.0.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.0.contains_key(k)
+    }
+
     pub fn insert(&mut self, id: K, device: Def<D>) -> Result<Def<D>, ContainerError> {
         match self.0.entry(id) {
             Entry::Occupied(_) => Err(ContainerError::KeyExists {key: id.to_string()}),
@@ -35,14 +55,70 @@ where
         }
     }
 
+    /// Insert device, overwriting any existing device with the same id
+    ///
+    /// Unlike [`DeviceContainer::insert()`], existing entries are not rejected. This is intended
+    /// for reconciling configuration, where a modified device replaces its predecessor.
+    ///
+    /// # Returns
+    ///
+    /// Previous device stored under `id`, or `None` if `id` did not exist in container
+    pub fn insert_or_replace(&mut self, id: K, device: Def<D>) -> Option<Def<D>> {
+        self.0.insert(id, device)
+    }
+
     pub fn get(&self, k: &K) -> Option<&Def<D>> {
         self.0.get(k)
     }
 
+    /// Remove device from container
+    ///
+    /// # Returns
+    ///
+    /// Removed device, or `None` if `k` does not exist in container
+    pub fn remove(&mut self, k: &K) -> Option<Def<D>> {
+        self.0.remove(k)
+    }
+
+    /// Mutable reference to stored device
+    ///
+    /// # Returns
+    ///
+    /// `None` if `k` does not exist in container
+    pub fn get_mut(&mut self, k: &K) -> Option<&mut Def<D>> {
+        self.0.get_mut(k)
+    }
+
+    /// Mutable reference to stored device without locking
+    ///
+    /// # Returns
+    ///
+    /// `None` if `k` does not exist in container or if device is shared elsewhere (ie: an output
+    /// referenced by an [`crate::action::Action`]).
+    ///
+    /// # See Also
+    ///
+    /// - [`Def::get_mut()`] for restrictions
+    pub fn get_mut_unlocked(&mut self, k: &K) -> Option<&mut D> {
+        self.0.get_mut(k).and_then(|device| device.get_mut())
+    }
+
+    /// In-place access to entry of underlying map
+    ///
+    /// Unlike [`DeviceContainer::insert()`], existing entries are not rejected, so this should only
+    /// be used when overwriting or amending an entry is intended.
+    pub fn entry(&mut self, k: K) -> Entry<'_, K, Def<D>> {
+        self.0.entry(k)
+    }
+
     pub fn iter(&self) -> Iter<K, Def<D>> {
         self.0.iter()
     }
 
+    pub fn iter_mut(&mut self) -> IterMut<K, Def<D>> {
+        self.0.iter_mut()
+    }
+
     /// Call [`Device::set_root()`] on all stored device objects
     ///
     /// # Panics
@@ -61,6 +137,7 @@ where
 mod tests {
     use std::ops::Deref;
     use crate::io::{Device, DeviceContainer, Output, Input};
+    use crate::name::Name;
     use crate::storage::{Chronicle, Directory, Document};
 
     #[test]
@@ -104,14 +181,18 @@ mod tests {
         let mut container = DeviceContainer::default();
 
         assert_eq!(0, container.len());
+        assert!(container.is_empty());
 
         for id in 0..ITERATIONS {
+            assert!(!container.contains_key(&id));
+
             let input = Input::new("", id, None).into_deferred();
 
             assert!(
                 container.insert(id, input)
                     .is_ok()
             );
+            assert!(container.contains_key(&id));
             assert_eq!(
                 (id + 1) as usize,
                 container.len()
@@ -132,6 +213,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn with_capacity() {
+        let mut container = DeviceContainer::with_capacity(32);
+        assert!(container.is_empty());
+        assert!(container.capacity() >= 32);
+
+        for id in 0..32 {
+            container.insert(id, Input::new("", id, None).into_deferred()).unwrap();
+        }
+        assert_eq!(32, container.len());
+    }
+
+    #[test]
+    fn get_mut_and_entry() {
+        let mut container = DeviceContainer::default();
+        container.insert(0, Output::new("", 0, None).into_deferred()).unwrap();
+
+        assert!(container.get_mut(&1).is_none());
+        *container.get_mut(&0).unwrap() = Output::new("replaced", 0, None).into_deferred();
+        assert_eq!("replaced", container.get(&0).unwrap().try_lock().unwrap().name());
+
+        container.entry(1).or_insert_with(|| Output::new("", 1, None).into_deferred());
+        assert_eq!(2, container.len());
+
+        // `insert()` still rejects existing keys
+        assert!(container.insert(1, Output::new("", 1, None).into_deferred()).is_err());
+    }
+
+    #[test]
+    fn insert_or_replace() {
+        let mut container = DeviceContainer::default();
+        container.insert(1, Output::new("other", 1, None).into_deferred()).unwrap();
+
+        assert!(container.insert_or_replace(0, Output::new("a", 0, None).into_deferred()).is_none());
+
+        let previous = container.insert_or_replace(0, Output::new("b", 0, None).into_deferred()).unwrap();
+        assert_eq!("a", previous.try_lock().unwrap().name());
+        assert_eq!("b", container.get(&0).unwrap().try_lock().unwrap().name());
+
+        // remaining entries are untouched
+        assert_eq!(2, container.len());
+        assert_eq!("other", container.get(&1).unwrap().try_lock().unwrap().name());
+    }
+
+    #[test]
+    fn get_mut_unlocked() {
+        let mut container = DeviceContainer::default();
+        container.insert(0, Input::new("", 0, None).into_deferred()).unwrap();
+
+        assert!(container.get_mut_unlocked(&1).is_none());
+        assert!(container.get_mut_unlocked(&0).is_some());
+
+        // shared devices cannot be accessed without locking
+        let shared = container.get(&0).unwrap().clone();
+        assert!(container.get_mut_unlocked(&0).is_none());
+
+        drop(shared);
+        assert!(container.get_mut_unlocked(&0).is_some());
+    }
+
     #[test]
     /// Ensure that [`Device::set_root()`] is called on each device
     fn set_root() {
@@ -11,10 +11,10 @@
 use std::path::{Path};
 use crate::action::IOCommand;
 use crate::helpers::Def;
-use crate::io::{DeviceMetadata, IODirection, IOKind, IdType, RawValue};
+use crate::io::{DeviceConfig, DeviceMetadata, IOEvent, IODirection, IOKind, IdType, RawValue};
 use crate::storage::Document;
 use crate::storage::{Chronicle, Log, Persistent};
-use crate::errors::ErrorType;
+use crate::errors::{DeviceError, ErrorType};
 use crate::name::Name;
 
 /// Common constructors and builder methods for all device types
@@ -42,10 +42,54 @@ pub trait Device: Name + Chronicle + DeviceGetters + DeviceSetters + Persistent
     /// # Returns
     ///
     /// Passes ownership of `self`
+    ///
+    /// # Panics
+    ///
+    /// If direction of `command` does not agree with device
+    #[deprecated(note = "use `Device::with_command()` which returns an error instead of panicking")]
     fn set_command(self, command: IOCommand) -> Self
+    where
+        Self: Sized
+    {
+        self.with_command(command)
+            .expect("Command direction does not agree with device")
+    }
+
+    /// Fallible builder method for setting `command` field
+    ///
+    /// Direction of `command` is validated using [`IOCommand::agrees()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `command`: Low-level command used to interact with hardware
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with ownership of `self` with `command` set
+    /// - `Err` with [`crate::errors::DeviceError::CommandMismatch`] if direction of `command`
+    ///   does not agree with device.
+    fn with_command(self, command: IOCommand) -> Result<Self, ErrorType>
     where
         Self: Sized;
 
+    /// Verify that low-level command is functional
+    ///
+    /// This is meant to be called during commissioning, before devices are polled, so that a
+    /// misconfigured or miswired device can be flagged early. No [`crate::io::IOEvent`] is
+    /// generated or logged.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` that is:
+    ///
+    /// - `Ok` if device is operational. Default implementation always returns `Ok`.
+    /// - `Err` containing the error encountered during test
+    fn self_test(&self) -> Result<(), ErrorType> {
+        Ok(())
+    }
+
     /// Initialize, set, and return log.
     fn init_log(mut self) -> Self
     where
@@ -56,6 +100,46 @@ pub trait Device: Name + Chronicle + DeviceGetters + DeviceSetters + Persistent
         self
     }
 
+    /// Generate serializable configuration
+    ///
+    /// # Returns
+    ///
+    /// [`DeviceConfig`] which can be used to rebuild device with [`Device::from_config()`]
+    fn to_config(&self) -> DeviceConfig
+    where
+        Self: Sized
+    {
+        DeviceConfig {
+            metadata: self.metadata().clone(),
+            log: self.has_log(),
+            input: None,
+        }
+    }
+
+    /// Rebuild device from configuration
+    ///
+    /// No command is associated. [`Device::with_command()`] should be chained afterwards.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: Configuration generated by [`Device::to_config()`]
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with device using metadata from `config`. A new [`Log`] is initialized if `config`
+    ///   specifies one.
+    /// - `Err` with [`DeviceError::DirectionMismatch`] if direction stored in `config` does not
+    ///   agree with device type, or with [`DeviceError::InvalidBounds`] if bounds stored in
+    ///   `config` are invalid for the kind of device
+    fn from_config(config: &DeviceConfig) -> Result<Self, DeviceError>
+    where
+        Self: Sized
+    {
+        from_device_config(config)
+    }
+
     fn into_deferred(self) -> Def<Self>
     where
         Self: Sized
@@ -64,6 +148,32 @@ pub trait Device: Name + Chronicle + DeviceGetters + DeviceSetters + Persistent
     }
 }
 
+/// Build device from settings which are common to all device types
+///
+/// Used by [`Device::from_config()`] so that implementors which override it may extend, rather
+/// than duplicate, the default behavior.
+pub(crate) fn from_device_config<D: Device>(config: &DeviceConfig) -> Result<D, DeviceError> {
+    let metadata = &config.metadata;
+    let mut device = D::new(metadata.name.clone(), metadata.id, metadata.kind);
+    if device.direction() != metadata.direction {
+        return Err(DeviceError::DirectionMismatch {metadata: metadata.clone(), expected: device.direction()});
+    }
+
+    device.set_description(metadata.description.clone());
+    device.set_location(metadata.location.clone());
+    if let Some((min, max)) = metadata.raw_bounds() {
+        if metadata.validate_bounds().is_err() {
+            return Err(DeviceError::InvalidBounds {metadata: metadata.clone(), min, max});
+        }
+    }
+    device.set_raw_bounds(metadata.raw_bounds());
+
+    Ok(match config.log {
+        true => device.init_log(),
+        false => device,
+    })
+}
+
 /// Common getter methods shared by all device types
 pub trait DeviceGetters {
     /// Reference to device metadata
@@ -89,11 +199,39 @@ pub trait DeviceGetters {
         self.metadata().direction
     }
 
+    /// Check if device is an input
+    ///
+    /// # Returns
+    ///
+    /// `true` if [`DeviceGetters::direction()`] is [`IODirection::In`]
+    fn is_input(&self) -> bool {
+        self.direction() == IODirection::In
+    }
+
+    /// Check if device is an output
+    ///
+    /// # Returns
+    ///
+    /// `true` if [`DeviceGetters::direction()`] is [`IODirection::Out`]
+    fn is_output(&self) -> bool {
+        self.direction() == IODirection::Out
+    }
+
     /// Returns the type of device as `IOKind`.
     fn kind(&self) -> IOKind {
         self.metadata().kind
     }
 
+    /// Returns the user given description of the device, if any.
+    fn description(&self) -> Option<&str> {
+        self.metadata().description.as_deref()
+    }
+
+    /// Returns the user given location of the device, if any.
+    fn location(&self) -> Option<&str> {
+        self.metadata().location.as_deref()
+    }
+
     /// Immutable reference to cached state
     ///
     /// # Returns
@@ -102,6 +240,30 @@ pub trait DeviceGetters {
     /// - `None` upon initialization since device has not been read from or written to.
     /// - `RawValue` after first read or write, and represents last known state.
     fn state(&self) -> &Option<RawValue>;
+
+    /// Clone of the most recent event stored in device log
+    ///
+    /// For an [`crate::io::Output`], this reflects the last write. Unlike
+    /// [`DeviceGetters::state()`], the timestamp and tags of the event are also available.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` that is:
+    ///
+    /// - `Some` with the [`IOEvent`] with the latest timestamp
+    /// - `None` if device has no log, log is empty, or log is currently locked
+    ///
+    /// # See Also
+    ///
+    /// - [`Log::latest()`] for a reference to the most recent event
+    fn last_event(&self) -> Option<IOEvent>
+    where
+        Self: Chronicle
+    {
+        let log = self.log()?;
+        let binding = log.try_lock().ok()?;
+        binding.latest().cloned()
+    }
 }
 
 /// Command setter methods share by all device types
@@ -110,6 +272,22 @@ pub trait DeviceSetters {
 
     /// Setter for `log` field
     fn set_log(&mut self, log: Def<Log>);
+
+    /// Setter for descriptive notes stored in metadata
+    ///
+    /// This is purely descriptive and does not affect logging or polling.
+    fn set_description(&mut self, description: Option<String>);
+
+    /// Setter for physical location stored in metadata
+    ///
+    /// This is purely descriptive and does not affect logging or polling.
+    fn set_location(&mut self, location: Option<String>);
+
+    /// Setter for scalar bounds of valid readings stored in metadata
+    ///
+    /// Bounds are not validated. This is meant for restoring bounds from configuration after
+    /// [`DeviceMetadata::validate_bounds()`]. Otherwise, [`DeviceMetadata::set_bounds()`] should be used.
+    fn set_raw_bounds(&mut self, bounds: Option<(f32, f32)>);
 }
 
 impl<T: Device> Persistent for T {
@@ -126,6 +304,20 @@ impl<T: Device> Persistent for T {
             None => Ok(())
         }
     }
+
+    fn save_to(&self, path: &Path) -> Result<(), ErrorType> {
+        match self.log() {
+            Some(log) => log.try_lock().unwrap().save_to(path),
+            None => Ok(())
+        }
+    }
+
+    fn load_from(&mut self, path: &Path) -> Result<(), ErrorType> {
+        match self.log() {
+            Some(log) => log.try_lock().unwrap().load_from(path),
+            None => Ok(())
+        }
+    }
 }
 
 /// Helper for setting log directory
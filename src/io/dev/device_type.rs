@@ -0,0 +1,23 @@
+use crate::helpers::Def;
+use crate::io::{IODirection, Input, Output};
+
+/// Shared reference to either an [`Input`] or [`Output`] device
+///
+/// Used when devices of both directions are returned together (ie:
+/// [`crate::storage::Group::devices_by_kind()`]), or when direction is unknown (ie:
+/// [`crate::storage::Group::find_device()`]).
+#[derive(Clone)]
+pub enum DeviceType {
+    Input(Def<Input>),
+    Output(Def<Output>),
+}
+
+impl DeviceType {
+    /// Direction of referenced device
+    pub fn direction(&self) -> IODirection {
+        match self {
+            Self::Input(_) => IODirection::In,
+            Self::Output(_) => IODirection::Out,
+        }
+    }
+}
@@ -1,12 +1,16 @@
 use std::fmt::Formatter;
+use chrono::{DateTime, Duration, Utc};
 use std::path::{Path, PathBuf};
 use crate::action::{Command, IOCommand, Publisher};
-use crate::errors::DeviceError;
-use crate::helpers::Def;
-use crate::io::{Device, DeviceMetadata, IODirection, IOEvent, IOKind, IdType, RawValue, DeviceGetters, DeviceSetters};
-use crate::io::dev::device::set_log_dir;
+use crate::clock;
+use crate::errors::{DeviceError, ErrorType, ValueError};
+use crate::helpers::{retry_with_backoff_if, Def};
+use crate::io::{kind_defaults, Aggregation, Device, DeviceConfig, DeviceMetadata, IODirection, IOEvent, IOKind, IdType, InputConfig, RawValue, DeviceGetters, DeviceSetters, RATE_TAG};
+use crate::io::dev::device::{from_device_config, set_log_dir};
 use crate::name::Name;
+use crate::settings::MAX_BACKOFF_SECS;
 use crate::storage::{Chronicle, Directory, Log};
+use crate::units::Unit;
 
 #[derive(Default)]
 /// This is the generic implementation for any external input device.
@@ -33,17 +37,17 @@ use crate::storage::{Chronicle, Directory, Log};
 /// ```
 ///
 /// Now that we are able to set device metadata, constructor methods still don't
-/// provide any way to interact with hardware. The builder method [`Device::set_command()`]
+/// provide any way to interact with hardware. The builder method [`Device::with_command()`]
 /// is used to add low-level code. In this example, we return a static value:
 ///
 /// ```
 /// use sensd::action::IOCommand;
 /// use sensd::io::{Device, Input, RawValue};
 ///
-/// let command = IOCommand::Input(|| RawValue::Binary(true));
+/// let command = IOCommand::input(|| RawValue::Binary(true));
 /// let input =
 ///     Input::default()
-///         .set_command(command);
+///         .with_command(command).unwrap();
 /// ```
 ///
 /// With a `command` set, [`Input::read()`] can be used to generate [`IOEvent`] objects
@@ -55,6 +59,39 @@ pub struct Input {
     command: Option<IOCommand>,
     state: Option<RawValue>,
 
+    /// Number of consecutive failed reads
+    failures: u32,
+    /// Timestamp of last successful read
+    last_read: Option<DateTime<Utc>>,
+
+    /// Maximum duration to wait for `command`
+    timeout: Option<Duration>,
+
+    /// Maximum number of retries of a failed read and delay before first retry
+    retry: Option<(u32, Duration)>,
+
+    /// Interval between polls which overrides that of group
+    poll_interval: Option<Duration>,
+
+    /// Explicit bounds of valid readings
+    range: Option<(f32, f32)>,
+
+    /// Reject readings outside of [`Input::range()`]
+    validate: bool,
+
+    /// Reject readings whose kind differs from [`IOKind::expected_value_kind()`]
+    check_value_kind: bool,
+
+    /// Minimum change and maximum interval between logged events
+    deadband: Option<(f64, Option<Duration>)>,
+    /// Timestamp and value of last logged event
+    last_logged: Option<(DateTime<Utc>, RawValue)>,
+
+    /// Unit of time of emitted rate of change
+    rate: Option<Duration>,
+    /// Timestamp and value of last reading used to calculate rate of change
+    last_sample: Option<(DateTime<Utc>, f64)>,
+
     dir: Option<PathBuf>,
 }
 
@@ -69,7 +106,7 @@ impl Device for Input {
     ///
     /// # Returns
     ///
-    /// Partially initialized [`Input`]. The builder method [`Device::set_command()`]
+    /// Partially initialized [`Input`]. The builder method [`Device::with_command()`]
     /// needs to be called to assign an [`IOCommand`] to interact with hardware.
     fn new<N, K>(name: N, id: IdType, kind: K) -> Self
     where
@@ -86,6 +123,23 @@ impl Device for Input {
         let log = None;
         let state = None;
 
+        let failures = 0;
+        let last_read = None;
+        let timeout = None;
+
+        let retry = None;
+        let poll_interval = None;
+
+        let range = None;
+        let validate = false;
+        let check_value_kind = false;
+
+        let deadband = None;
+        let last_logged = None;
+
+        let rate = None;
+        let last_sample = None;
+
         let dir = None;
 
         Self {
@@ -94,18 +148,80 @@ impl Device for Input {
             publisher,
             command,
             state,
+            failures,
+            last_read,
+            timeout,
+            retry,
+            poll_interval,
+            range,
+            validate,
+            check_value_kind,
+            deadband,
+            last_logged,
+            rate,
+            last_sample,
             dir,
         }
     }
 
-    fn set_command(mut self, command: IOCommand) -> Self
+    fn with_command(mut self, command: IOCommand) -> Result<Self, ErrorType>
     where
         Self: Sized,
     {
-        command.agrees(IODirection::In)
-            .expect("Command is not input");
+        if command.agrees(IODirection::In).is_err() {
+            return Err(Box::new(DeviceError::CommandMismatch {metadata: self.metadata.clone()}));
+        }
         self.command = Some(command);
-        self
+        Ok(self)
+    }
+
+    /// Read from device once and discard value
+    ///
+    /// Value is not cached, logged, or propagated.
+    fn self_test(&self) -> Result<(), ErrorType> {
+        self.rx()?;
+        Ok(())
+    }
+
+    /// Generate serializable configuration, including settings specific to inputs
+    fn to_config(&self) -> DeviceConfig {
+        let millis = |duration: Duration| duration.num_milliseconds();
+
+        DeviceConfig {
+            metadata: self.metadata.clone(),
+            log: self.has_log(),
+            input: Some(Box::new(InputConfig {
+                range: self.range,
+                validate: self.validate,
+                check_value_kind: self.check_value_kind,
+                deadband: self.deadband.map(|(delta, max_interval)| (delta, max_interval.map(millis))),
+                poll_interval_ms: self.poll_interval.map(millis),
+                retry: self.retry.map(|(retries, backoff)| (retries, millis(backoff))),
+                timeout_ms: self.timeout.map(millis),
+                rate_of_change_ms: self.rate.map(millis),
+            })),
+        }
+    }
+
+    /// Rebuild input from configuration, including settings specific to inputs
+    ///
+    /// Settings which are missing from `config` take default values.
+    fn from_config(config: &DeviceConfig) -> Result<Self, DeviceError> {
+        let mut device: Self = from_device_config(config)?;
+
+        if let Some(input) = &config.input {
+            let millis = Duration::milliseconds;
+
+            device.range = input.range;
+            device.validate = input.validate;
+            device.check_value_kind = input.check_value_kind;
+            device.deadband = input.deadband.map(|(delta, max_interval)| (delta, max_interval.map(millis)));
+            device.poll_interval = input.poll_interval_ms.map(millis);
+            device.retry = input.retry.map(|(retries, backoff)| (retries, millis(backoff)));
+            device.timeout = input.timeout_ms.map(millis);
+            device.rate = input.rate_of_change_ms.map(millis);
+        }
+        Ok(device)
     }
 }
 
@@ -170,11 +286,54 @@ impl DeviceSetters for Input {
             set_log_dir(Some(log), dir)
         }
     }
+
+    fn set_description(&mut self, description: Option<String>) {
+        self.metadata.description = description;
+    }
+
+    fn set_location(&mut self, location: Option<String>) {
+        self.metadata.location = location;
+    }
+
+    fn set_raw_bounds(&mut self, bounds: Option<(f32, f32)>) {
+        self.metadata.bounds = bounds;
+    }
 }
 
 impl Input {
+    /// Constructor for a mock device which does not interact with hardware
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: name of device
+    /// - `id`: device ID
+    /// - `value`: value returned by every read
+    ///
+    /// # Returns
+    ///
+    /// [`Input`] with [`IOCommand::null_input()`] as command
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::{Input, RawValue};
+    ///
+    /// let mut input = Input::mock("mock", 0, RawValue::Float(6.5));
+    ///
+    /// assert_eq!(RawValue::Float(6.5), input.read().unwrap().value);
+    /// ```
+    pub fn mock<N>(name: N, id: IdType, value: RawValue) -> Self
+    where
+        N: Into<String>
+    {
+        Self::new(name, id, None)
+            .with_command(IOCommand::null_input(value)).unwrap()
+    }
+
     /// Execute low-level GPIO command to read data
     ///
+    /// When `command` is [`IOCommand::MultiInput`], only the event of the device kind is returned.
+    ///
     /// # Returns
     ///
     /// A `Result` containing:
@@ -186,19 +345,92 @@ impl Input {
     ///
     /// [Low level error type](https://github.com/PoorRican/sensd/issues/192)
     fn rx(&self) -> Result<IOEvent, DeviceError> {
-        let read_value = if let Some(command) = &self.command {
-            // execute command
-            let result = command.execute(None)?;
-            // return error if no value is read from device
-            match result {
-                None => Err(DeviceError::ValueExpected {metadata: self.metadata.clone()})?,
-                Some(inner) => inner,
+        let mut events = self.rx_all()?;
+        let primary = self.primary(&events);
+        Ok(events.swap_remove(primary))
+    }
+
+    /// Execute low-level GPIO command to read all values reported by device
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with one [`IOEvent`] per value. Only [`IOCommand::MultiInput`] yields more than one
+    ///   event, in which case events share a timestamp and are labeled by kind.
+    /// - `Err` if no `command` is set, if command failed, or if no value was read
+    fn rx_all(&self) -> Result<Vec<IOEvent>, DeviceError> {
+        let command = self.command.as_ref()
+            .ok_or_else(|| DeviceError::NoCommand {metadata: self.metadata.clone()})?;
+
+        if let IOCommand::MultiInput(_) = command {
+            let values = match self.timeout {
+                Some(timeout) => command.execute_multi_timeout(timeout),
+                None => command.execute_multi(),
+            }.map_err(|e| e.with_metadata(&self.metadata))?;
+            if values.is_empty() {
+                return Err(DeviceError::ValueExpected {metadata: self.metadata.clone()});
             }
-        } else {
-            Err(DeviceError::NoCommand {metadata: self.metadata.clone()})?
+
+            let timestamp = clock::now();
+            return Ok(values.into_iter()
+                .map(|(kind, value)| {
+                    let mut event = IOEvent::with_timestamp(timestamp, value);
+                    event.kind = Some(kind);
+                    event
+                })
+                .collect());
+        }
+
+        // execute command
+        let result = match self.timeout {
+            Some(timeout) => command.execute_timeout(None, timeout),
+            None => command.execute(None),
+        }.map_err(|e| e.with_metadata(&self.metadata))?;
+        // return error if no value is read from device
+        match result {
+            None => Err(DeviceError::ValueExpected {metadata: self.metadata.clone()}),
+            Some(inner) => Ok(vec![IOEvent::new(inner)]),
+        }
+    }
+
+    /// Index of event which represents the device itself
+    ///
+    /// # Returns
+    ///
+    /// Index of first event whose kind is unlabeled or matches device kind. Otherwise, `0`.
+    fn primary(&self, events: &[IOEvent]) -> usize {
+        events.iter()
+            .position(|event| event.kind.is_none_or(|kind| kind == self.metadata.kind))
+            .unwrap_or_default()
+    }
+
+    /// Execute [`Input::rx_all()`], retrying failed attempts according to retry policy
+    ///
+    /// Only failures of the low-level command are retried. Each retry is logged at `warn` level.
+    ///
+    /// # See Also
+    ///
+    /// - [`Input::set_retry()`] for how total duration is bounded
+    fn rx_retry(&self) -> Result<Vec<IOEvent>, DeviceError> {
+        let (retries, backoff) = match self.retry {
+            Some(retry) => retry,
+            None => return self.rx_all(),
+        };
+        let max_delay = match self.timeout {
+            Some(timeout) => timeout.min(Duration::seconds(MAX_BACKOFF_SECS)),
+            None => Duration::seconds(MAX_BACKOFF_SECS),
         };
 
-        Ok(IOEvent::new(read_value))
+        retry_with_backoff_if(
+            &format!("Read from {}", self.metadata),
+            retries,
+            backoff,
+            max_delay,
+            |error| matches!(error, DeviceError::HWFault {..} | DeviceError::ValueExpected {..}
+                | DeviceError::CommandFailed {..} | DeviceError::Timeout | DeviceError::Busy),
+            || self.rx_all(),
+        )
     }
 
     /// Propagate `IOEvent` to all subscribers.
@@ -214,6 +446,20 @@ impl Input {
         };
     }
 
+    /// Propagate secondary value of a composite device to subscribers of its kind, or rate of
+    /// change to subscribers of rate
+    ///
+    /// Silently fails when there is no associated publisher.
+    ///
+    /// # Parameters
+    ///
+    /// - `event`: A reference to [`IOEvent`] labeled by kind or tagged with [`RATE_TAG`]
+    fn propagate_secondary(&mut self, event: &IOEvent) {
+        if let Some(publisher) = &mut self.publisher {
+            publisher.propagate_secondary(event);
+        };
+    }
+
     /// Get IOEvent, add to log, and propagate to publisher/subscribers
     ///
     /// Primary interface method during polling.
@@ -240,15 +486,15 @@ impl Input {
     /// use sensd::io::{Device, DeviceGetters, Input, RawValue};
     ///
     /// let value = RawValue::default();
-    /// let command = IOCommand::Input(|| RawValue::default());
-    /// let mut input = Input::default().set_command(command);
+    /// let command = IOCommand::input(|| RawValue::default());
+    /// let mut input = Input::default().with_command(command).unwrap();
     ///
     /// let event = input.read().unwrap();
     ///
     /// assert_eq!(event.value, value);
     ///
     /// // cached state is updated
-    /// assert_eq!(input.state().unwrap(), value);
+    /// assert_eq!(input.state().clone().unwrap(), value);
     /// ```
     ///
     /// # See Also
@@ -256,17 +502,631 @@ impl Input {
     /// - [`Publisher::propagate()`] for how [`IOEvent`] is given to subscribing [`Action`]'s
     /// - [`Input::push_to_log()`] for adding [`IOEvent`] to [`Log`]
     pub fn read(&mut self) -> Result<IOEvent, DeviceError> {
-        let event = self.rx()?;
+        let (mut events, primary) = self.read_events()?;
+        Ok(events.swap_remove(primary))
+    }
+
+    /// Read all values reported by device
+    ///
+    /// This is the counterpart of [`Input::read()`] for composite sensors whose command is
+    /// [`IOCommand::MultiInput`]. Every value is returned as a distinct [`IOEvent`] labeled by kind,
+    /// and all events share a timestamp. For any other command, a single event is returned.
+    ///
+    /// The event matching the device kind (or the first event if none match) is the primary
+    /// event: it updates cached state, is logged, and is propagated by [`Publisher::propagate()`].
+    /// The remaining secondary events are propagated by [`Publisher::propagate_secondary()`], so
+    /// that only subscribers of their kind receive them (see [`crate::action::EventSource`]).
+    /// Since a [`Log`] holds one event per timestamp, secondary events are not logged.
+    ///
+    /// Every event is validated against its own kind. An invalid primary event fails the read,
+    /// while an invalid secondary event is discarded with a warning so that valid readings are
+    /// kept.
+    ///
+    /// When enabled by [`Input::set_rate_of_change()`], an event tagged with [`RATE_TAG`] is
+    /// appended after all events read and is only propagated to subscribers of
+    /// [`crate::action::EventSource::Rate`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with all valid events read
+    /// - `Err` if read failed or primary value is invalid. Nothing is recorded in that case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::action::IOCommand;
+    /// use sensd::io::{Device, DeviceGetters, Input, IOKind, RawValue};
+    ///
+    /// let command = IOCommand::multi_input(|| vec![
+    ///     (IOKind::Temperature, RawValue::Float(21.5)),
+    ///     (IOKind::RelativeHumidity, RawValue::Float(48.0)),
+    /// ]);
+    /// let mut input = Input::new("bme280", 0, IOKind::Temperature)
+    ///     .with_command(command).unwrap();
+    ///
+    /// let events = input.read_all().unwrap();
+    ///
+    /// assert_eq!(2, events.len());
+    /// assert_eq!(events[0].timestamp, events[1].timestamp);
+    /// assert_eq!(Some(IOKind::RelativeHumidity), events[1].kind);
+    /// assert_eq!(Some(RawValue::Float(21.5)), *input.state());
+    /// ```
+    pub fn read_all(&mut self) -> Result<Vec<IOEvent>, DeviceError> {
+        self.read_events().map(|(events, _)| events)
+    }
+
+    /// Implementation of [`Input::read_all()`] which also returns index of primary event
+    ///
+    /// Primary event is identified before any rate of change event is appended, since an appended
+    /// event is unlabeled and would otherwise be mistaken for the primary event.
+    fn read_events(&mut self) -> Result<(Vec<IOEvent>, usize), DeviceError> {
+        let read = match self.rx_retry() {
+            Ok(events) => events,
+            Err(e) => {
+                self.failures += 1;
+                return Err(e);
+            }
+        };
+
+        let mut primary = self.primary(&read);
+        let mut events = Vec::with_capacity(read.len() + 1);
+        for (index, event) in read.into_iter().enumerate() {
+            match self.validated(event) {
+                Ok(event) => {
+                    if index == primary {
+                        primary = events.len();
+                    }
+                    events.push(event);
+                }
+                Err(e) if index == primary => {
+                    self.failures += 1;
+                    return Err(e);
+                }
+                Err(e) => log::warn!("Discarding secondary value: {}", e),
+            }
+        }
+
+        for (index, event) in events.iter().enumerate() {
+            match index == primary {
+                true => self.record(event),
+                false => self.propagate_secondary(event),
+            }
+        }
+
+        if let Some(rate) = self.rate_event(&events[primary]) {
+            self.propagate_secondary(&rate);
+            events.push(rate);
+        }
+
+        Ok((events, primary))
+    }
+
+    /// Read multiple samples and record a single aggregated event
+    ///
+    /// This reduces noise from analog inputs without the latency of a moving average spanning
+    /// multiple polling cycles. Samples are read back-to-back, then combined using
+    /// [`Aggregation::aggregate()`]. Only the aggregated [`IOEvent`] is logged and propagated.
+    ///
+    /// Binary inputs are combined by majority vote.
+    ///
+    /// # Parameters
+    ///
+    /// - `count`: Number of samples to read
+    /// - `agg`: Method used to combine samples
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with aggregated [`IOEvent`]
+    /// - `Err` if any sample fails to be read, or [`DeviceError::ValueExpected`] if `count` is zero.
+    ///   When validation is enabled, the aggregated value is validated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::action::IOCommand;
+    /// use sensd::io::{Aggregation, Device, Input, RawValue};
+    ///
+    /// let mut input = Input::default()
+    ///     .with_command(IOCommand::input(|| RawValue::Float(0.5))).unwrap();
+    ///
+    /// assert_eq!(RawValue::Float(0.5), input.read_n(8, Aggregation::Median).unwrap().value);
+    /// ```
+    pub fn read_n(&mut self, count: usize, agg: Aggregation) -> Result<IOEvent, ErrorType> {
+        let mut samples = Vec::with_capacity(count);
+        for _ in 0..count {
+            match self.rx() {
+                Ok(event) => samples.push(event.value),
+                Err(e) => {
+                    self.failures += 1;
+                    return Err(Box::new(e));
+                }
+            }
+        }
 
-        // Update cached state
-        self.state = Some(event.value);
+        let value = agg.aggregate(&samples)
+            .ok_or_else(|| DeviceError::ValueExpected {metadata: self.metadata.clone()})?;
+        let event = match self.validated(IOEvent::new(value)) {
+            Ok(event) => event,
+            Err(e) => {
+                self.failures += 1;
+                return Err(Box::new(e));
+            }
+        };
 
-        self.propagate(&event);
-        self.push_to_log(&event);
+        self.record(&event);
 
         Ok(event)
     }
 
+    /// Check that value of `event` has the expected kind and is within [`Input::range()`]
+    ///
+    /// Kind is only checked when enabled by [`Input::set_value_kind_check()`]. Non-numeric values
+    /// and events from devices without validation enabled are always within range. Events labeled
+    /// with a kind other than the device kind are checked against defaults of their own kind.
+    ///
+    /// # Returns
+    ///
+    /// `event` if valid, otherwise [`DeviceError::ValueKindMismatch`] or
+    /// [`DeviceError::OutOfRange`]
+    fn validated(&self, event: IOEvent) -> Result<IOEvent, DeviceError> {
+        let kind = event.kind.unwrap_or(self.metadata.kind);
+        let expected = kind.expected_value_kind();
+        if self.check_value_kind && event.value.kind() != expected {
+            return Err(DeviceError::ValueKindMismatch {
+                metadata: self.metadata.clone(),
+                expected,
+                value: event.value,
+            });
+        }
+
+        if !self.validate {
+            return Ok(event);
+        }
+        let range = match kind == self.metadata.kind {
+            true => self.range(),
+            false => kind_defaults(kind),
+        };
+        match (range, event.value.as_f64()) {
+            (Some((min, max)), Some(value)) if value < min.into() || value > max.into() => {
+                Err(DeviceError::OutOfRange {metadata: self.metadata.clone(), value: event.value})
+            }
+            _ => Ok(event),
+        }
+    }
+
+    /// Builder method for enabling validation of readings
+    ///
+    /// When enabled, numeric readings outside of [`Input::range()`] are rejected by
+    /// [`Input::read()`] with [`DeviceError::OutOfRange`], counted as a failed read, and are not
+    /// logged or propagated. Validation is disabled by default.
+    ///
+    /// # Parameters
+    ///
+    /// - `enabled`: Whether readings should be validated
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::action::IOCommand;
+    /// use sensd::io::{Device, Input, IOKind, RawValue};
+    ///
+    /// // disconnected probe
+    /// let mut input = Input::new("pH", 0, IOKind::PH)
+    ///     .with_command(IOCommand::input(|| RawValue::Float(25.0))).unwrap()
+    ///     .set_validation(true);
+    ///
+    /// assert!(input.read().is_err());
+    /// ```
+    pub fn set_validation(mut self, enabled: bool) -> Self {
+        self.validate = enabled;
+        self
+    }
+
+    /// Builder method for rejecting readings of an unexpected kind
+    ///
+    /// When enabled, readings whose [`RawValue::kind()`] differs from
+    /// [`IOKind::expected_value_kind()`] of the device kind (ie: a binary value from a pH probe)
+    /// are rejected by [`Input::read()`] with [`DeviceError::ValueKindMismatch`], counted as a
+    /// failed read, and are not logged or propagated. This catches miswired or misconfigured
+    /// devices early. The check is disabled by default so that generic devices are not
+    /// constrained.
+    ///
+    /// # Parameters
+    ///
+    /// - `enabled`: Whether kind of readings should be checked
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::action::IOCommand;
+    /// use sensd::io::{Device, Input, IOKind, RawValue};
+    ///
+    /// let mut input = Input::new("pH", 0, IOKind::PH)
+    ///     .with_command(IOCommand::input(|| RawValue::Binary(true))).unwrap()
+    ///     .set_value_kind_check(true);
+    ///
+    /// assert!(input.read().is_err());
+    /// ```
+    pub fn set_value_kind_check(mut self, enabled: bool) -> Self {
+        self.check_value_kind = enabled;
+        self
+    }
+
+    /// Builder method for setting explicit bounds of valid readings
+    ///
+    /// Bounds are only used when validation is enabled by [`Input::set_validation()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: Inclusive lower bound
+    /// - `max`: Inclusive upper bound
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    pub fn set_range(mut self, min: f32, max: f32) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Builder method for setting bounds of valid readings expressed in the unit of device kind
+    ///
+    /// Unlike [`Input::set_range()`], bounds are validated by `U` and are stored in
+    /// [`DeviceMetadata`], therefore they are persisted with device configuration. Bounds are only
+    /// used when validation is enabled by [`Input::set_validation()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: Inclusive lower bound
+    /// - `max`: Inclusive upper bound
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with ownership of `self` to allow method chaining
+    /// - `Err` if bounds are rejected by [`DeviceMetadata::set_bounds()`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::{Device, Input, IOKind};
+    /// use sensd::units::Ph;
+    ///
+    /// let input = Input::new("pH", 0, IOKind::PH)
+    ///     .set_bounds(Ph(5.5), Ph(7.5)).unwrap();
+    /// assert_eq!(Some((5.5, 7.5)), input.range());
+    ///
+    /// assert!(Input::new("pH", 0, IOKind::PH).set_bounds(Ph(0.0), Ph(1000.0)).is_err());
+    /// ```
+    pub fn set_bounds<U: Unit>(mut self, min: U, max: U) -> Result<Self, ValueError> {
+        self.metadata.set_bounds(min, max)?;
+        Ok(self)
+    }
+
+    /// Getter for bounds of valid readings
+    ///
+    /// # Returns
+    ///
+    /// Explicit bounds if set by [`Input::set_range()`], then bounds set by
+    /// [`Input::set_bounds()`], otherwise default range of device kind given by
+    /// [`kind_defaults()`]. `None` if none exist.
+    pub fn range(&self) -> Option<(f32, f32)> {
+        self.range
+            .or(self.metadata.raw_bounds())
+            .or_else(|| kind_defaults(self.metadata.kind))
+    }
+
+    /// Update cached state, then propagate and log successfully read event
+    fn record(&mut self, event: &IOEvent) {
+        self.state = Some(event.value.clone());
+        self.failures = 0;
+        self.last_read = Some(event.timestamp);
+
+        self.propagate(event);
+
+        if self.should_log(event) {
+            self.push_to_log(event);
+            self.last_logged = Some((event.timestamp, event.value.clone()));
+        }
+    }
+
+    /// Check if `event` falls outside of deadband and therefore should be logged
+    ///
+    /// # Returns
+    ///
+    /// `true` if no deadband is set, no event has been logged, the maximum interval has elapsed,
+    /// or value differs from last logged value by more than delta. Non-numeric values are logged
+    /// whenever they change.
+    fn should_log(&self, event: &IOEvent) -> bool {
+        let ((delta, max_interval), (timestamp, value)) = match (&self.deadband, &self.last_logged) {
+            (Some(deadband), Some(last)) => (deadband, last),
+            _ => return true,
+        };
+
+        if max_interval.is_some_and(|max_interval| event.timestamp - *timestamp >= max_interval) {
+            return true;
+        }
+
+        match (event.value.as_f64(), value.as_f64()) {
+            (Some(current), Some(last)) => (current - last).abs() > *delta,
+            _ => event.value != *value,
+        }
+    }
+
+    /// Builder method for suppressing logging of readings which have not meaningfully changed
+    ///
+    /// Readings are still cached and propagated to subscribers, however an [`IOEvent`] is only
+    /// added to [`Log`] when value differs from the last logged value by more than `delta`, or
+    /// when `max_interval` has elapsed since the last logged event. This reduces log volume for
+    /// slow-moving signals.
+    ///
+    /// # Parameters
+    ///
+    /// - `delta`: Minimum change in value for a reading to be logged
+    /// - `max_interval`: Maximum duration between logged events. If `None`, unchanged readings
+    ///   are never logged.
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use sensd::action::IOCommand;
+    /// use sensd::io::{Device, Input, RawValue};
+    ///
+    /// let input = Input::default()
+    ///     .with_command(IOCommand::input(|| RawValue::Float(21.3))).unwrap()
+    ///     .set_deadband(0.5, Duration::minutes(15));
+    ///
+    /// assert_eq!(Some((0.5, Some(Duration::minutes(15)))), input.deadband());
+    /// ```
+    pub fn set_deadband<D>(mut self, delta: f64, max_interval: D) -> Self
+    where
+        D: Into<Option<Duration>>
+    {
+        self.deadband = Some((delta, max_interval.into()));
+        self
+    }
+
+    /// Getter for deadband
+    ///
+    /// # Returns
+    ///
+    /// Minimum change and maximum interval between logged events, or `None` if every reading is
+    /// logged
+    pub fn deadband(&self) -> Option<(f64, Option<Duration>)> {
+        self.deadband
+    }
+
+    /// Builder method for emitting the rate of change between consecutive readings
+    ///
+    /// When enabled, [`Input::read_all()`] calculates the change in value since the previous
+    /// reading divided by the time elapsed, and emits it as an additional [`IOEvent`] tagged with
+    /// [`RATE_TAG`]. Actions subscribed to [`crate::action::EventSource::Rate`] receive rate
+    /// events, allowing them to act on how fast a value changes (eg: a leak causing a water level
+    /// to drop) rather than on the value itself. Rate events are not passed to subscribers of
+    /// [`crate::action::EventSource::Primary`], so actions on the value itself are unaffected.
+    ///
+    /// Rate events are returned by [`Input::read_all()`], but are not logged and do not update
+    /// cached state. No rate is emitted for the first reading, for
+    /// non-numeric readings, or for a reading sharing the timestamp of the previous one.
+    ///
+    /// # Parameters
+    ///
+    /// - `per`: Unit of time of the emitted rate (eg: `Duration::minutes(1)` for change per minute)
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use sensd::action::IOCommand;
+    /// use sensd::clock::MockClock;
+    /// use sensd::io::{Device, Input, RawValue, RATE_TAG};
+    ///
+    /// let clock = MockClock::new(Utc::now());
+    /// let mut input = Input::default()
+    ///     .with_command(IOCommand::input(|| RawValue::Float(12.0))).unwrap()
+    ///     .set_rate_of_change(Duration::minutes(1));
+    ///
+    /// // first reading has no prior reading to compare against
+    /// assert_eq!(1, input.read_all().unwrap().len());
+    ///
+    /// clock.advance(Duration::seconds(30));
+    /// let events = input.read_all().unwrap();
+    /// assert!(events[1].has_tag(RATE_TAG));
+    /// assert_eq!(RawValue::Float(0.0), events[1].value);
+    /// ```
+    pub fn set_rate_of_change(mut self, per: Duration) -> Self {
+        self.rate = Some(per);
+        self
+    }
+
+    /// Getter for unit of time of emitted rate of change
+    ///
+    /// # Returns
+    ///
+    /// `Some` with unit of time, or `None` if rate of change is not emitted
+    ///
+    /// # See Also
+    ///
+    /// - [`Input::set_rate_of_change()`] for how rate is calculated
+    pub fn rate_of_change(&self) -> Option<Duration> {
+        self.rate
+    }
+
+    /// Calculate rate of change between `event` and the previous reading
+    ///
+    /// `event` is stored as the previous reading, unless it shares the timestamp of the previous
+    /// reading.
+    ///
+    /// # Returns
+    ///
+    /// `Some` with [`IOEvent`] tagged with [`RATE_TAG`], or `None` if rate of change is disabled,
+    /// `event` is non-numeric, there is no previous reading, or no time has elapsed
+    fn rate_event(&mut self, event: &IOEvent) -> Option<IOEvent> {
+        let per = self.rate?.num_microseconds()? as f64;
+        let value = event.value.as_f64()?;
+
+        let previous = self.last_sample;
+        if previous.is_some_and(|(timestamp, _)| timestamp >= event.timestamp) {
+            return None;
+        }
+        self.last_sample = Some((event.timestamp, value));
+
+        let (timestamp, last) = previous?;
+        let elapsed = (event.timestamp - timestamp).num_microseconds()? as f64;
+
+        let rate = (value - last) * per / elapsed;
+
+        let mut rate = IOEvent::with_timestamp(event.timestamp, RawValue::Float(rate as f32));
+        rate.tag(RATE_TAG);
+        Some(rate)
+    }
+
+    /// Builder method for setting read timeout
+    ///
+    /// When set, [`IOCommand::execute_timeout()`] is used by [`Input::read()`] so that a hung
+    /// low-level read does not block polling. See [`IOCommand::execute_timeout()`] for the
+    /// requirements this imposes on the command.
+    ///
+    /// # Parameters
+    ///
+    /// - `timeout`: Maximum duration to wait for a read
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Builder method for retrying failed reads with exponential backoff
+    ///
+    /// When a low-level read by [`Input::read()`] fails (eg: a transient bus error or a timeout),
+    /// it is retried up to `retries` times before the error is returned. The delay before the
+    /// first retry is `backoff`, and is doubled before each subsequent retry. Since a failed read
+    /// that is retried still counts as a single failure, [`Input::consecutive_failures()`] is
+    /// unaffected by retries.
+    ///
+    /// # Bounds
+    ///
+    /// Each delay is capped to [`MAX_BACKOFF_SECS`]. When a timeout is set by
+    /// [`Input::set_timeout()`], each attempt is bounded by the timeout and each delay is capped to
+    /// the timeout as well. Therefore, a single call to [`Input::read()`] takes at most
+    /// `(2 * retries + 1) * timeout`, which should be less than the polling interval.
+    /// While a read that timed out is still running, further attempts fail immediately with
+    /// [`DeviceError::Busy`] and are retried as well.
+    ///
+    /// # Parameters
+    ///
+    /// - `retries`: Maximum number of retries. `0` disables retrying.
+    /// - `backoff`: Delay before first retry
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    pub fn set_retry(mut self, retries: u32, backoff: Duration) -> Self {
+        self.retry = Some((retries, backoff));
+        self
+    }
+
+    /// Getter for retry policy
+    ///
+    /// # Returns
+    ///
+    /// Maximum number of retries and delay before first retry, or `None` if failed reads are not
+    /// retried
+    pub fn retry(&self) -> Option<(u32, Duration)> {
+        self.retry
+    }
+
+    /// Builder method for polling device at its own interval
+    ///
+    /// By default, devices are read whenever their [`crate::storage::Group`] is polled. When an
+    /// interval is set, [`crate::storage::Group::poll()`] instead reads the device only once its
+    /// own interval has elapsed since it was last polled, regardless of the group interval. This
+    /// allows fast and slow sensors to share a group.
+    ///
+    /// # Parameters
+    ///
+    /// - `interval`: Interval between polls, or `None` to follow the group interval
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    pub fn set_poll_interval(mut self, interval: Option<Duration>) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Getter for interval between polls
+    ///
+    /// # Returns
+    ///
+    /// `None` if device follows the interval of its group
+    pub fn poll_interval(&self) -> Option<Duration> {
+        self.poll_interval
+    }
+
+    /// Getter for read timeout
+    ///
+    /// # Returns
+    ///
+    /// `None` if reads are not bounded by a timeout
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Number of consecutive failed reads
+    ///
+    /// Counter is reset by any successful call to [`Input::read()`].
+    pub fn consecutive_failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// Timestamp of last successful read
+    ///
+    /// # Returns
+    ///
+    /// `None` if device has never been successfully read
+    pub fn last_read(&self) -> Option<DateTime<Utc>> {
+        self.last_read
+    }
+
+    /// Forget runtime state as if device was never read
+    ///
+    /// Cached state, [`Input::consecutive_failures()`], [`Input::last_read()`], the last
+    /// logged event used by [`Input::set_deadband()`], and the previous reading used by
+    /// [`Input::set_rate_of_change()`] are cleared. Configuration and log are not modified.
+    pub fn reset(&mut self) {
+        self.state = None;
+        self.failures = 0;
+        self.last_read = None;
+        self.last_logged = None;
+        self.last_sample = None;
+    }
+
     /// Create and set publisher or silently fail
     pub fn init_publisher(mut self) -> Self
     where
@@ -276,7 +1136,7 @@ impl Input {
                 self.publisher = Some(Publisher::default());
             }
             _ => {
-                eprintln!("Publisher already exists!");
+                log::warn!("Publisher already exists for {}", self.metadata);
             }
         }
         self
@@ -325,12 +1185,16 @@ impl PartialEq for Input {
 // Testing
 #[cfg(test)]
 mod tests {
+    use chrono::Duration;
     use crate::action::{IOCommand};
-    use crate::io::{Device, Input, IOKind, RawValue};
+    use crate::errors::DeviceError;
+    use crate::io::{Aggregation, Device, DeviceGetters, Input, IOKind, RawValue, RawValueKind};
     use crate::storage::{Chronicle, Directory, Document};
 
     const DUMMY_OUTPUT: RawValue = RawValue::Float(1.2);
-    const COMMAND: IOCommand = IOCommand::Input(move || DUMMY_OUTPUT);
+    fn dummy_command() -> IOCommand {
+        IOCommand::input(|| DUMMY_OUTPUT)
+    }
 
     #[test]
     /// Test that constructor accepts `name` as `&str` or `String`
@@ -350,7 +1214,7 @@ mod tests {
     fn test_rx() {
         let mut input = Input::default();
 
-        input.command = Some(COMMAND);
+        input.command = Some(dummy_command());
 
         let event = input.rx().unwrap();
         assert_eq!(event.value, DUMMY_OUTPUT);
@@ -361,7 +1225,7 @@ mod tests {
         let mut input = Input::default().init_log();
         let log = input.log();
 
-        input.command = Some(COMMAND);
+        input.command = Some(dummy_command());
 
         assert_eq!(log.clone().unwrap().try_lock().unwrap().iter().count(), 0);
 
@@ -412,4 +1276,492 @@ mod tests {
             .dir()
             .is_some());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn read_timeout() {
+        let command = IOCommand::input(|| {
+            std::thread::sleep(std::time::Duration::from_millis(500));
+            DUMMY_OUTPUT
+        });
+        let mut input = Input::default()
+            .with_command(command).unwrap()
+            .set_timeout(Duration::milliseconds(10));
+
+        assert!(matches!(input.read(), Err(DeviceError::Timeout)));
+        assert_eq!(1, input.consecutive_failures());
+
+        let mut input = Input::default()
+            .with_command(dummy_command()).unwrap()
+            .set_timeout(Duration::seconds(1));
+        assert_eq!(DUMMY_OUTPUT, input.read().unwrap().value);
+    }
+
+    #[test]
+    fn read_retry() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        // first attempt hangs, subsequent attempts succeed once it has completed
+        let command = IOCommand::input(|| {
+            if CALLS.fetch_add(1, Ordering::SeqCst) == 0 {
+                std::thread::sleep(std::time::Duration::from_millis(100));
+            }
+            DUMMY_OUTPUT
+        });
+        let mut input = Input::default()
+            .with_command(command.clone()).unwrap()
+            .set_timeout(Duration::milliseconds(20))
+            .set_retry(20, Duration::milliseconds(5));
+        assert_eq!(Some((20, Duration::milliseconds(5))), input.retry());
+
+        assert_eq!(DUMMY_OUTPUT, input.read().unwrap().value);
+        // busy attempts do not call command
+        assert_eq!(2, CALLS.load(Ordering::SeqCst));
+        assert_eq!(0, input.consecutive_failures());
+
+        // persistent failures are returned after all retries, and delays are bounded by timeout
+        let command = IOCommand::input(|| {
+            std::thread::sleep(std::time::Duration::from_millis(500));
+            DUMMY_OUTPUT
+        });
+        let mut input = Input::default()
+            .with_command(command).unwrap()
+            .set_timeout(Duration::milliseconds(10))
+            .set_retry(3, Duration::seconds(10));
+        let start = std::time::Instant::now();
+        assert!(matches!(input.read(), Err(DeviceError::Busy)));
+        assert!(start.elapsed() < std::time::Duration::from_millis(400));
+        assert_eq!(1, input.consecutive_failures());
+
+        // missing command is not retried
+        let mut input = Input::default().set_retry(3, Duration::seconds(10));
+        assert!(matches!(input.read(), Err(DeviceError::NoCommand {..})));
+    }
+
+    #[test]
+    fn value_kind_check() {
+        assert_eq!(RawValueKind::Float, IOKind::PH.expected_value_kind());
+        assert_eq!(RawValueKind::Binary, IOKind::Proximity.expected_value_kind());
+
+        // generic devices are unconstrained by default
+        let mut input = Input::new("pH", 0, IOKind::PH)
+            .with_command(IOCommand::input(|| RawValue::Binary(true))).unwrap();
+        assert!(input.read().is_ok());
+
+        let mut input = input.set_value_kind_check(true);
+        assert!(matches!(input.read(), Err(DeviceError::ValueKindMismatch {..})));
+        assert_eq!(1, input.consecutive_failures());
+
+        let mut input = Input::new("pH", 0, IOKind::PH)
+            .with_command(dummy_command()).unwrap()
+            .set_value_kind_check(true);
+        assert!(input.read().is_ok());
+    }
+
+    #[test]
+    fn deadband() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        const VALUES: [f32; 5] = [20.0, 20.2, 20.4, 21.0, 21.1];
+        let command = IOCommand::input(|| {
+            RawValue::Float(VALUES[CALLS.fetch_add(1, Ordering::SeqCst)])
+        });
+        let mut input = Input::default()
+            .with_command(command).unwrap()
+            .init_log()
+            .set_deadband(0.5, None);
+        let log = input.log().unwrap();
+
+        for value in VALUES {
+            // every reading is returned and cached
+            assert_eq!(RawValue::Float(value), input.read().unwrap().value);
+            assert_eq!(Some(RawValue::Float(value)), input.state().clone());
+        }
+
+        let logged: Vec<RawValue> = log.try_lock().unwrap()
+            .events()
+            .map(|event| event.value.clone())
+            .collect();
+        assert_eq!(vec![RawValue::Float(20.0), RawValue::Float(21.0)], logged);
+
+        // unchanged readings are logged after maximum interval
+        let mut input = Input::default()
+            .with_command(dummy_command()).unwrap()
+            .init_log()
+            .set_deadband(0.5, Duration::zero());
+        let log = input.log().unwrap();
+        for _ in 0..3 {
+            input.read().unwrap();
+            std::thread::sleep(std::time::Duration::from_nanos(1));
+        }
+        assert_eq!(3, log.try_lock().unwrap().iter().count());
+    }
+
+    #[test]
+    fn rate_of_change() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use crate::clock::MockClock;
+        use crate::io::RATE_TAG;
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        const VALUES: [f32; 5] = [100.0, 97.0, 97.0, 91.0, 90.0];
+        let command = IOCommand::input(|| {
+            RawValue::Float(VALUES[CALLS.fetch_add(1, Ordering::SeqCst)])
+        });
+        let mut input = Input::default()
+            .with_command(command).unwrap()
+            .set_rate_of_change(Duration::minutes(1));
+        assert_eq!(Some(Duration::minutes(1)), input.rate_of_change());
+
+        let clock = MockClock::new(chrono::Utc::now());
+
+        // no prior reading
+        assert_eq!(1, input.read_all().unwrap().len());
+
+        clock.advance(Duration::seconds(30));
+        let events = input.read_all().unwrap();
+        assert_eq!(2, events.len());
+        assert!(events[1].has_tag(RATE_TAG));
+        assert_eq!(events[0].timestamp, events[1].timestamp);
+        assert_eq!(RawValue::Float(-6.0), events[1].value);
+
+        // no time has elapsed
+        assert_eq!(1, input.read_all().unwrap().len());
+
+        clock.advance(Duration::minutes(1));
+        let events = input.read_all().unwrap();
+        assert_eq!(RawValue::Float(-6.0), events[1].value);
+        assert_eq!(Some(RawValue::Float(91.0)), input.state().clone());
+
+        // rate is forgotten when reset
+        input.reset();
+        clock.advance(Duration::minutes(1));
+        assert_eq!(RawValue::Float(90.0), input.read().unwrap().value);
+        assert!(input.last_sample.is_some());
+
+        // non-numeric readings and disabled inputs never emit rate
+        let mut input = Input::default()
+            .with_command(IOCommand::input(|| RawValue::Binary(true))).unwrap()
+            .set_rate_of_change(Duration::seconds(1));
+        for _ in 0..2 {
+            clock.advance(Duration::seconds(1));
+            assert_eq!(1, input.read_all().unwrap().len());
+        }
+
+        let mut input = Input::default().with_command(dummy_command()).unwrap();
+        assert!(input.rate_of_change().is_none());
+        for _ in 0..2 {
+            clock.advance(Duration::seconds(1));
+            assert_eq!(1, input.read_all().unwrap().len());
+        }
+    }
+
+    #[test]
+    fn consecutive_failures() {
+        let mut input = Input::default();
+
+        for count in 1..4 {
+            assert!(input.read().is_err());
+            assert_eq!(count, input.consecutive_failures());
+        }
+        assert!(input.last_read().is_none());
+
+        input.command = Some(dummy_command());
+        let event = input.read().unwrap();
+        assert_eq!(0, input.consecutive_failures());
+        assert_eq!(Some(event.timestamp), input.last_read());
+    }
+
+    #[test]
+    fn multi_input() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let reads = Arc::new(AtomicUsize::new(0));
+        let handle = reads.clone();
+        let command = IOCommand::multi_input(move || {
+            handle.fetch_add(1, Ordering::SeqCst);
+            vec![
+                (IOKind::RelativeHumidity, RawValue::Float(48.0)),
+                (IOKind::Temperature, RawValue::Float(21.5)),
+                (IOKind::Pressure, RawValue::Float(1013.0)),
+            ]
+        });
+
+        let mut input = Input::new("bme280", 0, IOKind::Temperature)
+            .with_command(command).unwrap()
+            .init_log();
+
+        let events = input.read_all().unwrap();
+        assert_eq!(1, reads.load(Ordering::SeqCst));
+        assert_eq!(3, events.len());
+        assert!(events.iter().all(|event| event.timestamp == events[0].timestamp));
+        assert_eq!(Some(IOKind::Pressure), events[2].kind);
+
+        // only event of device kind is cached and logged
+        assert_eq!(Some(RawValue::Float(21.5)), *input.state());
+        assert_eq!(1, input.log().unwrap().try_lock().unwrap().iter().count());
+
+        // `read()` returns event of device kind
+        let event = input.read().unwrap();
+        assert_eq!(Some(IOKind::Temperature), event.kind);
+        assert_eq!(RawValue::Float(21.5), event.value);
+
+        // invalid secondary values are discarded without affecting primary value
+        let mut input = Input::new("bme280", 0, IOKind::Temperature)
+            .with_command(IOCommand::multi_input(|| vec![
+                (IOKind::Temperature, RawValue::Float(21.5)),
+                (IOKind::RelativeHumidity, RawValue::Float(150.0)),
+            ])).unwrap()
+            .set_validation(true)
+            .init_log();
+        let events = input.read_all().unwrap();
+        assert_eq!(1, events.len());
+        assert_eq!(0, input.consecutive_failures());
+        assert_eq!(Some(RawValue::Float(21.5)), *input.state());
+        assert_eq!(1, input.log().unwrap().try_lock().unwrap().iter().count());
+
+        // invalid primary value fails read
+        let mut input = Input::new("bme280", 0, IOKind::Temperature)
+            .with_command(IOCommand::multi_input(|| vec![
+                (IOKind::RelativeHumidity, RawValue::Float(48.0)),
+                (IOKind::Temperature, RawValue::Float(500.0)),
+            ])).unwrap()
+            .set_validation(true);
+        assert!(input.read_all().is_err());
+        assert_eq!(1, input.consecutive_failures());
+        assert!(input.state().is_none());
+
+        let mut input = Input::default()
+            .with_command(IOCommand::multi_input(Vec::new)).unwrap();
+        assert!(matches!(input.read(), Err(DeviceError::ValueExpected {..})));
+    }
+
+    #[test]
+    fn multi_input_subscribers() {
+        use crate::action::actions::Threshold;
+        use crate::action::{Action, EventSource, Trigger};
+        use crate::io::Output;
+
+        let command = IOCommand::multi_input(|| vec![
+            (IOKind::Temperature, RawValue::Float(21.5)),
+            (IOKind::Pressure, RawValue::Float(1013.0)),
+        ]);
+        let fan = Output::default().with_command(IOCommand::output(|_| Ok(()))).unwrap().into_deferred();
+        let alarm = Output::default().with_command(IOCommand::output(|_| Ok(()))).unwrap().into_deferred();
+
+        let mut input = Input::new("bme280", 0, IOKind::Temperature)
+            .with_command(command).unwrap()
+            .init_publisher();
+        let publisher = input.publisher_mut().as_mut().unwrap();
+        publisher.subscribe(
+            Threshold::with_output("fan", RawValue::Float(30.0), Trigger::GT, fan.clone()).into_boxed());
+        publisher.subscribe_to(
+            Threshold::with_output("alarm", RawValue::Float(1000.0), Trigger::GT, alarm.clone()).into_boxed(),
+            EventSource::Kind(IOKind::Pressure));
+
+        input.read_all().unwrap();
+
+        // pressure does not reach temperature subscriber
+        assert_eq!(Some(RawValue::Binary(false)), *fan.try_lock().unwrap().state());
+        assert_eq!(Some(RawValue::Binary(true)), *alarm.try_lock().unwrap().state());
+    }
+
+    #[test]
+    fn rate_of_change_subscribers() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use crate::action::actions::Threshold;
+        use crate::action::{Action, EventSource, Trigger};
+        use crate::clock::MockClock;
+        use crate::io::Output;
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        const VALUES: [f32; 3] = [90.0, 90.0, 85.0];
+        let command = IOCommand::input(|| {
+            RawValue::Float(VALUES[CALLS.fetch_add(1, Ordering::SeqCst)])
+        });
+        let pump = Output::default().with_command(IOCommand::output(|_| Ok(()))).unwrap().into_deferred();
+        let leak = Output::default().with_command(IOCommand::output(|_| Ok(()))).unwrap().into_deferred();
+
+        let mut input = Input::new("level", 0, IOKind::Unassigned)
+            .with_command(command).unwrap()
+            .set_rate_of_change(Duration::minutes(1))
+            .init_publisher();
+        let publisher = input.publisher_mut().as_mut().unwrap();
+        publisher.subscribe(
+            Threshold::with_output("pump", RawValue::Float(80.0), Trigger::GT, pump.clone()).into_boxed());
+        publisher.subscribe_to(
+            Threshold::with_output("leak", RawValue::Float(-1.0), Trigger::LT, leak.clone()).into_boxed(),
+            EventSource::Rate);
+
+        let clock = MockClock::new(chrono::Utc::now());
+        input.read_all().unwrap();
+        assert_eq!(Some(RawValue::Binary(true)), *pump.try_lock().unwrap().state());
+
+        // rate of 0.0 does not reach threshold acting on absolute value
+        clock.advance(Duration::minutes(1));
+        assert_eq!(2, input.read_all().unwrap().len());
+        assert_eq!(Some(RawValue::Binary(true)), *pump.try_lock().unwrap().state());
+        assert_eq!(Some(RawValue::Binary(false)), *leak.try_lock().unwrap().state());
+
+        clock.advance(Duration::minutes(1));
+        input.read_all().unwrap();
+        assert_eq!(Some(RawValue::Binary(true)), *pump.try_lock().unwrap().state());
+        assert_eq!(Some(RawValue::Binary(true)), *leak.try_lock().unwrap().state());
+    }
+
+    #[test]
+    fn rate_of_change_unmatched_kind() {
+        use crate::clock::MockClock;
+        use crate::io::RATE_TAG;
+
+        let command = IOCommand::multi_input(|| vec![
+            (IOKind::Temperature, RawValue::Float(21.5)),
+            (IOKind::RelativeHumidity, RawValue::Float(48.0)),
+        ]);
+        let mut input = Input::new("bme280", 0, IOKind::PH)
+            .with_command(command).unwrap()
+            .set_rate_of_change(Duration::minutes(1));
+
+        let clock = MockClock::new(chrono::Utc::now());
+        input.read().unwrap();
+        clock.advance(Duration::minutes(1));
+
+        // first reading is returned instead of appended rate event
+        let event = input.read().unwrap();
+        assert!(!event.has_tag(RATE_TAG));
+        assert_eq!(Some(IOKind::Temperature), event.kind);
+        assert_eq!(RawValue::Float(21.5), event.value);
+    }
+
+    #[test]
+    fn reset() {
+        let mut input = Input::default().init_log();
+        assert!(input.read().is_err());
+
+        input.command = Some(dummy_command());
+        input.read().unwrap();
+        input.command = None;
+        assert!(input.read().is_err());
+        assert!(input.state().is_some());
+
+        input.reset();
+        assert_eq!(0, input.consecutive_failures());
+        assert!(input.last_read().is_none());
+        assert!(input.state().is_none());
+
+        // log is retained
+        assert_eq!(1, input.log().unwrap().try_lock().unwrap().iter().count());
+    }
+
+    #[test]
+    fn self_test() {
+        let input = Input::default().init_log();
+        assert!(input.self_test().is_err());
+
+        let input = input.with_command(dummy_command()).unwrap();
+        assert!(input.self_test().is_ok());
+
+        // nothing is logged or cached
+        assert_eq!(0, input.log().unwrap().try_lock().unwrap().iter().count());
+        assert!(input.state().is_none());
+    }
+
+    #[test]
+    fn direction() {
+        let device = Input::default();
+        assert!(device.is_input());
+        assert!(!device.is_output());
+    }
+
+    #[test]
+    fn read_n() {
+        let mut input = Input::default().with_command(dummy_command()).unwrap().init_log();
+
+        let event = input.read_n(5, Aggregation::Mean).unwrap();
+        assert_eq!(DUMMY_OUTPUT, event.value);
+        assert_eq!(Some(DUMMY_OUTPUT), *input.state());
+
+        // only a single event is logged
+        assert_eq!(1, input.log().unwrap().try_lock().unwrap().iter().count());
+
+        assert!(input.read_n(0, Aggregation::Mean).is_err());
+        assert!(Input::default().read_n(3, Aggregation::Mean).is_err());
+    }
+
+    #[test]
+    fn validation() {
+        let bad_ph = || IOCommand::input(|| RawValue::Float(25.0));
+
+        // validation is opt-in
+        let mut input = Input::new("", 0, IOKind::PH).with_command(bad_ph()).unwrap().init_log();
+        assert!(input.read().is_ok());
+
+        // kind defaults are used when no explicit range is set
+        let mut input = input.set_validation(true);
+        assert_eq!(Some((0.0, 14.0)), input.range());
+        match input.read() {
+            Err(DeviceError::OutOfRange {value, ..}) => assert_eq!(RawValue::Float(25.0), value),
+            _ => panic!("Expected out of range error"),
+        }
+        assert_eq!(1, input.consecutive_failures());
+        assert!(input.read_n(3, Aggregation::Mean).is_err());
+
+        // out of range values are not logged
+        assert_eq!(1, input.log().unwrap().try_lock().unwrap().iter().count());
+
+        // explicit range takes precedence
+        let mut input = input.set_range(0.0, 30.0);
+        assert!(input.read().is_ok());
+
+        // kinds without defaults are not validated
+        let mut input = Input::new("", 0, IOKind::Light).with_command(bad_ph()).unwrap()
+            .set_validation(true);
+        assert!(input.range().is_none());
+        assert!(input.read().is_ok());
+    }
+
+    #[test]
+    fn unit_bounds() {
+        use crate::errors::ValueError;
+        use crate::io::Device;
+        use crate::units::{Celsius, Ph};
+
+        let reading = || IOCommand::input(|| RawValue::Float(8.0));
+
+        let mut input = Input::new("", 0, IOKind::PH).with_command(reading()).unwrap()
+            .set_validation(true)
+            .set_bounds(Ph(5.5), Ph(7.5)).unwrap();
+        assert_eq!(Some((5.5, 7.5)), input.range());
+        assert!(matches!(input.read(), Err(DeviceError::OutOfRange {..})));
+
+        // bounds are persisted with configuration
+        let rebuilt = Input::from_config(&input.to_config()).unwrap();
+        assert_eq!(Some((Ph(5.5), Ph(7.5))), rebuilt.metadata().bounds());
+
+        // invalid bounds in configuration are rejected
+        let mut config = input.to_config();
+        config.metadata.bounds = Some((-3.0, 1000.0));
+        assert!(matches!(Input::from_config(&config), Err(DeviceError::InvalidBounds {..})));
+
+        // out of unit range bounds are rejected with unit error
+        let input = Input::new("", 0, IOKind::PH);
+        assert!(matches!(input.set_bounds(Ph(0.0), Ph(1000.0)), Err(ValueError::UnitRange {..})));
+        let input = Input::new("", 0, IOKind::PH);
+        assert!(matches!(input.set_bounds(Ph(9.0), Ph(4.0)), Err(ValueError::InvalidBounds {..})));
+        let input = Input::new("", 0, IOKind::PH);
+        assert!(matches!(input.set_bounds(Celsius(0.0), Celsius(1.0)), Err(ValueError::UnitMismatch {..})));
+    }
+
+    #[test]
+    fn with_command() {
+        assert!(Input::default().with_command(dummy_command()).is_ok());
+        assert!(Input::default().with_command(IOCommand::output(|_| Ok(()))).is_err());
+
+        // bidirectional commands are accepted and read from
+        let command = IOCommand::bidirectional(|| DUMMY_OUTPUT, |_| Ok(()));
+        let mut input = Input::default().with_command(command).unwrap();
+        assert_eq!(DUMMY_OUTPUT, input.read().unwrap().value);
+    }
+}
@@ -1,9 +1,13 @@
 mod device;
+mod device_type;
 mod input;
 mod output;
 mod container;
+mod typed;
 
 pub use device::{Device, DeviceGetters, DeviceSetters};
+pub use device_type::DeviceType;
 pub use input::Input;
 pub use output::Output;
 pub use container::DeviceContainer;
+pub use typed::TypedInput;
@@ -1,7 +1,8 @@
 use std::fmt::Formatter;
 use std::path::{Path, PathBuf};
-use chrono::{Duration, Utc};
-use crate::action::{Command, IOCommand, Routine};
+use chrono::Duration;
+use crate::action::{Command, IOCommand, Routine, SequenceRoutine};
+use crate::clock;
 use crate::errors::{DeviceError, ErrorType};
 use crate::helpers::Def;
 use crate::io::{Device, DeviceMetadata, IODirection, IOEvent, IOKind, IdType, RawValue, DeviceGetters, DeviceSetters};
@@ -9,12 +10,11 @@ use crate::io::dev::device::set_log_dir;
 use crate::name::Name;
 use crate::storage::{Chronicle, Directory, Log};
 
-#[derive(Default)]
 /// This is the generic implementation for any external output device.
 ///
 /// # Getting Started
 ///
-/// While [`Output`] derives a [`Default`] implementation, `name` and `id`
+/// While [`Output`] implements [`Default`], `name` and `id`
 /// should be passed to [`Device::new()`] constructor to differentiate it
 /// from other [`Output`] objects.
 ///
@@ -34,17 +34,17 @@ use crate::storage::{Chronicle, Directory, Log};
 /// ```
 ///
 /// Now that we are able to set device metadata, constructor methods still don't
-/// provide any way to interact with hardware. The builder method [`Device::set_command()`]
+/// provide any way to interact with hardware. The builder method [`Device::with_command()`]
 /// is used to add low-level code. In this example, we return a static value:
 ///
 /// ```
 /// use sensd::action::IOCommand;
 /// use sensd::io::{Device, Output, RawValue};
 ///
-/// let command = IOCommand::Output(|_| Ok(()));
+/// let command = IOCommand::output(|_| Ok(()));
 /// let device =
 ///     Output::default()
-///         .set_command(command);
+///         .with_command(command).unwrap();
 /// ```
 ///
 /// With a `command` set, [`Output::write()`] can be used to actuate or send data
@@ -59,6 +59,15 @@ pub struct Output {
     dir: Option<PathBuf>,
 }
 
+impl Default for Output {
+    /// Unnamed output device with `id` of 0
+    ///
+    /// Unlike a derived implementation, direction is correctly set to [`IODirection::Out`].
+    fn default() -> Self {
+        Self::new(String::default(), IdType::default(), None)
+    }
+}
+
 impl Name for Output {
     fn name(&self) -> &String {
         &self.metadata().name
@@ -116,6 +125,18 @@ impl DeviceSetters for Output {
             set_log_dir(Some(log), dir)
         }
     }
+
+    fn set_description(&mut self, description: Option<String>) {
+        self.metadata.description = description;
+    }
+
+    fn set_location(&mut self, location: Option<String>) {
+        self.metadata.location = location;
+    }
+
+    fn set_raw_bounds(&mut self, bounds: Option<(f32, f32)>) {
+        self.metadata.bounds = bounds;
+    }
 }
 
 /// Implement unique constructors and builder methods
@@ -151,18 +172,58 @@ impl Device for Output {
         }
     }
 
-    fn set_command(mut self, command: IOCommand) -> Self
+    fn with_command(mut self, command: IOCommand) -> Result<Self, ErrorType>
     where
         Self: Sized,
     {
-        command.agrees(IODirection::Out)
-            .expect("Command is not output");
+        if command.agrees(IODirection::Out).is_err() {
+            return Err(Box::new(DeviceError::CommandMismatch {metadata: self.metadata.clone()}));
+        }
         self.command = Some(command);
-        self
+        Ok(self)
+    }
+
+    /// Verify that a command is associated
+    ///
+    /// Since writing an arbitrary value to hardware is not guaranteed to be safe, the command
+    /// itself is not executed.
+    fn self_test(&self) -> Result<(), ErrorType> {
+        match self.command {
+            Some(_) => Ok(()),
+            None => Err(Box::new(DeviceError::NoCommand {metadata: self.metadata.clone()})),
+        }
     }
 }
 
 impl Output {
+    /// Constructor for a mock device which does not interact with hardware
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: name of device
+    /// - `id`: device ID
+    ///
+    /// # Returns
+    ///
+    /// [`Output`] with [`IOCommand::null_output()`] as command
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::{Output, RawValue};
+    ///
+    /// let mut output = Output::mock("mock", 0);
+    ///
+    /// assert!(output.write(RawValue::Binary(true)).is_ok());
+    /// ```
+    pub fn mock<N>(name: N, id: IdType) -> Self
+    where
+        N: Into<String>
+    {
+        Self::new(name, id, None)
+            .with_command(IOCommand::null_output()).unwrap()
+    }
+
     /// Execute low-level GPIO command to write data
     ///
     /// # Parameters
@@ -181,7 +242,8 @@ impl Output {
     /// [Low level error type](https://github.com/PoorRican/sensd/issues/192)
     fn tx(&self, value: RawValue) -> Result<IOEvent, DeviceError> {
         if let Some(command) = &self.command {
-            command.execute(Some(value))?;
+            command.execute(Some(value.clone()))
+                .map_err(|e| e.with_metadata(&self.metadata))?;
         } else {
             Err(DeviceError::NoCommand {metadata: self.metadata.clone()})?;
         };
@@ -189,6 +251,51 @@ impl Output {
         Ok(IOEvent::new(value))
     }
 
+    /// Read current state from a bidirectional device
+    ///
+    /// This allows a single device to both report state and accept writes (eg: a valve that reports
+    /// position). Like [`Output::write()`], the cached state is updated and [`IOEvent`] is logged.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with [`IOEvent`] containing read value
+    /// - `Err` with [`DeviceError::NoCommand`] if no command is set, or with
+    ///   [`DeviceError::CommandMismatch`] if command is not [`IOCommand::Bidirectional`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::action::IOCommand;
+    /// use sensd::io::{Device, DeviceGetters, Output, RawValue};
+    ///
+    /// let command = IOCommand::bidirectional(|| RawValue::Float(0.5), |_| Ok(()));
+    /// let mut valve = Output::default().with_command(command).unwrap();
+    ///
+    /// assert_eq!(RawValue::Float(0.5), valve.read().unwrap().value);
+    /// assert!(valve.write(RawValue::Float(1.0)).is_ok());
+    /// ```
+    pub fn read(&mut self) -> Result<IOEvent, DeviceError> {
+        let value = match &self.command {
+            Some(command @ IOCommand::Bidirectional(..)) => command.execute(None)
+                .map_err(|e| e.with_metadata(&self.metadata))?,
+            Some(_) => Err(DeviceError::CommandMismatch {metadata: self.metadata.clone()})?,
+            None => Err(DeviceError::NoCommand {metadata: self.metadata.clone()})?,
+        };
+        let event = match value {
+            Some(value) => IOEvent::new(value),
+            None => Err(DeviceError::ValueExpected {metadata: self.metadata.clone()})?,
+        };
+
+        // update cached state
+        self.state = Some(event.value.clone());
+
+        self.push_to_log(&event);
+
+        Ok(event)
+    }
+
     /// Get [`IOEvent`], add to log and update cache.
     ///
     /// Primary interface method called during polling,
@@ -213,15 +320,15 @@ impl Output {
     /// use sensd::io::{Device, DeviceGetters, Output, RawValue};
     ///
     /// let value = RawValue::default();
-    /// let command = IOCommand::Output(|_| Ok(()));
-    /// let mut output = Output::default().set_command(command);
+    /// let command = IOCommand::output(|_| Ok(()));
+    /// let mut output = Output::default().with_command(command).unwrap();
     ///
-    /// let event = output.write(value).unwrap();
+    /// let event = output.write(value.clone()).unwrap();
     ///
     /// assert_eq!(event.value, value);
     ///
     /// // cached state is updated
-    /// assert_eq!(output.state().unwrap(), value);
+    /// assert_eq!(output.state().clone().unwrap(), value);
     /// ```
     ///
     /// # Issues
@@ -235,7 +342,7 @@ impl Output {
         let event = self.tx(value).expect("Low level device error while writing");
 
         // update cached state
-        self.state = Some(event.value);
+        self.state = Some(event.value.clone());
 
         self.push_to_log(&event);
 
@@ -253,7 +360,7 @@ impl Output {
     ///
     /// [`Routine`] ready to be added to [`crate::action::SchedRoutineHandler`]
     pub fn create_routine(&self, value: RawValue, duration: Duration) -> Routine {
-        let timestamp = Utc::now() + duration;
+        let timestamp = clock::now() + duration;
         let log = self.log.as_ref()
             .expect("Output device does not have log")
             .to_owned()
@@ -269,6 +376,39 @@ impl Output {
             command,
         )
     }
+
+    /// Create a [`SequenceRoutine`] given values to write at offsets from now
+    ///
+    /// # Parameters
+    ///
+    /// - `steps`: Offsets from now and values to write at each offset
+    ///
+    /// # Returns
+    ///
+    /// [`SequenceRoutine`] ready to be added to [`crate::action::SchedRoutineHandler`]
+    ///
+    /// # Panics
+    ///
+    /// If device does not have a command
+    pub fn create_sequence(&self, steps: Vec<(Duration, RawValue)>) -> SequenceRoutine {
+        let command = self.command.as_ref()
+            .expect("Output device does not have command")
+            .clone();
+        SequenceRoutine::new(
+            clock::now(),
+            steps,
+            self.log.clone(),
+            command,
+        )
+    }
+
+    /// Forget cached state
+    ///
+    /// This is intended for test setup or re-commissioning, when hardware state is unknown. Log
+    /// is not modified.
+    pub fn reset_state(&mut self) {
+        self.state = None;
+    }
 }
 
 impl Chronicle for Output {
@@ -303,7 +443,9 @@ mod tests {
 
     /// Dummy output command for testing.
     /// Accepts value and returns `Ok(())`
-    const COMMAND: IOCommand = IOCommand::Output(move |_| Ok(()));
+    fn dummy_command() -> IOCommand {
+        IOCommand::output(|_| Ok(()))
+    }
 
     #[test]
     /// Test that constructor accepts `name` as `&str` or `String`
@@ -319,13 +461,32 @@ mod tests {
         Output::new("", 0, IOKind::Unassigned);
     }
 
+    #[test]
+    fn bidirectional() {
+        let command = IOCommand::bidirectional(|| RawValue::Float(0.25), |_| Ok(()));
+        let mut output = Output::default().with_command(command).unwrap().init_log();
+
+        assert_eq!(RawValue::Float(0.25), output.read().unwrap().value);
+        assert_eq!(Some(RawValue::Float(0.25)), *output.state());
+
+        output.write(RawValue::Float(1.0)).unwrap();
+        assert_eq!(Some(RawValue::Float(1.0)), *output.state());
+
+        // reads and writes are both logged
+        assert_eq!(2, output.log().unwrap().try_lock().unwrap().iter().count());
+
+        // output-only commands cannot be read
+        let mut output = Output::default().with_command(dummy_command()).unwrap();
+        assert!(output.read().is_err());
+    }
+
     #[test]
     fn test_tx() {
         let mut output = Output::default();
-        output.command = Some(COMMAND);
+        output.command = Some(dummy_command());
 
         let value = RawValue::Binary(true);
-        let event = output.tx(value).expect("Unknown error occurred in `tx()`");
+        let event = output.tx(value.clone()).expect("Unknown error occurred in `tx()`");
 
         assert_eq!(value, event.value);
     }
@@ -339,17 +500,17 @@ mod tests {
         assert_eq!(log.try_lock().unwrap().iter().count(), 0);
 
         let value = RawValue::Binary(true);
-        output.command = Some(COMMAND);
+        output.command = Some(dummy_command());
 
         // check `state` before `::write()`
         assert_eq!(None, *output.state());
 
         let event = output
-            .write(value)
+            .write(value.clone())
             .expect("Unknown error returned by `::write()`");
 
         // check state after `::write()`
-        assert_eq!(value, output.state().unwrap());
+        assert_eq!(value, output.state().clone().unwrap());
 
         // check returned `IOEvent`
         assert_eq!(value, event.value);
@@ -403,4 +564,67 @@ mod tests {
             .dir()
             .is_some());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn self_test() {
+        let output = Output::default();
+        assert!(output.self_test().is_err());
+
+        let output = output.with_command(dummy_command()).unwrap();
+        assert!(output.self_test().is_ok());
+        assert!(output.state().is_none());
+    }
+
+    #[test]
+    fn direction() {
+        let device = Output::default();
+        assert!(!device.is_input());
+        assert!(device.is_output());
+    }
+
+    #[test]
+    fn with_command() {
+        assert!(Output::default().with_command(dummy_command()).is_ok());
+        assert!(Output::default().with_command(IOCommand::input(RawValue::default)).is_err());
+    }
+
+    #[test]
+    fn last_event() {
+        let mut output = Output::default().with_command(dummy_command()).unwrap();
+        output.write(RawValue::Binary(true)).unwrap();
+        // no log
+        assert!(output.last_event().is_none());
+
+        let mut output = output.init_log();
+        assert!(output.last_event().is_none());
+
+        output.write(RawValue::Binary(true)).unwrap();
+        std::thread::sleep(std::time::Duration::from_nanos(1));
+        let event = output.write(RawValue::Binary(false)).unwrap();
+        assert_eq!(Some(event), output.last_event());
+
+        // locked log
+        let log = output.log().unwrap();
+        let _binding = log.try_lock().unwrap();
+        assert!(output.last_event().is_none());
+    }
+
+    #[test]
+    fn command_failed() {
+        use crate::errors::DeviceError;
+
+        let output = Output::new("pump", 3, IOKind::Flow)
+            .with_command(IOCommand::output(|_| Err(())))
+            .unwrap();
+
+        let error = output.tx(RawValue::Binary(true)).unwrap_err();
+        match &error {
+            DeviceError::CommandFailed {metadata, value, ..} => {
+                assert_eq!(3, metadata.id);
+                assert_eq!(RawValue::Binary(true), *value);
+            }
+            _ => panic!("expected CommandFailed"),
+        }
+        assert!(error.to_string().contains("Flow"));
+    }
+}
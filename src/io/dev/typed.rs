@@ -0,0 +1,121 @@
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use crate::errors::{DeviceError, ErrorType, ValueError};
+use crate::io::{DeviceGetters, Input};
+use crate::units::Unit;
+
+/// High-level wrapper around [`Input`] which returns readings as a [`Unit`]
+///
+/// Readings are still handled by [`Input::read()`], so logging and publishing are unaffected. The
+/// wrapped [`Input`] is accessible through `Deref`.
+///
+/// # Example
+///
+/// ```
+/// use sensd::action::IOCommand;
+/// use sensd::io::{Device, Input, IOKind, RawValue, TypedInput};
+/// use sensd::units::Ph;
+///
+/// let input = Input::new("pH probe", 0, IOKind::PH)
+///     .with_command(IOCommand::input(|| RawValue::Float(6.5))).unwrap();
+/// let mut probe: TypedInput<Ph> = TypedInput::new(input).unwrap();
+///
+/// assert_eq!(Ph(6.5), probe.read_typed().unwrap());
+/// ```
+pub struct TypedInput<U: Unit> {
+    input: Input,
+    unit: PhantomData<U>,
+}
+
+impl<U: Unit> TypedInput<U> {
+    /// Constructor for [`TypedInput`]
+    ///
+    /// # Parameters
+    ///
+    /// - `input`: Device to wrap. Kind must match [`Unit::KIND`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok`: with wrapped `input`
+    /// - `Err`: with [`DeviceError::KindMismatch`] if kind of `input` does not match `U`
+    pub fn new(input: Input) -> Result<Self, DeviceError> {
+        if input.kind() != U::KIND {
+            return Err(DeviceError::KindMismatch {
+                metadata: input.metadata().clone(),
+                expected: U::KIND,
+            });
+        }
+        Ok(Self { input, unit: PhantomData })
+    }
+
+    /// Read a value from device and convert to `U`
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok`: with converted reading
+    /// - `Err`: with error returned by [`Input::read()`], or [`ValueError::ConversionError`] if
+    ///   reading is not numeric
+    pub fn read_typed(&mut self) -> Result<U, ErrorType> {
+        let value = self.input.read()?.value;
+        U::from_raw(value.clone())
+            .ok_or_else(|| Box::new(ValueError::ConversionError {value, unit: U::SYMBOL}) as ErrorType)
+    }
+
+    /// Unwrap to underlying [`Input`]
+    pub fn into_inner(self) -> Input {
+        self.input
+    }
+}
+
+impl<U: Unit> Deref for TypedInput<U> {
+    type Target = Input;
+
+    fn deref(&self) -> &Self::Target {
+        &self.input
+    }
+}
+
+impl<U: Unit> DerefMut for TypedInput<U> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::IOCommand;
+    use crate::io::{Device, Input, IOKind, RawValue, TypedInput};
+    use crate::storage::Chronicle;
+    use crate::units::{Celsius, Ph};
+
+    #[test]
+    fn kind_mismatch() {
+        let input = Input::new("", 0, IOKind::Temperature);
+        assert!(TypedInput::<Ph>::new(input).is_err());
+
+        let input = Input::new("", 0, IOKind::Temperature);
+        assert!(TypedInput::<Celsius>::new(input).is_ok());
+    }
+
+    #[test]
+    fn read_typed() {
+        let input = Input::new("", 0, IOKind::PH)
+            .with_command(IOCommand::input(|| RawValue::Binary(true))).unwrap()
+            .init_log();
+        let mut probe = TypedInput::<Ph>::new(input).unwrap();
+
+        // non-numeric readings cannot be converted, but are still logged
+        assert!(probe.read_typed().is_err());
+        assert_eq!(1, probe.log().unwrap().try_lock().unwrap().iter().count());
+
+        let input = Input::new("", 0, IOKind::PH)
+            .with_command(IOCommand::input(|| RawValue::Int(7))).unwrap();
+        let mut probe = TypedInput::<Ph>::new(input).unwrap();
+        assert_eq!(Ph(7.0), probe.read_typed().unwrap());
+    }
+}
@@ -1,7 +1,11 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::io::{IdTraits, RawValue};
+use crate::clock;
+use crate::io::{IdTraits, IOKind, NumericBinary, RawValue};
+
+/// Tag of events whose value is the rate of change between consecutive readings
+pub const RATE_TAG: &str = "rate";
 
 /// Dedicated object for storing a single record at a specific point in time.
 ///
@@ -14,7 +18,7 @@ use crate::io::{IdTraits, RawValue};
 ///
 /// let value = RawValue::default();
 ///
-/// let event = IOEvent::new(value);
+/// let event = IOEvent::new(value.clone());
 ///
 /// assert_eq!(value, event.value);
 /// ```
@@ -22,13 +26,48 @@ use crate::io::{IdTraits, RawValue};
 /// However, if a specific `timestamp` is desired, the [`IOEvent::with_timestamp()`]
 /// allows `timestamp` to be passed as a parameter.
 ///
+/// # Tags
+///
+/// Free-form tags may be attached to mark notable events (eg: "calibration done") for later
+/// filtering by [`crate::storage::Log::events_with_tag()`]. Tags are only serialized when present,
+/// therefore untagged events are stored exactly as before.
+///
+/// # Serialization
+///
+/// Events are serialized as `{"timestamp":..,"value":..}`. The legacy `data` key is accepted in
+/// place of `value` when deserializing. `tags` and `kind` are only serialized when present.
+///
+/// # Composite Sensors
+///
+/// Events read by [`crate::action::IOCommand::MultiInput`] are labeled by `kind`, since a single
+/// device reports several quantities at the same `timestamp`. Otherwise, `kind` is `None` and the
+/// kind of the originating device applies.
+///
+/// # Derived Events
+///
+/// Events derived from readings rather than read from a device, such as those emitted by
+/// [`crate::io::Input::set_rate_of_change()`], are tagged with [`RATE_TAG`].
+///
 /// # See Also
 ///
 /// A collection of multiple [`IOEvent`] objects is handled by [`crate::storage::EventCollection`].
-#[derive(Debug, Serialize, Deserialize, Clone)]
+///
+/// # Equality
+///
+/// Events are equal when all fields are equal. Values are compared by [`RawValue`] equality,
+/// therefore numeric values of different variants may be equal.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct IOEvent {
     pub timestamp: DateTime<Utc>,
+    #[serde(alias = "data")]
     pub value: RawValue,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Kind of value when it differs from that of originating device
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<IOKind>,
 }
 
 impl IOEvent {
@@ -52,7 +91,7 @@ impl IOEvent {
     /// let now = Utc::now();
     /// let value = RawValue::default();
     ///
-    /// let event = IOEvent::with_timestamp(now, value);
+    /// let event = IOEvent::with_timestamp(now, value.clone());
     ///
     /// assert_eq!(now, event.timestamp);
     /// assert_eq!(value, event.value);
@@ -61,6 +100,8 @@ impl IOEvent {
         IOEvent {
             timestamp,
             value,
+            tags: Vec::new(),
+            kind: None,
         }
     }
 
@@ -81,14 +122,226 @@ impl IOEvent {
     ///
     /// let value = RawValue::default();
     ///
-    /// let event = IOEvent::new(value);
+    /// let event = IOEvent::new(value.clone());
     ///
     /// assert_eq!(value, event.value);
     /// ```
     pub fn new(value: RawValue) -> Self {
-        let timestamp = Utc::now();
+        let timestamp = clock::now();
         IOEvent::with_timestamp(timestamp, value)
     }
+
+    /// Attach a tag to event
+    ///
+    /// Duplicate tags are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// - `tag`: Free-form label
+    ///
+    /// # Returns
+    ///
+    /// Mutable reference to `self` to allow method chaining
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::{IOEvent, RawValue};
+    ///
+    /// let mut event = IOEvent::new(RawValue::default());
+    /// event.tag("calibration done")
+    ///      .tag("manual override");
+    ///
+    /// assert!(event.has_tag("manual override"));
+    /// ```
+    pub fn tag<S>(&mut self, tag: S) -> &mut Self
+    where
+        S: Into<String>
+    {
+        let tag = tag.into();
+        if !self.has_tag(&tag) {
+            self.tags.push(tag);
+        }
+        self
+    }
+
+    /// Check if event has been marked with `tag`
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|inner| inner == tag)
+    }
+
+    /// Time elapsed since event was recorded
+    ///
+    /// # Returns
+    ///
+    /// Difference between now and `timestamp`. Negative if `timestamp` is in the future.
+    pub fn age(&self) -> Duration {
+        clock::now() - self.timestamp
+    }
+
+    /// Check if event is stale
+    ///
+    /// # Parameters
+    ///
+    /// - `duration`: Maximum age of a fresh event
+    ///
+    /// # Returns
+    ///
+    /// `true` if more than `duration` has elapsed since event was recorded
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use sensd::io::{IOEvent, RawValue};
+    ///
+    /// let event = IOEvent::with_timestamp(Utc::now() - Duration::minutes(5), RawValue::default());
+    ///
+    /// assert!(event.is_older_than(Duration::minutes(1)));
+    /// assert!(!event.is_older_than(Duration::hours(1)));
+    /// ```
+    pub fn is_older_than(&self, duration: Duration) -> bool {
+        self.age() > duration
+    }
+}
+
+/// Borrowed [`IOEvent`] which serializes [`RawValue::Binary`] values as `0` or `1`
+///
+/// Fields are serialized exactly as [`IOEvent`] otherwise.
+///
+/// # See Also
+///
+/// - [`crate::storage::Log::set_numeric_binary()`] for usage
+#[derive(Serialize)]
+pub(crate) struct NumericBinaryEvent<'a> {
+    timestamp: &'a DateTime<Utc>,
+    value: NumericBinary<'a>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<IOKind>,
+}
+
+impl<'a> From<&'a IOEvent> for NumericBinaryEvent<'a> {
+    fn from(event: &'a IOEvent) -> Self {
+        Self {
+            timestamp: &event.timestamp,
+            value: NumericBinary(&event.value),
+            tags: &event.tags,
+            kind: event.kind,
+        }
+    }
 }
 
 impl IdTraits for DateTime<Utc> {}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use crate::clock;
+    use crate::clock::MockClock;
+    use crate::io::{IOEvent, IOKind, RawValue};
+
+    fn timestamp() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2023-01-01T00:00:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn age() {
+        let clock = MockClock::new(timestamp());
+        let event = IOEvent::with_timestamp(timestamp() - chrono::Duration::seconds(30), RawValue::default());
+        assert_eq!(chrono::Duration::seconds(30), event.age());
+
+        clock.advance(chrono::Duration::seconds(1));
+        assert_eq!(chrono::Duration::seconds(31), event.age());
+
+        let future = IOEvent::with_timestamp(clock::now() + chrono::Duration::seconds(30), RawValue::default());
+        assert!(future.age() < chrono::Duration::zero());
+        assert!(!future.is_older_than(chrono::Duration::zero()));
+    }
+
+    #[test]
+    fn clone_and_eq() {
+        let mut event = IOEvent::with_timestamp(timestamp(), RawValue::Float(1.5));
+        event.tag("a");
+        let cloned = event.clone();
+        assert_eq!(event, cloned);
+
+        // all fields are compared
+        assert_ne!(event, IOEvent::with_timestamp(timestamp(), RawValue::Float(1.5)));
+        let mut other = cloned.clone();
+        other.tag("b");
+        assert_ne!(event, other);
+        let mut other = cloned.clone();
+        other.kind = Some(IOKind::PH);
+        assert_ne!(event, other);
+        let mut other = cloned.clone();
+        other.timestamp = timestamp() + chrono::Duration::seconds(1);
+        assert_ne!(event, other);
+    }
+
+    #[test]
+    fn numeric_binary() {
+        use crate::io::event::NumericBinaryEvent;
+
+        let mut event = IOEvent::with_timestamp(timestamp(), RawValue::Float(1.5));
+        event.tag("a");
+        event.kind = Some(IOKind::PH);
+
+        // only binary values differ
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            serde_json::to_string(&NumericBinaryEvent::from(&event)).unwrap(),
+        );
+
+        let event = IOEvent::with_timestamp(timestamp(), RawValue::Binary(true));
+        let json = serde_json::to_string(&NumericBinaryEvent::from(&event)).unwrap();
+        assert_eq!(r#"{"timestamp":"2023-01-01T00:00:00Z","value":{"type":"binary","value":1}}"#, json);
+
+        let deserialized: IOEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(event, deserialized);
+    }
+
+    #[test]
+    fn json_shape() {
+        let event = IOEvent::with_timestamp(timestamp(), RawValue::Float(1.5));
+
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(r#"{"timestamp":"2023-01-01T00:00:00Z","value":{"type":"float","value":1.5}}"#, json);
+
+        let deserialized: IOEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(event.timestamp, deserialized.timestamp);
+        assert_eq!(event.value, deserialized.value);
+    }
+
+    #[test]
+    fn legacy_data_key() {
+        let json = r#"{"timestamp":"2023-01-01T00:00:00Z","data":{"Binary":true}}"#;
+        let event: IOEvent = serde_json::from_str(json).unwrap();
+
+        assert_eq!(timestamp(), event.timestamp);
+        assert_eq!(RawValue::Binary(true), event.value);
+
+        // legacy key is not written
+        assert!(serde_json::to_string(&event).unwrap().contains(r#""value":"#));
+    }
+
+    #[test]
+    fn tags_serialization() {
+        let mut event = IOEvent::new(RawValue::Int(1));
+
+        // untagged events are unchanged
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(!json.contains("tags"));
+        let deserialized: IOEvent = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.tags.is_empty());
+
+        event.tag("calibration").tag("calibration");
+        assert_eq!(1, event.tags.len());
+
+        let json = serde_json::to_string(&event).unwrap();
+        let deserialized: IOEvent = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.has_tag("calibration"));
+        assert!(!deserialized.has_tag("override"));
+    }
+}
@@ -1,5 +1,7 @@
+use crate::errors::ValueError;
 use crate::io;
 use crate::io::{IdType, IOKind, IODirection};
+use crate::units::{Celsius, Conductivity, Humidity, Ph, Unit};
 use serde::{Deserialize, Serialize};
 use std::fmt::Formatter;
 
@@ -21,6 +23,22 @@ pub struct DeviceMetadata {
 
     /// I/O direction
     pub direction: IODirection,
+
+    /// Free-text notes describing device
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Free-text physical location of device (eg: "reservoir A, north probe")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+
+    /// Inclusive bounds of valid readings, expressed in the unit of `kind`
+    ///
+    /// Bounds are only set via [`DeviceMetadata::set_bounds()`] so that they are validated by
+    /// the associated [`Unit`]. Deserialized bounds are checked by
+    /// [`DeviceMetadata::validate_bounds()`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) bounds: Option<(f32, f32)>,
 }
 
 impl DeviceMetadata {
@@ -63,8 +81,116 @@ impl DeviceMetadata {
             id,
             kind,
             direction,
+            description: None,
+            location: None,
+            bounds: None,
+        }
+    }
+
+    /// Set bounds of valid readings using the unit produced by this kind of device
+    ///
+    /// Bounds are stored alongside `kind` so that they are persisted with device configuration.
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: Inclusive lower bound
+    /// - `max`: Inclusive upper bound
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` if bounds were set
+    /// - `Err` with [`ValueError::UnitMismatch`] if [`Unit::KIND`] is not `kind`, with
+    ///   [`ValueError::UnitRange`] if either bound is out of range for the unit, or with
+    ///   [`ValueError::InvalidBounds`] if `min` is greater than `max`. Previous bounds are kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::{DeviceMetadata, IODirection, IOKind};
+    /// use sensd::units::{Celsius, Ph};
+    ///
+    /// let mut metadata = DeviceMetadata::new("pH", 0, IOKind::PH, IODirection::In);
+    ///
+    /// assert!(metadata.set_bounds(Ph(4.0), Ph(9.0)).is_ok());
+    /// assert_eq!(Some((Ph(4.0), Ph(9.0))), metadata.bounds());
+    ///
+    /// // nonsensical limits are rejected
+    /// assert!(metadata.set_bounds(Ph(0.0), Ph(1000.0)).is_err());
+    /// assert!(metadata.set_bounds(Celsius(0.0), Celsius(30.0)).is_err());
+    /// ```
+    pub fn set_bounds<U: Unit>(&mut self, min: U, max: U) -> Result<(), ValueError> {
+        if U::KIND != self.kind {
+            return Err(ValueError::UnitMismatch {unit: U::SYMBOL, kind: self.kind});
+        }
+
+        self.bounds = Some(validated_bounds(min, max)?);
+        Ok(())
+    }
+
+    /// Check bounds which were not set by [`DeviceMetadata::set_bounds()`]
+    ///
+    /// Bounds which are deserialized or restored by [`crate::io::Device::from_config()`] bypass
+    /// validation, therefore this should be called before such metadata is used.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` if bounds are not set, or are valid for the unit of `kind`
+    /// - `Err` with [`ValueError::NoUnit`] if `kind` has no associated unit, otherwise the same
+    ///   errors as [`DeviceMetadata::set_bounds()`]
+    pub fn validate_bounds(&self) -> Result<(), ValueError> {
+        let (min, max) = match self.bounds {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+        match self.kind {
+            IOKind::PH => validated_bounds(Ph(min), Ph(max)),
+            IOKind::Temperature => validated_bounds(Celsius(min), Celsius(max)),
+            IOKind::RelativeHumidity => validated_bounds(Humidity(min), Humidity(max)),
+            IOKind::EC => validated_bounds(Conductivity(min), Conductivity(max)),
+            kind => Err(ValueError::NoUnit {kind}),
+        }.map(|_| ())
+    }
+
+    /// Getter for bounds of valid readings
+    ///
+    /// # Returns
+    ///
+    /// Bounds set by [`DeviceMetadata::set_bounds()`], or `None` if bounds are not set or if
+    /// [`Unit::KIND`] is not `kind`
+    pub fn bounds<U: Unit>(&self) -> Option<(U, U)> {
+        match U::KIND == self.kind {
+            true => self.bounds.map(|(min, max)| (U::new(min), U::new(max))),
+            false => None,
         }
     }
+
+    /// Getter for bounds of valid readings as scalar values
+    pub fn raw_bounds(&self) -> Option<(f32, f32)> {
+        self.bounds
+    }
+
+    /// Remove bounds of valid readings
+    pub fn clear_bounds(&mut self) {
+        self.bounds = None;
+    }
+}
+
+/// Validate bounds against the default range of `U`
+///
+/// # Returns
+///
+/// `Ok` with scalar bounds, otherwise `Err` with [`ValueError::UnitRange`] if either bound is out
+/// of range or [`ValueError::InvalidBounds`] if `min` is greater than `max`
+fn validated_bounds<U: Unit>(min: U, max: U) -> Result<(f32, f32), ValueError> {
+    let (min, max) = (U::validated(min.value())?, U::validated(max.value())?);
+    match min.value() > max.value() {
+        true => Err(ValueError::InvalidBounds {min: min.value(), max: max.value()}),
+        false => Ok((min.value(), max.value())),
+    }
 }
 
 impl std::fmt::Display for DeviceMetadata {
@@ -79,6 +205,7 @@ impl std::fmt::Display for DeviceMetadata {
 
 #[cfg(test)]
 mod tests {
+    use crate::errors::ValueError;
     use crate::io::{DeviceMetadata, IODirection, IOKind};
 
     #[test]
@@ -87,4 +214,64 @@ mod tests {
         DeviceMetadata::new("as &str", 0, IOKind::default(), IODirection::default());
         DeviceMetadata::new(String::from("as String"), 0, IOKind::default(), IODirection::default());
     }
-}
\ No newline at end of file
+
+    #[test]
+    /// Metadata built with identical parameters should be equal
+    fn equality() {
+        let a = DeviceMetadata::new("probe", 1, IOKind::PH, IODirection::In);
+        let b = DeviceMetadata::new(String::from("probe"), 1, IOKind::PH, IODirection::In);
+        assert_eq!(a, b);
+        assert_eq!(a, a.clone());
+
+        assert_ne!(a, DeviceMetadata::new("probe", 2, IOKind::PH, IODirection::In));
+        assert_ne!(a, DeviceMetadata::new("probe", 1, IOKind::EC, IODirection::In));
+
+        let mut c = b.clone();
+        c.location = Some(String::from("reservoir"));
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    /// Ensure that all fields survive a round trip through JSON
+    fn serde_round_trip() {
+        let mut metadata = DeviceMetadata::new("pH probe", 7, IOKind::PH, IODirection::Out);
+        metadata.description = Some(String::from("calibrated monthly"));
+        metadata.location = Some(String::from("reservoir A, north probe"));
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        let deserialized: DeviceMetadata = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(metadata, deserialized);
+    }
+
+    #[test]
+    /// Metadata serialized without `description` or `location` should still be deserialized
+    fn deserialize_wo_descriptive_fields() {
+        let json = r#"{"name":"","id":0,"kind":"PH","direction":"In"}"#;
+        let metadata: DeviceMetadata = serde_json::from_str(json).unwrap();
+
+        assert!(metadata.description.is_none());
+        assert!(metadata.location.is_none());
+    }
+
+    #[test]
+    fn validate_bounds() {
+        let json = r#"{"name":"","id":0,"kind":"PH","direction":"In","bounds":[4.0,9.0]}"#;
+        let metadata: DeviceMetadata = serde_json::from_str(json).unwrap();
+        assert!(metadata.validate_bounds().is_ok());
+        assert!(DeviceMetadata::default().validate_bounds().is_ok());
+
+        // deserialized bounds bypass `set_bounds()`
+        let json = r#"{"name":"","id":0,"kind":"PH","direction":"In","bounds":[-3.0,1000.0]}"#;
+        let metadata: DeviceMetadata = serde_json::from_str(json).unwrap();
+        assert!(matches!(metadata.validate_bounds(), Err(ValueError::UnitRange {..})));
+
+        let json = r#"{"name":"","id":0,"kind":"PH","direction":"In","bounds":[9.0,4.0]}"#;
+        let metadata: DeviceMetadata = serde_json::from_str(json).unwrap();
+        assert!(matches!(metadata.validate_bounds(), Err(ValueError::InvalidBounds {..})));
+
+        let json = r#"{"name":"","id":0,"kind":"Flow","direction":"In","bounds":[0.0,1.0]}"#;
+        let metadata: DeviceMetadata = serde_json::from_str(json).unwrap();
+        assert!(matches!(metadata.validate_bounds(), Err(ValueError::NoUnit {..})));
+    }
+}
@@ -1,10 +1,15 @@
 //! Encapsulate IO for devices
+mod config;
 mod event;
 mod metadata;
+mod registry;
 mod types;
 mod dev;
 
+pub use config::{DeviceConfig, InputConfig};
 pub use dev::*;
-pub use event::IOEvent;
+pub use event::{IOEvent, RATE_TAG};
+pub(crate) use event::NumericBinaryEvent;
 pub use metadata::DeviceMetadata;
+pub use registry::{DeviceFactory, DeviceRegistry};
 pub use types::*;
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use crate::io::{Device, DeviceConfig, IOKind, IdType};
+
+/// Closure which builds a default-configured device from a name and id
+pub type DeviceFactory<D> = Box<dyn Fn(String, IdType) -> D + Send + Sync>;
+
+/// Mapping of [`IOKind`] to factories of default-configured devices
+///
+/// This allows configuration-driven setup to instantiate devices by kind, without matching over
+/// every [`IOKind`]. Each factory typically attaches the low-level command and bounds appropriate
+/// for a kind of hardware. Since factories are called with the name and id of each new device, a
+/// [`crate::action::IOCommand`] built within a factory may capture per-device state (ie: a bus
+/// address derived from `id`).
+///
+/// Separate registries are used for [`crate::io::Input`] and [`crate::io::Output`] devices.
+///
+/// # Example
+///
+/// ```
+/// use sensd::action::IOCommand;
+/// use sensd::io::{Device, DeviceGetters, DeviceRegistry, Input, IOKind, RawValue};
+///
+/// let mut registry: DeviceRegistry<Input> = DeviceRegistry::new();
+/// registry.register(IOKind::PH, |name, id| {
+///     Input::new(name, id, IOKind::PH)
+///         .with_command(IOCommand::input(|| RawValue::Float(7.0))).unwrap()
+///         .set_validation(true)
+/// });
+///
+/// let mut probe = registry.create(IOKind::PH, "reservoir pH", 0).unwrap();
+/// assert_eq!(IOKind::PH, probe.kind());
+/// assert_eq!(RawValue::Float(7.0), probe.read().unwrap().value);
+///
+/// assert!(registry.create(IOKind::EC, "reservoir EC", 1).is_none());
+/// ```
+pub struct DeviceRegistry<D: Device> {
+    factories: HashMap<IOKind, DeviceFactory<D>>,
+}
+
+impl<D: Device> Default for DeviceRegistry<D> {
+    fn default() -> Self {
+        Self { factories: HashMap::new() }
+    }
+}
+
+impl<D: Device> DeviceRegistry<D> {
+    /// Constructor for an empty [`DeviceRegistry`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register factory for `kind`
+    ///
+    /// Any factory previously registered for `kind` is replaced.
+    ///
+    /// # Parameters
+    ///
+    /// - `kind`: Kind of device built by `factory`
+    /// - `factory`: Closure accepting device name and id
+    ///
+    /// # Returns
+    ///
+    /// Mutable reference to `self` to allow method chaining
+    pub fn register<F>(&mut self, kind: IOKind, factory: F) -> &mut Self
+    where
+        F: Fn(String, IdType) -> D + Send + Sync + 'static
+    {
+        self.factories.insert(kind, Box::new(factory));
+        self
+    }
+
+    /// Check if a factory is registered for `kind`
+    pub fn is_registered(&self, kind: IOKind) -> bool {
+        self.factories.contains_key(&kind)
+    }
+
+    /// Build a device using the factory registered for `kind`
+    ///
+    /// # Parameters
+    ///
+    /// - `kind`: Kind of device to build
+    /// - `name`: Name of device
+    /// - `id`: Id of device
+    ///
+    /// # Returns
+    ///
+    /// `None` if no factory is registered for `kind`
+    pub fn create<N>(&self, kind: IOKind, name: N, id: IdType) -> Option<D>
+    where
+        N: Into<String>
+    {
+        self.factories.get(&kind)
+            .map(|factory| factory(name.into(), id))
+    }
+
+    /// Build a device from configuration using the factory registered for its kind
+    ///
+    /// Unlike [`Device::from_config()`], the command and any other defaults of the factory are
+    /// retained. Descriptive metadata is applied, and a [`crate::storage::Log`] is initialized if
+    /// `config` specifies one and the factory did not.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no factory is registered for kind stored in `config`
+    pub fn create_from_config(&self, config: &DeviceConfig) -> Option<D> {
+        let metadata = &config.metadata;
+        let mut device = self.create(metadata.kind, metadata.name.clone(), metadata.id)?;
+
+        device.set_description(metadata.description.clone());
+        device.set_location(metadata.location.clone());
+
+        match config.log && !device.has_log() {
+            true => Some(device.init_log()),
+            false => Some(device),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::IOCommand;
+    use crate::io::{Device, DeviceGetters, DeviceRegistry, IOKind, Input, Output, RawValue};
+    use crate::storage::Chronicle;
+
+    #[test]
+    fn register_and_create() {
+        let mut registry: DeviceRegistry<Output> = DeviceRegistry::default();
+        assert!(!registry.is_registered(IOKind::Flow));
+
+        registry
+            .register(IOKind::Flow, |name, id| Output::new(name, id, IOKind::Flow))
+            .register(IOKind::Light, |name, id| Output::new(name, id, IOKind::Light));
+        assert!(registry.is_registered(IOKind::Flow));
+
+        let pump = registry.create(IOKind::Flow, "pump", 3).unwrap();
+        assert_eq!((IOKind::Flow, 3), (pump.kind(), pump.id()));
+        assert!(registry.create(IOKind::PH, "missing", 0).is_none());
+
+        // registration replaces previous factory
+        registry.register(IOKind::Flow, |name, id| Output::new(name, id, IOKind::Flow).init_log());
+        assert!(registry.create(IOKind::Flow, "pump", 3).unwrap().has_log());
+    }
+
+    #[test]
+    fn create_from_config() {
+        let mut registry: DeviceRegistry<Input> = DeviceRegistry::new();
+        registry.register(IOKind::PH, |name, id| {
+            Input::new(name, id, IOKind::PH)
+                .with_command(IOCommand::input(|| RawValue::Float(6.5))).unwrap()
+        });
+
+        let mut config = Input::new("probe", 2, IOKind::PH).init_log().to_config();
+        config.metadata.location = Some(String::from("reservoir"));
+
+        let mut input = registry.create_from_config(&config).unwrap();
+        assert_eq!(&config.metadata, input.metadata());
+        assert!(input.has_log());
+        assert_eq!(RawValue::Float(6.5), input.read().unwrap().value);
+
+        config.metadata.kind = IOKind::EC;
+        assert!(registry.create_from_config(&config).is_none());
+    }
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+use crate::io::{RawValue, RawValueKind};
+
+/// Method of combining multiple samples into a single value
+///
+/// # See Also
+///
+/// - [`crate::io::Input::read_n()`] for oversampling an input
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Aggregation {
+    #[default]
+    Mean,
+    Median,
+    Min,
+    Max,
+    /// Most recent sample
+    Last,
+}
+
+impl Aggregation {
+    /// Combine samples into a single value
+    ///
+    /// Numeric results are converted back to the kind of the samples, therefore integer means and
+    /// medians are rounded to the nearest integer. [`RawValue::Binary`] samples are combined by
+    /// majority vote regardless of aggregation, with ties resolving to `false`. [`Aggregation::Last`]
+    /// returns the final sample unchanged, and is therefore applicable to every kind.
+    ///
+    /// # Parameters
+    ///
+    /// - `samples`: Values to combine. All values should be of the same kind.
+    ///
+    /// # Returns
+    ///
+    /// `None` if `samples` is empty, or unless aggregation is [`Aggregation::Last`], if `samples`
+    /// contains differing kinds or [`RawValue::Text`]. Otherwise, `Some` with combined value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::{Aggregation, RawValue};
+    ///
+    /// let samples = [RawValue::Float(1.0), RawValue::Float(9.0), RawValue::Float(2.0)];
+    ///
+    /// assert_eq!(Some(RawValue::Float(4.0)), Aggregation::Mean.aggregate(&samples));
+    /// assert_eq!(Some(RawValue::Float(2.0)), Aggregation::Median.aggregate(&samples));
+    /// ```
+    pub fn aggregate(&self, samples: &[RawValue]) -> Option<RawValue> {
+        if *self == Self::Last {
+            return samples.last().cloned();
+        }
+
+        let kind = samples.first()?.kind();
+        if kind == RawValueKind::Text || samples.iter().any(|sample| sample.kind() != kind) {
+            return None;
+        }
+
+        if kind == RawValueKind::Binary {
+            let high = samples.iter().filter(|sample| **sample == RawValue::Binary(true)).count();
+            return Some(RawValue::Binary(high * 2 > samples.len()));
+        }
+
+        let mut values: Vec<f64> = samples.iter().filter_map(RawValue::as_f64).collect();
+        values.sort_by(|a, b| a.total_cmp(b));
+
+        let value = match self {
+            Self::Mean => values.iter().sum::<f64>() / values.len() as f64,
+            Self::Median => {
+                let middle = values.len() / 2;
+                match values.len() % 2 {
+                    0 => (values[middle - 1] + values[middle]) / 2.0,
+                    _ => values[middle],
+                }
+            }
+            Self::Min => values[0],
+            Self::Max => values[values.len() - 1],
+            Self::Last => unreachable!(),
+        };
+
+        Some(with_kind(kind, value))
+    }
+}
+
+/// Convert `f64` to numeric [`RawValue`] of the given kind, rounding and saturating as necessary
+fn with_kind(kind: RawValueKind, value: f64) -> RawValue {
+    let rounded = value.round();
+    match kind {
+        RawValueKind::Binary => RawValue::Binary(value != 0.0),
+        RawValueKind::PosInt8 => RawValue::PosInt8(rounded as u8),
+        RawValueKind::Int8 => RawValue::Int8(rounded as i8),
+        RawValueKind::PosInt => RawValue::PosInt(rounded as u32),
+        RawValueKind::Int => RawValue::Int(rounded as i32),
+        RawValueKind::Float => RawValue::Float(value as f32),
+        RawValueKind::Percent => RawValue::percent(value as f32),
+        RawValueKind::Text => unreachable!("Text cannot be aggregated"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{Aggregation, RawValue};
+
+    #[test]
+    fn aggregate_numeric() {
+        let samples: Vec<RawValue> = [4, 1, 3, 2].into_iter().map(RawValue::Int).collect();
+
+        // integer results are rounded
+        assert_eq!(Some(RawValue::Int(3)), Aggregation::Mean.aggregate(&samples));
+        assert_eq!(Some(RawValue::Int(3)), Aggregation::Median.aggregate(&samples));
+        assert_eq!(Some(RawValue::Int(1)), Aggregation::Min.aggregate(&samples));
+        assert_eq!(Some(RawValue::Int(4)), Aggregation::Max.aggregate(&samples));
+        assert_eq!(Some(RawValue::Int(2)), Aggregation::Last.aggregate(&samples));
+
+        assert_eq!(None, Aggregation::Mean.aggregate(&[]));
+        assert_eq!(None, Aggregation::Mean.aggregate(&[RawValue::Int(1), RawValue::Float(1.0)]));
+        assert_eq!(None, Aggregation::Max.aggregate(&[RawValue::from("a"), RawValue::from("b")]));
+        assert_eq!(Some(RawValue::from("b")), Aggregation::Last.aggregate(&[RawValue::from("a"), RawValue::from("b")]));
+    }
+
+    #[test]
+    fn aggregate_binary() {
+        let samples = [RawValue::Binary(true), RawValue::Binary(false), RawValue::Binary(true)];
+        for aggregation in [Aggregation::Mean, Aggregation::Median, Aggregation::Min, Aggregation::Max] {
+            assert_eq!(Some(RawValue::Binary(true)), aggregation.aggregate(&samples));
+        }
+
+        // ties resolve to false
+        let samples = [RawValue::Binary(true), RawValue::Binary(false)];
+        assert_eq!(Some(RawValue::Binary(false)), Aggregation::Mean.aggregate(&samples));
+    }
+}
@@ -8,11 +8,14 @@ use serde::{Deserialize, Serialize};
 /// - `In`: indicates that data came from the outside world. This is the default.
 /// - `Out`: indicates that accept data was sent to manipulate and represents
 ///   physical/tangible change.
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+/// - `InOut`: indicates that data flows in both directions. This is used by
+///   [`crate::action::IOCommand::Bidirectional`].
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum IODirection {
     #[default]
     In,
     Out,
+    InOut,
 }
 
 impl Display for IODirection {
@@ -20,6 +23,7 @@ impl Display for IODirection {
         let name = match self {
             IODirection::In => "Input",
             IODirection::Out => "Output",
+            IODirection::InOut => "Bidirectional",
         };
         write!(f, "{}", name)
     }
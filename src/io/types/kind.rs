@@ -1,12 +1,13 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use crate::io::RawValueKind;
 
 /// Representation of physical processes
 ///
 /// # Contribution
 ///
 /// This is not an exhaustive list. Feel free to add variants as needed.
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub enum IOKind {
     #[default]
     Unassigned,
@@ -27,6 +28,54 @@ pub enum IOKind {
     PH,
 }
 
+/// Plausible range of readings for a kind of device
+///
+/// This is used to reject obviously invalid readings (eg: a disconnected pH probe reading 25) when
+/// explicit bounds are not given. Ranges are intentionally broad and assume common units:
+///
+/// | Kind | Range | Unit |
+/// |------|-------|------|
+/// | [`IOKind::PH`] | 0–14 | pH |
+/// | [`IOKind::EC`] | 0–20 | mS/cm |
+/// | [`IOKind::RelativeHumidity`] | 0–100 | % |
+/// | [`IOKind::Temperature`] | -40–125 | °C |
+/// | [`IOKind::VocIndex`], [`IOKind::NoxIndex`] | 0–500 | index |
+///
+/// # Returns
+///
+/// `Some` with inclusive `(min, max)`, or `None` if kind has no meaningful default range
+///
+/// # See Also
+///
+/// - [`crate::io::Input::set_validation()`] for enabling validation of readings
+pub fn kind_defaults(kind: IOKind) -> Option<(f32, f32)> {
+    match kind {
+        IOKind::PH => Some((0.0, 14.0)),
+        IOKind::EC => Some((0.0, 20.0)),
+        IOKind::RelativeHumidity => Some((0.0, 100.0)),
+        IOKind::Temperature => Some((-40.0, 125.0)),
+        IOKind::VocIndex | IOKind::NoxIndex => Some((0.0, 500.0)),
+        _ => None,
+    }
+}
+
+impl IOKind {
+    /// Kind of [`crate::io::RawValue`] a device of this kind is expected to produce
+    ///
+    /// [`IOKind::Proximity`] is expected to produce [`RawValueKind::Binary`]. All other kinds,
+    /// including [`IOKind::Unassigned`], are expected to produce [`RawValueKind::Float`].
+    ///
+    /// # See Also
+    ///
+    /// - [`crate::io::Input::set_value_kind_check()`] for rejecting readings of another kind
+    pub fn expected_value_kind(&self) -> RawValueKind {
+        match self {
+            IOKind::Proximity => RawValueKind::Binary,
+            _ => RawValueKind::Float,
+        }
+    }
+}
+
 impl Display for IOKind {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let name = match self {
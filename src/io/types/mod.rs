@@ -1,10 +1,12 @@
 //! Low-level type and interface definitions for I/O with the filesystem, memory, and other resources.
 
+mod aggregation;
 mod direction;
 mod id;
 mod kind;
 mod raw;
 
+pub use aggregation::*;
 pub use direction::*;
 pub use id::*;
 pub use kind::*;
@@ -1,6 +1,8 @@
-use crate::errors::ErrorType;
+use crate::errors::{ErrorType, ValueError};
 use float_cmp::approx_eq;
-use serde::{Deserialize, Serialize};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
@@ -11,25 +13,370 @@ use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 ///
 /// # Contribution
 ///
+/// [`RawValue::Text`] is provided for devices which report strings (ie: barcode readers, GPS
+/// modules). Text values are not numeric, therefore they are excluded from
+/// [`crate::storage::Log::statistics()`] and numeric filters. Arithmetic operators panic and
+/// comparison returns `None` when either operand is text.
+///
+/// [`RawValue::Percent`] is provided for quantities which are naturally expressed as a
+/// percentage (ie: PWM duty cycle, dimmers, humidity, soil moisture). See [`RawValue::percent()`].
+///
 /// The implemented types have been chosen as a good fit for GPIO. However,
 /// if a type is needed that is not here, feel free to initiate a pull request.
-#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialOrd)]
+///
+/// # Serialization
+///
+/// Values are serialized with an explicit type tag (ie: `{"type":"float","value":6.4}`) so that
+/// variants with compatible representations never load back as a different variant. The legacy
+/// externally tagged form (ie: `{"Float":6.4}`) and bare untagged values are still accepted
+/// when deserializing. Bare values are interpreted as `Binary`, `PosInt`, `Int`, `Float` or `Text`
+/// depending on their JSON type.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
 pub enum RawValue {
+    /// Deserialized from either a boolean or `0`/`1`
     Binary(bool),
     PosInt8(u8),
     Int8(i8),
     PosInt(u32),
     Int(i32),
     Float(f32),
+    /// Percentage between `0.0` and `100.0`
+    ///
+    /// Construct with [`RawValue::percent()`] so that the range is enforced. Arithmetic between
+    /// percentages saturates at the bounds of the range.
+    ///
+    /// When written to an analog [`crate::io::Output`], a percentage represents a position within
+    /// the range of the device rather than an absolute value. [`RawValue::scale_percent()`] maps
+    /// a percentage onto the bounds of the device (ie: [`crate::io::DeviceMetadata::raw_bounds()`]).
+    Percent(f32),
+    /// Arbitrary text reported by device
+    Text(String),
+}
+
+/// Discriminant of [`RawValue`] without an associated value
+///
+/// This is used to describe the type of value expected by a device, such as when parsing
+/// [`RawValue`] from text with [`RawValue::parse()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RawValueKind {
+    Binary,
+    PosInt8,
+    Int8,
+    PosInt,
+    Int,
+    Float,
+    Percent,
+    Text,
+}
+
+impl Display for RawValueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Binary => "Binary",
+                Self::PosInt8 => "PosInt8",
+                Self::Int8 => "Int8",
+                Self::PosInt => "PosInt",
+                Self::Int => "Int",
+                Self::Float => "Float",
+                Self::Percent => "Percent",
+                Self::Text => "Text",
+            }
+        )
+    }
+}
+
+/// Borrowed [`RawValue`] which serializes [`RawValue::Binary`] as `0` or `1`
+///
+/// All other variants are serialized exactly as [`RawValue`].
+///
+/// # See Also
+///
+/// - [`crate::storage::Log::set_numeric_binary()`] for usage
+pub(crate) struct NumericBinary<'a>(pub &'a RawValue);
+
+impl Serialize for NumericBinary<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        /// Tagged like [`RawValue`] so that only the representation of the value differs
+        #[derive(Serialize)]
+        #[serde(tag = "type", content = "value", rename_all = "snake_case")]
+        enum Numeric {
+            Binary(u8),
+        }
+
+        match self.0 {
+            RawValue::Binary(value) => Numeric::Binary(u8::from(*value)).serialize(serializer),
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+/// Accept both boolean and numeric (`0`/`1`) representations of [`RawValue::Binary`]
+fn deserialize_binary<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Binary {
+        Bool(bool),
+        Numeric(u8),
+    }
+
+    match Binary::deserialize(deserializer)? {
+        Binary::Bool(value) => Ok(value),
+        Binary::Numeric(0) => Ok(false),
+        Binary::Numeric(1) => Ok(true),
+        Binary::Numeric(other) => Err(D::Error::custom(
+            format!("invalid binary value: {}", other))),
+    }
+}
+
+/// [`RawValue`] with explicit type tag, as serialized
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+enum TaggedValue {
+    #[serde(deserialize_with = "deserialize_binary")]
+    Binary(bool),
+    PosInt8(u8),
+    Int8(i8),
+    PosInt(u32),
+    Int(i32),
+    Float(f32),
+    Percent(f32),
+    Text(String),
+}
+
+/// [`RawValue`] in legacy externally tagged form (ie: `{"Float":6.4}`)
+#[derive(Deserialize)]
+enum LegacyValue {
+    #[serde(deserialize_with = "deserialize_binary")]
+    Binary(bool),
+    PosInt8(u8),
+    Int8(i8),
+    PosInt(u32),
+    Int(i32),
+    Float(f32),
+    Percent(f32),
+    Text(String),
+}
+
+/// [`RawValue`] without any type information
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BareValue {
+    Binary(bool),
+    PosInt(u32),
+    Int(i32),
+    Float(f32),
+    Text(String),
+}
+
+/// Representations of [`RawValue`] accepted when deserializing
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CompatValue {
+    Tagged(TaggedValue),
+    Legacy(LegacyValue),
+    Bare(BareValue),
+}
+
+impl<'de> Deserialize<'de> for RawValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = CompatValue::deserialize(deserializer).map_err(|_| {
+            D::Error::custom("data did not match any representation of RawValue")
+        })?;
+
+        Ok(match value {
+            CompatValue::Tagged(value) => match value {
+                TaggedValue::Binary(value) => RawValue::Binary(value),
+                TaggedValue::PosInt8(value) => RawValue::PosInt8(value),
+                TaggedValue::Int8(value) => RawValue::Int8(value),
+                TaggedValue::PosInt(value) => RawValue::PosInt(value),
+                TaggedValue::Int(value) => RawValue::Int(value),
+                TaggedValue::Float(value) => RawValue::Float(value),
+                TaggedValue::Percent(value) => RawValue::percent(value),
+                TaggedValue::Text(value) => RawValue::Text(value),
+            },
+            CompatValue::Legacy(value) => match value {
+                LegacyValue::Binary(value) => RawValue::Binary(value),
+                LegacyValue::PosInt8(value) => RawValue::PosInt8(value),
+                LegacyValue::Int8(value) => RawValue::Int8(value),
+                LegacyValue::PosInt(value) => RawValue::PosInt(value),
+                LegacyValue::Int(value) => RawValue::Int(value),
+                LegacyValue::Float(value) => RawValue::Float(value),
+                LegacyValue::Percent(value) => RawValue::percent(value),
+                LegacyValue::Text(value) => RawValue::Text(value),
+            },
+            CompatValue::Bare(value) => match value {
+                BareValue::Binary(value) => RawValue::Binary(value),
+                BareValue::PosInt(value) => RawValue::PosInt(value),
+                BareValue::Int(value) => RawValue::Int(value),
+                BareValue::Float(value) => RawValue::Float(value),
+                BareValue::Text(value) => RawValue::Text(value),
+            },
+        })
+    }
 }
 
 impl RawValue {
+    /// Constructor for [`RawValue::Percent`]
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: Percentage which is clamped to `0.0`–`100.0`. `NaN` is treated as `0.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::RawValue;
+    ///
+    /// assert_eq!(RawValue::Percent(100.0), RawValue::percent(120.0));
+    /// assert_eq!("42.5%", RawValue::percent(42.5).to_string());
+    /// ```
+    pub fn percent(value: f32) -> Self {
+        match value.is_nan() {
+            true => Self::Percent(0.0),
+            false => Self::Percent(value.clamp(0.0, 100.0)),
+        }
+    }
+
+    /// Map a percentage onto a scalar range
+    ///
+    /// This is used to convert a percentage into the absolute value expected by an analog device,
+    /// such as an [`crate::io::Output`] whose bounds are given by
+    /// [`crate::io::DeviceMetadata::raw_bounds()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: Value corresponding to `0%`
+    /// - `max`: Value corresponding to `100%`
+    ///
+    /// # Returns
+    ///
+    /// `Some` with `min + (max - min) * percent / 100`, or `None` if variant is not
+    /// [`RawValue::Percent`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::RawValue;
+    ///
+    /// // PWM output with 8-bit duty cycle
+    /// assert_eq!(Some(127.5), RawValue::percent(50.0).scale_percent(0.0, 255.0));
+    /// assert_eq!(None, RawValue::Float(50.0).scale_percent(0.0, 255.0));
+    /// ```
+    pub fn scale_percent(&self, min: f32, max: f32) -> Option<f32> {
+        match *self {
+            Self::Percent(percent) => Some(min + (max - min) * percent / 100.0),
+            _ => None,
+        }
+    }
+
     pub fn is_numeric(&self) -> bool {
         match self {
-            Self::Binary(_) => false,
+            Self::Binary(_) | Self::Text(_) => false,
             _ => true,
         }
     }
+
+    /// Lossless conversion of numeric variants to `f64`
+    ///
+    /// # Returns
+    ///
+    /// An `Option` that is:
+    ///
+    /// - `Some` containing value as `f64` if variant is numeric
+    /// - `None` if variant is not numeric (ie: [`RawValue::Binary`] or [`RawValue::Text`])
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Self::Binary(_) | Self::Text(_) => None,
+            Self::PosInt8(val) => Some(val.into()),
+            Self::Int8(val) => Some(val.into()),
+            Self::PosInt(val) => Some(val.into()),
+            Self::Int(val) => Some(val.into()),
+            Self::Float(val) | Self::Percent(val) => Some(val.into()),
+        }
+    }
+
+    /// Get kind of variant
+    ///
+    /// # Returns
+    ///
+    /// [`RawValueKind`] corresponding to variant
+    pub fn kind(&self) -> RawValueKind {
+        match self {
+            Self::Binary(_) => RawValueKind::Binary,
+            Self::PosInt8(_) => RawValueKind::PosInt8,
+            Self::Int8(_) => RawValueKind::Int8,
+            Self::PosInt(_) => RawValueKind::PosInt,
+            Self::Int(_) => RawValueKind::Int,
+            Self::Float(_) => RawValueKind::Float,
+            Self::Percent(_) => RawValueKind::Percent,
+            Self::Text(_) => RawValueKind::Text,
+        }
+    }
+
+    /// Parse value from text according to the kind of value expected by target device
+    ///
+    /// Surrounding whitespace is ignored. [`RawValueKind::Binary`] accepts "true"/"false",
+    /// "on"/"off", and "1"/"0" (case-insensitive). All other kinds are parsed as their respective
+    /// primitive type, therefore "1" becomes `RawValue::Float(1.0)` when `expected` is
+    /// [`RawValueKind::Float`]. [`RawValueKind::Percent`] accepts an optional trailing `%` and is
+    /// clamped by [`RawValue::percent()`]. [`RawValueKind::Text`] accepts any input.
+    ///
+    /// # Parameters
+    ///
+    /// - `s`: Text to parse
+    /// - `expected`: Kind of value to parse `s` as
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with parsed [`RawValue`] whose kind is `expected`
+    /// - `Err` with [`ValueError::ParseError`] naming `s` and `expected` if `s` cannot be parsed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::{RawValue, RawValueKind};
+    ///
+    /// assert_eq!(RawValue::Binary(true), RawValue::parse("on", RawValueKind::Binary).unwrap());
+    /// assert_eq!(RawValue::Float(6.4), RawValue::parse("6.4", RawValueKind::Float).unwrap());
+    /// assert!(RawValue::parse("6.4", RawValueKind::Int).is_err());
+    /// ```
+    pub fn parse(s: &str, expected: RawValueKind) -> Result<RawValue, ErrorType> {
+        let trimmed = s.trim();
+        let value = match expected {
+            RawValueKind::Binary => match trimmed.to_ascii_lowercase().as_str() {
+                "true" | "on" | "1" => Some(Self::Binary(true)),
+                "false" | "off" | "0" => Some(Self::Binary(false)),
+                _ => None,
+            },
+            RawValueKind::PosInt8 => trimmed.parse().ok().map(Self::PosInt8),
+            RawValueKind::Int8 => trimmed.parse().ok().map(Self::Int8),
+            RawValueKind::PosInt => trimmed.parse().ok().map(Self::PosInt),
+            RawValueKind::Int => trimmed.parse().ok().map(Self::Int),
+            RawValueKind::Float => trimmed.parse().ok().map(Self::Float),
+            RawValueKind::Percent => trimmed.strip_suffix('%').unwrap_or(trimmed).trim_end()
+                .parse().ok().map(Self::percent),
+            RawValueKind::Text => Some(Self::Text(trimmed.to_string())),
+        };
+
+        value.ok_or_else(|| {
+            Box::new(ValueError::ParseError { input: s.to_string(), kind: expected }) as ErrorType
+        })
+    }
 }
 
 impl Default for RawValue {
@@ -52,6 +399,8 @@ impl Display for RawValue {
                 Self::PosInt(val) => val.to_string(),
                 Self::Int(val) => val.to_string(),
                 Self::Float(val) => val.to_string(),
+                Self::Percent(val) => format!("{}%", val),
+                Self::Text(val) => val.clone(),
             }
         )
     }
@@ -88,6 +437,16 @@ impl TryFrom<f32> for RawValue {
         Ok(RawValue::Float(value))
     }
 }
+impl From<String> for RawValue {
+    fn from(value: String) -> Self {
+        RawValue::Text(value)
+    }
+}
+impl From<&str> for RawValue {
+    fn from(value: &str) -> Self {
+        RawValue::Text(value.to_string())
+    }
+}
 impl TryFrom<bool> for RawValue {
     type Error = ErrorType;
     fn try_from(value: bool) -> Result<Self, Self::Error> {
@@ -103,6 +462,7 @@ impl Add for RawValue {
         match (self, other) {
             (RawValue::Binary(x), RawValue::Binary(y)) => RawValue::Binary(x || y),
             (RawValue::Float(x), RawValue::Float(y)) => RawValue::Float(x + y),
+            (RawValue::Percent(x), RawValue::Percent(y)) => RawValue::percent(x + y),
             (RawValue::Int8(x), RawValue::Int8(y)) => RawValue::Int8(x + y),
             (RawValue::PosInt8(x), RawValue::PosInt8(y)) => RawValue::PosInt8(x + y),
             (RawValue::Int(x), RawValue::Int(y)) => RawValue::Int(x + y),
@@ -119,6 +479,7 @@ impl Sub for RawValue {
         // TODO: Catch binary as type
         match (self, other) {
             (RawValue::Float(x), RawValue::Float(y)) => RawValue::Float(x - y),
+            (RawValue::Percent(x), RawValue::Percent(y)) => RawValue::percent(x - y),
             (RawValue::Int8(x), RawValue::Int8(y)) => RawValue::Int8(x - y),
             (RawValue::PosInt8(x), RawValue::PosInt8(y)) => RawValue::PosInt8(x - y),
             (RawValue::Int(x), RawValue::Int(y)) => RawValue::Int(x - y),
@@ -135,6 +496,8 @@ impl Mul for RawValue {
         // TODO: Catch binary as type
         match (self, other) {
             (RawValue::Float(x), RawValue::Float(y)) => RawValue::Float(x * y),
+            // percentage of a percentage (ie: 50% of 50% is 25%)
+            (RawValue::Percent(x), RawValue::Percent(y)) => RawValue::percent(x * y / 100.0),
             (RawValue::Int8(x), RawValue::Int8(y)) => RawValue::Int8(x * y),
             (RawValue::PosInt8(x), RawValue::PosInt8(y)) => RawValue::PosInt8(x * y),
             (RawValue::Int(x), RawValue::Int(y)) => RawValue::Int(x * y),
@@ -151,6 +514,7 @@ impl Div for RawValue {
         // TODO: Catch binary as type
         match (self, other) {
             (RawValue::Float(x), RawValue::Float(y)) => RawValue::Float(x / y),
+            (RawValue::Percent(x), RawValue::Percent(y)) => RawValue::percent(x / y * 100.0),
             (RawValue::Int8(x), RawValue::Int8(y)) => RawValue::Int8(x / y),
             (RawValue::PosInt8(x), RawValue::PosInt8(y)) => RawValue::PosInt8(x / y),
             (RawValue::Int(x), RawValue::Int(y)) => RawValue::Int(x / y),
@@ -198,18 +562,65 @@ impl PartialEq for RawValue {
         match (self, other) {
             (RawValue::Binary(x), RawValue::Binary(y)) => x == y,
             (RawValue::Float(x), RawValue::Float(y)) => approx_eq!(f32, *x, *y, ulps = 2),
+            (RawValue::Percent(x), RawValue::Percent(y)) => approx_eq!(f32, *x, *y, ulps = 2),
             (RawValue::Int8(x), RawValue::Int8(y)) => x == y,
             (RawValue::PosInt8(x), RawValue::PosInt8(y)) => x == y,
             (RawValue::Int(x), RawValue::Int(y)) => x == y,
             (RawValue::PosInt(x), RawValue::PosInt(y)) => x == y,
+            (RawValue::Text(x), RawValue::Text(y)) => x == y,
             _ => false,
         }
     }
 }
 
+/// Values of the same variant are compared by value, and mismatched variants are ordered by
+/// declaration order. Text is compared lexically against other text, but is not ordered against
+/// other variants, so comparison returns `None` if only one operand is [`RawValue::Text`]. This
+/// keeps `partial_cmp` consistent with `PartialEq`.
+impl PartialOrd for RawValue {
+    fn partial_cmp(&self, other: &RawValue) -> Option<Ordering> {
+        match (self, other) {
+            (RawValue::Text(x), RawValue::Text(y)) => x.partial_cmp(y),
+            (RawValue::Text(_), _) | (_, RawValue::Text(_)) => None,
+            (RawValue::Binary(x), RawValue::Binary(y)) => x.partial_cmp(y),
+            (RawValue::Float(x), RawValue::Float(y)) => x.partial_cmp(y),
+            (RawValue::Percent(x), RawValue::Percent(y)) => x.partial_cmp(y),
+            (RawValue::Int8(x), RawValue::Int8(y)) => x.partial_cmp(y),
+            (RawValue::PosInt8(x), RawValue::PosInt8(y)) => x.partial_cmp(y),
+            (RawValue::Int(x), RawValue::Int(y)) => x.partial_cmp(y),
+            (RawValue::PosInt(x), RawValue::PosInt(y)) => x.partial_cmp(y),
+            _ => (self.kind() as u8).partial_cmp(&(other.kind() as u8)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::io::RawValue;
+    use crate::io::{RawValue, RawValueKind};
+
+    #[test]
+    fn parse() {
+        for (input, expected) in [("true", true), ("ON", true), ("1", true), (" off ", false), ("0", false)] {
+            assert_eq!(RawValue::Binary(expected), RawValue::parse(input, RawValueKind::Binary).unwrap());
+        }
+
+        // ambiguity is resolved by expected kind
+        assert_eq!(RawValue::Float(1.0), RawValue::parse("1", RawValueKind::Float).unwrap());
+        assert_eq!(RawValue::PosInt8(1), RawValue::parse("1", RawValueKind::PosInt8).unwrap());
+        assert_eq!(RawValue::Int(-3), RawValue::parse("-3", RawValueKind::Int).unwrap());
+
+        for kind in [RawValueKind::Int, RawValueKind::Float, RawValueKind::Binary] {
+            assert_eq!(kind, RawValue::parse("1", kind).unwrap().kind());
+        }
+
+        // out-of-range and malformed values are rejected
+        assert!(RawValue::parse("-3", RawValueKind::PosInt).is_err());
+        assert!(RawValue::parse("300", RawValueKind::Int8).is_err());
+        assert!(RawValue::parse("2", RawValueKind::Binary).is_err());
+
+        let error = RawValue::parse("six", RawValueKind::Float).unwrap_err();
+        assert_eq!("Could not parse \"six\" as Float", error.to_string());
+    }
 
     #[test]
     fn test_rawvalue_add() {
@@ -299,6 +710,115 @@ mod tests {
         assert_eq!(c, RawValue::Float(3.14 / 2.71));
     }
 
+    #[test]
+    fn deserialize_binary() {
+        for (json, expected) in [
+            ("{\"Binary\":true}", true),
+            ("{\"Binary\":false}", false),
+            ("{\"Binary\":1}", true),
+            ("{\"Binary\":0}", false),
+            (r#"{"type":"binary","value":true}"#, true),
+            (r#"{"type":"binary","value":0}"#, false),
+        ] {
+            let value: RawValue = serde_json::from_str(json).unwrap();
+            assert_eq!(RawValue::Binary(expected), value);
+        }
+
+        assert!(serde_json::from_str::<RawValue>("{\"Binary\":2}").is_err());
+        assert!(serde_json::from_str::<RawValue>(r#"{"type":"binary","value":2}"#).is_err());
+    }
+
+    #[test]
+    fn tagged_serialization() {
+        let json = serde_json::to_string(&RawValue::Float(6.5)).unwrap();
+        assert_eq!(r#"{"type":"float","value":6.5}"#, json);
+        let json = serde_json::to_string(&RawValue::PosInt8(3)).unwrap();
+        assert_eq!(r#"{"type":"pos_int8","value":3}"#, json);
+
+        for value in [
+            RawValue::Binary(true),
+            RawValue::Binary(false),
+            RawValue::PosInt8(1),
+            RawValue::Int8(-1),
+            RawValue::PosInt(1),
+            RawValue::Int(1),
+            RawValue::Float(1.0),
+            RawValue::Float(0.0),
+            RawValue::percent(1.0),
+            RawValue::from("1"),
+        ] {
+            let json = serde_json::to_string(&value).unwrap();
+            let loaded: RawValue = serde_json::from_str(&json).unwrap();
+            assert_eq!(value.kind(), loaded.kind());
+            assert_eq!(value, loaded);
+        }
+    }
+
+    #[test]
+    fn binary_float_never_confused() {
+        let loaded: RawValue = serde_json::from_str(
+            &serde_json::to_string(&RawValue::Binary(true)).unwrap()).unwrap();
+        assert_eq!(RawValueKind::Binary, loaded.kind());
+
+        let loaded: RawValue = serde_json::from_str(
+            &serde_json::to_string(&RawValue::Float(1.0)).unwrap()).unwrap();
+        assert_eq!(RawValueKind::Float, loaded.kind());
+
+        // tag takes precedence over value
+        let loaded: RawValue = serde_json::from_str(r#"{"type":"float","value":1}"#).unwrap();
+        assert_eq!(RawValue::Float(1.0), loaded);
+        assert!(serde_json::from_str::<RawValue>(r#"{"type":"binary","value":1.0}"#).is_err());
+        assert!(serde_json::from_str::<RawValue>(r#"{"type":"float","value":true}"#).is_err());
+    }
+
+    #[test]
+    fn legacy_deserialization() {
+        for (json, expected) in [
+            (r#"{"Float":6.4}"#, RawValue::Float(6.4)),
+            (r#"{"Int":-3}"#, RawValue::Int(-3)),
+            (r#"{"PosInt8":3}"#, RawValue::PosInt8(3)),
+            (r#"{"Text":"abc"}"#, RawValue::from("abc")),
+            // bare values
+            ("true", RawValue::Binary(true)),
+            ("1", RawValue::PosInt(1)),
+            ("-1", RawValue::Int(-1)),
+            ("1.5", RawValue::Float(1.5)),
+            (r#""abc""#, RawValue::from("abc")),
+        ] {
+            let value: RawValue = serde_json::from_str(json).unwrap();
+            assert_eq!(expected.kind(), value.kind());
+            assert_eq!(expected, value);
+        }
+
+        assert!(serde_json::from_str::<RawValue>(r#"{"Unknown":1}"#).is_err());
+    }
+
+    #[test]
+    fn text() {
+        let text = RawValue::from("$GPGGA,123519");
+        assert_eq!("$GPGGA,123519", text.to_string());
+        assert_eq!(RawValueKind::Text, text.kind());
+        assert!(!text.is_numeric());
+        assert!(text.as_f64().is_none());
+
+        // text is only ordered against text
+        assert!(text.partial_cmp(&RawValue::Float(1.0)).is_none());
+        assert_eq!(Some(std::cmp::Ordering::Equal), text.partial_cmp(&text.clone()));
+        assert!(text < RawValue::from("ABC124"));
+        assert_ne!(text, RawValue::Int(0));
+
+        let json = serde_json::to_string(&text).unwrap();
+        assert_eq!(text, serde_json::from_str::<RawValue>(&json).unwrap());
+
+        assert_eq!(RawValue::from("ABC123"), RawValue::parse(" ABC123 ", RawValueKind::Text).unwrap());
+    }
+
+    #[should_panic]
+    #[test]
+    fn text_arithmetic() {
+        let _ = RawValue::from("a") + RawValue::from("b");
+    }
+
     #[should_panic]
     #[test]
     fn test_rawvalue_div_mismatched() {
@@ -306,4 +826,31 @@ mod tests {
         let b = RawValue::Float(7.0);
         let _ = a / b;
     }
+
+    #[test]
+    fn percent() {
+        assert_eq!(RawValue::Percent(0.0), RawValue::percent(-5.0));
+        assert_eq!(RawValue::Percent(100.0), RawValue::percent(250.0));
+        assert_eq!(RawValue::Percent(0.0), RawValue::percent(f32::NAN));
+        assert_eq!(RawValueKind::Percent, RawValue::percent(50.0).kind());
+        assert_eq!(Some(50.0), RawValue::percent(50.0).as_f64());
+        assert_eq!("50%", RawValue::percent(50.0).to_string());
+
+        // arithmetic saturates
+        assert_eq!(RawValue::percent(100.0), RawValue::percent(70.0) + RawValue::percent(70.0));
+        assert_eq!(RawValue::percent(0.0), RawValue::percent(30.0) - RawValue::percent(70.0));
+        assert_eq!(RawValue::percent(25.0), RawValue::percent(50.0) * RawValue::percent(50.0));
+        assert_eq!(RawValue::percent(100.0), RawValue::percent(80.0) / RawValue::percent(40.0));
+        assert!(RawValue::percent(20.0) < RawValue::percent(30.0));
+
+        assert_eq!(RawValue::percent(42.0), RawValue::parse(" 42 % ", RawValueKind::Percent).unwrap());
+        assert_eq!(RawValue::percent(100.0), RawValue::parse("101", RawValueKind::Percent).unwrap());
+
+        // deserialized values are clamped
+        let json = serde_json::to_string(&RawValue::Percent(150.0)).unwrap();
+        assert_eq!(r#"{"type":"percent","value":150.0}"#, json);
+        assert_eq!(RawValue::percent(100.0), serde_json::from_str::<RawValue>(&json).unwrap());
+
+        assert_eq!(Some(4.0), RawValue::percent(25.0).scale_percent(2.0, 10.0));
+    }
 }
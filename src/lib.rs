@@ -1,12 +1,15 @@
 extern crate chrono;
 extern crate custom_error;
 extern crate float_cmp;
+extern crate log;
 extern crate pid as ext_pid;
 
 pub mod action;
+pub mod clock;
 pub mod errors;
 pub mod helpers;
 pub mod io;
 pub mod name;
 pub mod settings;
 pub mod storage;
+pub mod units;
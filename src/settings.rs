@@ -1,3 +1,4 @@
+use chrono::Duration;
 use dotenv::dotenv;
 use std::env::var;
 use crate::storage::RootPath;
@@ -8,9 +9,78 @@ const VERSION: &str = "0.1.0";
 /// Default Filename Prefixes
 pub const LOG_FN_PREFIX: &str = "log_";
 
+/// Format version written to log files
+///
+/// # Versions
+///
+/// 1. `{"metadata": ..., "log": {timestamp: event, ...}}` (unversioned)
+/// 2. `{"version": 2, "metadata": ..., "events": [event, ...]}` with events in chronological order
+/// 3. Same layout as version 2, with values in tagged form (ie: `{"type": "float", "value": 1.5}`)
+///    as described by [`crate::io::RawValue`]
+pub const LOG_VERSION: u64 = 3;
+
+/// Filename (without filetype) of group audit log
+pub const AUDIT_FN: &str = "audit";
+
 /// Default for top-level directory
+///
+/// # See Also
+///
+/// - [`data_root()`] for resolution order
 pub const DATA_ROOT: &str = "sensd";
 
+/// Environment variable which overrides [`DATA_ROOT`]
+pub const DATA_ROOT_ENV: &str = "SENS_DATA_ROOT";
+
+/// Resolve default top-level directory
+///
+/// Precedence is as follows:
+///
+/// 1. Value of `SENS_DATA_ROOT` environment variable (see [`DATA_ROOT_ENV`]) if set and non-empty
+/// 2. Value of `DATA_ROOT` environment variable if set and non-empty. This is also read from
+///    ".env" by [`Settings::initialize()`].
+/// 3. [`DATA_ROOT`] constant
+///
+/// An explicit root given to [`crate::storage::Group`] (ie: [`crate::storage::Group::with_root()`])
+/// takes precedence over both since this is only used as a default.
+///
+/// # Returns
+///
+/// Path to top-level directory
+pub fn data_root() -> String {
+    resolve_data_root(var(DATA_ROOT_ENV).ok(), var("DATA_ROOT").ok())
+}
+
+/// Resolve top-level directory from values of [`DATA_ROOT_ENV`] and `DATA_ROOT`
+///
+/// Separated from [`data_root()`] so that resolution may be tested without mutating the
+/// process-wide environment.
+fn resolve_data_root(env: Option<String>, legacy: Option<String>) -> String {
+    env.filter(|root| !root.is_empty())
+        .or_else(|| legacy.filter(|root| !root.is_empty()))
+        .unwrap_or_else(|| String::from(DATA_ROOT))
+}
+
+/// Default polling interval in seconds
+pub const INTERVAL_SECS: i64 = 5;
+
+/// Number of consecutive failed reads before a device is considered faulted
+pub const FAULT_THRESHOLD: u32 = 3;
+
+/// Maximum delay in seconds between retries by [`crate::helpers::retry_with_backoff()`]
+///
+/// Equal to [`INTERVAL_SECS`] so that exponential backoff does not grow without bound.
+pub const MAX_BACKOFF_SECS: i64 = INTERVAL_SECS;
+
+/// Maximum number of worker threads used by [`crate::storage::Group::save_concurrent()`]
+pub const SAVE_THREADS: usize = 4;
+
+/// Number of polling intervals without a successful read before a device is considered stale
+pub const STALE_INTERVALS: i32 = 2;
+
+/// Default number of pending events buffered for queued subscribers of a [`crate::action::Publisher`]
+pub const QUEUE_CAPACITY: usize = 64;
+
 #[derive(PartialEq, Debug)]
 /// Global runtime settings
 pub struct Settings {
@@ -23,13 +93,26 @@ pub struct Settings {
     ///
     /// [`Settings::set_root()`] for mutability limitations.
     root_path: RootPath,
+
+    /// Polling interval used by [`crate::storage::Group`]
+    interval: Duration,
+
+    /// Events older than this are discarded by [`crate::storage::Group::enforce_retention()`]
+    max_log_age: Option<Duration>,
+
+    /// Maximum number of events retained per device log by
+    /// [`crate::storage::Group::enforce_retention()`]
+    max_log_events: Option<usize>,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
             version: VERSION.to_string(),
-            root_path: RootPath::from(DATA_ROOT),
+            root_path: RootPath::from(data_root()),
+            interval: Duration::seconds(INTERVAL_SECS),
+            max_log_age: None,
+            max_log_events: None,
         }
     }
 }
@@ -38,7 +121,10 @@ impl Settings {
     /// Read settings from ".env" file
     ///
     /// If values do not exist in ".env" file, then default values are used. However, ".env" is not
-    /// updated.
+    /// updated. Root path is resolved by [`data_root()`] once ".env" is loaded.
+    ///
+    /// Retention is read from "MAX_LOG_AGE" (in seconds) and "MAX_LOG_EVENTS". Retention is
+    /// disabled when either is missing or invalid.
     ///
     /// # Returns
     ///
@@ -46,11 +132,22 @@ impl Settings {
     pub fn initialize() -> Self {
         dotenv().ok();
         let version = var("VERSION").unwrap_or_else(|_| String::from(VERSION));
-        let data_root = var("DATA_ROOT").unwrap_or_else(|_| String::from(DATA_ROOT));
+        let data_root = data_root();
+        let interval = var("INTERVAL").ok()
+            .and_then(|secs| secs.parse().ok())
+            .unwrap_or(INTERVAL_SECS);
+        let max_log_age = var("MAX_LOG_AGE").ok()
+            .and_then(|secs| secs.parse().ok())
+            .map(Duration::seconds);
+        let max_log_events = var("MAX_LOG_EVENTS").ok()
+            .and_then(|count| count.parse().ok());
 
         Settings {
             version,
             root_path: RootPath::from(data_root),
+            interval: Duration::seconds(interval),
+            max_log_age,
+            max_log_events,
         }
     }
 
@@ -73,6 +170,57 @@ impl Settings {
         self.root_path.clone()
     }
 
+    /// Getter for `interval`
+    ///
+    /// # Returns
+    ///
+    /// Polling interval. This is read from "INTERVAL" (in seconds) by [`Settings::initialize()`].
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Setter for `interval`
+    ///
+    /// # Parameters
+    ///
+    /// - `interval`: New polling interval
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval
+    }
+
+    /// Getter for `max_log_age`
+    ///
+    /// # Returns
+    ///
+    /// Maximum age of logged events, or `None` if events are not discarded by age
+    pub fn max_log_age(&self) -> Option<Duration> {
+        self.max_log_age
+    }
+
+    /// Getter for `max_log_events`
+    ///
+    /// # Returns
+    ///
+    /// Maximum number of events per device log, or `None` if events are not discarded by count
+    pub fn max_log_events(&self) -> Option<usize> {
+        self.max_log_events
+    }
+
+    /// Setter for log retention policy
+    ///
+    /// # Parameters
+    ///
+    /// - `max_age`: Events older than this are discarded
+    /// - `max_events`: Maximum number of events retained per device log
+    ///
+    /// # See Also
+    ///
+    /// - [`crate::storage::Group::enforce_retention()`] for applying retention
+    pub fn set_retention(&mut self, max_age: Option<Duration>, max_events: Option<usize>) {
+        self.max_log_age = max_age;
+        self.max_log_events = max_events;
+    }
+
     /// Setter for `root_path`.
     ///
     /// This method can only be called *before* initialization
@@ -99,9 +247,27 @@ impl Settings {
 
 #[cfg(test)]
 mod tests {
-    use crate::settings::Settings;
+    use crate::settings::{resolve_data_root, Settings, DATA_ROOT};
     use crate::storage::RootPath;
 
+    #[test]
+    fn data_root_env_override() {
+        const PATH: &str = "/tmp/sensd_tests/env_root";
+
+        const LEGACY: &str = "/tmp/sensd_tests/legacy_root";
+
+        assert_eq!(PATH, resolve_data_root(Some(String::from(PATH)), None));
+
+        // `SENS_DATA_ROOT` takes precedence over `DATA_ROOT`
+        assert_eq!(PATH, resolve_data_root(Some(String::from(PATH)), Some(String::from(LEGACY))));
+        assert_eq!(LEGACY, resolve_data_root(None, Some(String::from(LEGACY))));
+        assert_eq!(LEGACY, resolve_data_root(Some(String::new()), Some(String::from(LEGACY))));
+
+        // missing or empty value falls back to default
+        assert_eq!(DATA_ROOT, resolve_data_root(None, None));
+        assert_eq!(DATA_ROOT, resolve_data_root(Some(String::new()), Some(String::new())));
+    }
+
     #[test]
     /// Asserts that `Settings::set_root()` properly converts using `Into<_>`
     fn set_root_into() {
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::clock;
+use crate::io::{IdType, IODirection};
+
+/// Configuration change recorded by [`AuditEvent`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditAction {
+    /// Device was added to [`crate::storage::Group`]
+    Added,
+    /// Device was removed from [`crate::storage::Group`]
+    Removed,
+}
+
+/// Timestamped record of a change to the devices of a [`crate::storage::Group`]
+///
+/// Unlike [`crate::io::IOEvent`] which records data from a single device, audit events record how
+/// the roster of a group has changed over time.
+///
+/// # See Also
+///
+/// - [`crate::storage::Group::audit_log()`] for accessing recorded events
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub timestamp: DateTime<Utc>,
+    pub action: AuditAction,
+
+    /// Direction of affected device
+    pub direction: IODirection,
+
+    /// Id of affected device
+    pub id: IdType,
+
+    /// Name of affected device at time of change
+    pub name: String,
+}
+
+impl AuditEvent {
+    /// Constructor for [`AuditEvent`] with internally generated `timestamp`
+    ///
+    /// # Parameters
+    ///
+    /// - `action`: Type of change
+    /// - `direction`: Direction of affected device
+    /// - `id`: Id of affected device
+    /// - `name`: Name of affected device
+    pub fn new<N>(action: AuditAction, direction: IODirection, id: IdType, name: N) -> Self
+    where
+        N: Into<String>
+    {
+        Self {
+            timestamp: clock::now(),
+            action,
+            direction,
+            id,
+            name: name.into(),
+        }
+    }
+}
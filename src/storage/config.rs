@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+use crate::io::DeviceConfig;
+
+/// Serializable configuration of a [`crate::storage::Group`]
+///
+/// Only static configuration is stored. Like [`DeviceConfig`], low-level commands are not
+/// included and must be attached to devices after rebuilding a group.
+///
+/// # See Also
+///
+/// - [`crate::storage::Group::to_config()`] for generating configuration from a group
+/// - [`crate::storage::Group::from_config()`] for rebuilding a group
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct GroupConfig {
+    /// Name of group
+    pub name: String,
+
+    /// Polling interval in milliseconds
+    pub interval_ms: i64,
+
+    /// Configuration of input devices ordered by id
+    #[serde(default)]
+    pub inputs: Vec<DeviceConfig>,
+
+    /// Configuration of output devices ordered by id
+    #[serde(default)]
+    pub outputs: Vec<DeviceConfig>,
+
+    /// Configuration of nested groups
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subgroups: Vec<GroupConfig>,
+}
+
+/// Difference between a running [`crate::storage::Group`] and a [`GroupConfig`]
+///
+/// Generated by [`crate::storage::Group::diff_config()`]. Devices are matched by direction and id,
+/// and subgroups are matched by name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigChange {
+    /// Name of group differs
+    Name { current: String, other: String },
+
+    /// Polling interval in milliseconds differs
+    Interval { current: i64, other: i64 },
+
+    /// Device only exists in other configuration
+    Added(DeviceConfig),
+
+    /// Device only exists in running group
+    Removed(DeviceConfig),
+
+    /// Device exists in both, but configuration differs
+    Modified { current: DeviceConfig, other: DeviceConfig },
+
+    /// Subgroup only exists in other configuration
+    SubgroupAdded(GroupConfig),
+
+    /// Subgroup only exists in running group
+    SubgroupRemoved(GroupConfig),
+
+    /// Changes within a subgroup which exists in both
+    Subgroup { name: String, changes: Vec<ConfigChange> },
+}
+
+impl GroupConfig {
+    /// Compare against another configuration
+    ///
+    /// # Parameters
+    ///
+    /// - `other`: Configuration to compare against
+    ///
+    /// # Returns
+    ///
+    /// Changes required to transform `self` into `other`. Empty if both are equivalent.
+    pub fn diff(&self, other: &GroupConfig) -> Vec<ConfigChange> {
+        fn devices(current: &[DeviceConfig], other: &[DeviceConfig], changes: &mut Vec<ConfigChange>) {
+            for device in current.iter() {
+                match other.iter().find(|o| o.metadata.id == device.metadata.id) {
+                    None => changes.push(ConfigChange::Removed(device.clone())),
+                    Some(o) if o != device => changes.push(ConfigChange::Modified {
+                        current: device.clone(),
+                        other: o.clone(),
+                    }),
+                    _ => (),
+                }
+            }
+            for device in other.iter() {
+                if !current.iter().any(|c| c.metadata.id == device.metadata.id) {
+                    changes.push(ConfigChange::Added(device.clone()));
+                }
+            }
+        }
+
+        let mut changes = Vec::new();
+
+        if self.name != other.name {
+            changes.push(ConfigChange::Name { current: self.name.clone(), other: other.name.clone() });
+        }
+        if self.interval_ms != other.interval_ms {
+            changes.push(ConfigChange::Interval { current: self.interval_ms, other: other.interval_ms });
+        }
+
+        devices(&self.inputs, &other.inputs, &mut changes);
+        devices(&self.outputs, &other.outputs, &mut changes);
+
+        for subgroup in self.subgroups.iter() {
+            match other.subgroups.iter().find(|o| o.name == subgroup.name) {
+                None => changes.push(ConfigChange::SubgroupRemoved(subgroup.clone())),
+                Some(o) => {
+                    let diff = subgroup.diff(o);
+                    if !diff.is_empty() {
+                        changes.push(ConfigChange::Subgroup { name: subgroup.name.clone(), changes: diff });
+                    }
+                }
+            }
+        }
+        for subgroup in other.subgroups.iter() {
+            if !self.subgroups.iter().any(|c| c.name == subgroup.name) {
+                changes.push(ConfigChange::SubgroupAdded(subgroup.clone()));
+            }
+        }
+
+        changes
+    }
+}
@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::action::Routine;
+use crate::io::{DeviceConfig, IOEvent, RawValue};
+use crate::storage::GroupHealth;
+
+/// Snapshot of the runtime state of a [`crate::storage::Group`] for diagnostics
+///
+/// Unlike [`crate::storage::GroupConfig`], volatile state such as the last event of each device
+/// and pending routines is included. Devices which are locked while the snapshot is taken are
+/// listed in `unavailable` instead of being described.
+///
+/// # See Also
+///
+/// - [`crate::storage::Group::dump_state()`] for generating a snapshot as JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateDump {
+    /// Name of group
+    pub name: String,
+
+    /// Time at which snapshot was taken
+    pub generated: DateTime<Utc>,
+
+    /// Polling interval in milliseconds
+    pub interval_ms: i64,
+
+    /// Whether polling is paused by [`crate::storage::Group::pause()`]
+    pub paused: bool,
+
+    /// Health report as returned by [`crate::storage::Group::health()`]
+    pub health: GroupHealth,
+
+    /// State of input devices ordered by id
+    pub inputs: Vec<DeviceState>,
+
+    /// State of output devices ordered by id
+    pub outputs: Vec<DeviceState>,
+
+    /// Description of every device or routine handler which could not be locked
+    pub unavailable: Vec<String>,
+
+    /// Snapshots of nested groups
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subgroups: Vec<StateDump>,
+}
+
+/// State of a single device within a [`StateDump`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceState {
+    /// Static configuration, including metadata
+    pub config: DeviceConfig,
+
+    /// Cached state of device
+    pub state: Option<RawValue>,
+
+    /// Most recent logged event
+    pub last_event: Option<IOEvent>,
+
+    /// Routines scheduled by the publisher of an input device which have not been executed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routines: Vec<RoutineState>,
+}
+
+/// Pending [`Routine`] within a [`StateDump`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoutineState {
+    /// Scheduled time of execution
+    pub timestamp: DateTime<Utc>,
+
+    /// Value to be written
+    pub value: RawValue,
+
+    /// Execution priority
+    pub priority: i32,
+
+    /// Number of failed executions
+    pub attempts: u32,
+}
+
+impl From<&Routine> for RoutineState {
+    fn from(routine: &Routine) -> Self {
+        Self {
+            timestamp: routine.timestamp(),
+            value: routine.value().clone(),
+            priority: routine.priority(),
+            attempts: routine.attempts(),
+        }
+    }
+}
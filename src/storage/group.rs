@@ -1,13 +1,37 @@
-use crate::errors::{DeviceError, ErrorType};
-use crate::helpers::check_results;
-use crate::io::{Device, DeviceContainer, DeviceGetters, IdType, Input, Output};
-use crate::settings::DATA_ROOT;
-use crate::storage::{Directory, Persistent, RootDirectory, RootPath};
+use crate::action::{BoxedAction, Publisher};
+use crate::clock;
+use crate::errors::{ContainerError, DeviceError, ErrorType, FilesystemError};
+use crate::helpers::{check_results, retry_with_backoff, set_permissions_recursive, writable_or_create, writable_or_create_with_mode, Def};
+use crate::io::{Device, DeviceConfig, DeviceContainer, DeviceGetters, DeviceMetadata, DeviceType, IdType, IODirection, IOEvent, IOKind, Input, Output, RawValue};
+use crate::settings::{data_root, Settings, AUDIT_FN, FAULT_THRESHOLD, INTERVAL_SECS, LOG_FN_PREFIX, SAVE_THREADS, STALE_INTERVALS};
+use crate::storage::{AuditAction, AuditEvent, BoxedSink, DeviceState, RoutineState, StateDump, Chronicle, ConfigChange, Directory, Document, FaultObserver, GroupConfig, GroupHealth, MergedEvent, Observers, Persistent, PollObserver, PollStartObserver, ReadLatency, RoutineObserver, RootDirectory, RootPath, Watchdog, FILETYPE};
 
 use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Instant;
 use crate::name::Name;
 
+/// Behavior of [`Group::poll()`] when caller has fallen more than one interval behind
+///
+/// The default is [`PollPolicy::Skip`] since it prevents a delayed caller from triggering a burst
+/// of back-to-back reads.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum PollPolicy {
+    /// Read once, then resync schedule to the most recent missed interval
+    #[default]
+    Skip,
+
+    /// Read once per call, advancing schedule by a single interval. Subsequent calls execute
+    /// immediately until all missed intervals have been read.
+    CatchUp,
+}
+
 /// High-level container to manage multiple [`Device`] objects, logging, and
 /// actions.
 ///
@@ -53,6 +77,23 @@ use crate::name::Name;
 /// group.push_output(output);
 /// ```
 ///
+/// ## Subgroups
+///
+/// Devices may be organized into logical zones by nesting [`Group`]s with [`Group::push_subgroup()`].
+/// Each subgroup is polled at its own interval by [`Group::poll()`], and is included by
+/// [`Group::attempt_routines()`], [`Group::save()`] and [`Group::load()`]. Subgroup directories are
+/// located within the parent directory:
+///
+/// ```
+/// use sensd::storage::{Directory, Group};
+///
+/// let mut controller = Group::with_root("controller", "/tmp/root_dir/");
+/// controller.push_subgroup(Group::new("veg room"));
+///
+/// assert_eq!("/tmp/root_dir/controller/veg room",
+///            controller.subgroups()[0].full_path().to_str().unwrap());
+/// ```
+///
 /// ## Main Operation / Polling
 ///
 /// [`Group::poll()`] and [`Group::attempt_routines()`] are the primary callables for function. Both functions are
@@ -75,11 +116,53 @@ pub struct Group {
     /// Buffer to store time of the last successful poll.
     last_execution: DateTime<Utc>,
 
+    /// Time of the last poll where all reads succeeded
+    last_success: Option<DateTime<Utc>>,
+
+    /// Read durations of input devices recorded by [`Group::poll()`]
+    latencies: HashMap<IdType, ReadLatency>,
+
+    /// Time each input with its own poll interval was last polled
+    last_polled: HashMap<IdType, DateTime<Utc>>,
+
     /// Immutable storage of runtime settings
     root: RootPath,
 
     interval: Duration,
 
+    /// Handling of missed polling intervals
+    policy: PollPolicy,
+
+    /// Schedule polls at multiples of `interval` since the Unix epoch
+    align: bool,
+
+    /// History of devices added or removed
+    audit: Vec<AuditEvent>,
+
+    /// Nested groups which are polled, saved, and loaded along with this group
+    subgroups: Vec<Group>,
+
+    /// Root path was explicitly set and should not be inherited from a parent group
+    explicit_root: bool,
+
+    /// Maximum age and maximum number of events applied to device logs
+    retention: (Option<Duration>, Option<usize>),
+
+    /// Lifecycle callbacks
+    observers: Observers,
+
+    /// Additional destinations for polled events
+    sinks: Vec<BoxedSink>,
+
+    /// Unix permissions applied to group directory and saved files
+    mode: Option<u32>,
+
+    /// Time at which polling and routines were paused by [`Group::pause()`]
+    paused: Option<DateTime<Utc>>,
+
+    /// Maximum number of retries of a failed device save and delay before first retry
+    save_retry: Option<(u32, Duration)>,
+
     pub inputs: DeviceContainer<IdType, Input>,
     pub outputs: DeviceContainer<IdType, Output>,
 }
@@ -87,44 +170,272 @@ pub struct Group {
 impl Group {
     /// Primary callable to iterate through input device container once.
     ///
-    /// [`Input::read()`] is called once on each input device at a frequency of
+    /// [`Input::read_all()`] is called once on each input device at a frequency of
     /// [`Group::interval()`]. Generated [`crate::io::IOEvent`] instances are
-    /// handled by [`Input::read()`].
+    /// handled by [`Input::read_all()`], which yields one event per kind for composite sensors.
+    ///
+    /// Inputs with their own interval set by [`Input::set_poll_interval()`] are instead read only
+    /// once that interval has elapsed since they were last polled. Therefore, a poll may be
+    /// executed to read such inputs before the group interval has elapsed, in which case inputs
+    /// following the group interval are not read.
     ///
     /// Failure of any individual read does not halt execution. Instead, errors
     /// from [`Input::read()`] are returned as a [`Vec`].
     ///
+    /// If more than one interval has passed since the last scheduled poll, behavior is
+    /// determined by [`Group::poll_policy()`].
+    ///
+    /// Subgroups are polled recursively according to their own intervals.
+    ///
+    /// While paused by [`Group::pause()`], no devices of this group or its subgroups are read.
+    ///
     /// # Returns
     ///
     /// A `Result` containing:
     ///
-    /// - `Ok` when poll has been executed. `Ok` value will contain any errors
-    ///   that arose.
-    /// - `Err` when poll was not executed
+    /// - `Ok` when poll has been executed by this group or any subgroup. `Ok` value will contain
+    ///   any errors that arose. `Ok` value is empty when group is paused.
+    /// - `Err` when no poll was executed
     pub fn poll(&mut self) -> Result<Vec<DeviceError>, ()> {
         let mut errors = Vec::new();
-        let next_execution = self.last_execution + *self.interval();
+        self.poll_with(&mut |result| {
+            if let Err(e) = result {
+                errors.push(e);
+            }
+        }, false)?;
+        Ok(errors)
+    }
+
+    /// Poll devices, sending each result through a channel as soon as it is read
+    ///
+    /// This is the streaming counterpart of [`Group::poll()`] and follows the same scheduling
+    /// rules. Rather than collecting errors, every successful [`IOEvent`] and every error is sent
+    /// through `tx` immediately after the device is read, so that a receiver can react to each
+    /// device without waiting for the slowest one.
+    ///
+    /// # Backpressure
+    ///
+    /// [`Sender`] is unbounded, therefore sending never blocks polling and results queue until
+    /// they are received. If the receiver has been dropped, results are discarded and polling
+    /// continues.
+    ///
+    /// Since results are [`Send`], the receiver may be moved to another thread (ie: a UI thread).
+    ///
+    /// # Parameters
+    ///
+    /// - `tx`: Channel which receives result of every read
+    ///
+    /// # Returns
+    ///
+    /// `true` when poll has been executed by this group or any subgroup, or when group is paused.
+    /// `false` when no poll was executed.
+    pub fn poll_streaming(&mut self, tx: &Sender<Result<IOEvent, DeviceError>>) -> bool {
+        self.poll_with(&mut |result| {
+            let _ = tx.send(result);
+        }, false).is_ok()
+    }
+
+    /// Read every input device immediately, regardless of schedule
+    ///
+    /// Unlike [`Group::poll()`], the interval is ignored so that every input of this group and all
+    /// subgroups is read exactly once. This is intended for manual triggering and tests. Schedule
+    /// is restarted from the current time, therefore the next scheduled poll is due one interval
+    /// later. Nothing is read while paused by [`Group::pause()`].
+    ///
+    /// # Returns
+    ///
+    /// Result of every read, in the order devices were read
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use sensd::io::{Device, Input};
+    /// use sensd::storage::Group;
+    ///
+    /// let mut group = Group::with_interval("", Duration::hours(1));
+    /// group.push_input(Input::new("sensor", 0, None));
+    ///
+    /// assert_eq!(1, group.poll_once_now().len());
+    /// assert_eq!(1, group.poll_once_now().len());
+    ///
+    /// // scheduled poll is not due
+    /// assert!(group.poll().is_err());
+    /// ```
+    pub fn poll_once_now(&mut self) -> Vec<Result<IOEvent, ErrorType>> {
+        let mut results = Vec::new();
+        let _ = self.poll_with(&mut |result| {
+            results.push(result.map_err(|e| Box::new(e) as ErrorType));
+        }, true);
+        results
+    }
+
+    /// Poll subgroups then devices, passing the result of every read to `handler`
+    ///
+    /// When `force` is set, all devices are read regardless of schedule.
+    fn poll_with(&mut self, handler: &mut dyn FnMut(Result<IOEvent, DeviceError>), force: bool) -> Result<(), ()> {
+        if self.is_paused() {
+            return Ok(());
+        }
+
+        let mut polled = false;
+
+        for subgroup in self.subgroups.iter_mut() {
+            if subgroup.poll_with(handler, force).is_ok() {
+                polled = true;
+            }
+        }
+
+        match self.poll_devices(handler, force) {
+            Ok(()) => Ok(()),
+            Err(_) if polled => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Read all input devices which are due, ignoring subgroups
+    ///
+    /// Inputs without their own poll interval are due when the group is due. Inputs with their own
+    /// interval are due once it has elapsed since they were last polled. When `force` is set, all
+    /// inputs are due and schedule is restarted from the current time.
+    fn poll_devices(&mut self, handler: &mut dyn FnMut(Result<IOEvent, DeviceError>), force: bool) -> Result<(), ()> {
+        let mut failed = false;
+        let now = clock::now();
+        let next_execution = self.next_execution();
+        let scheduled = force || next_execution <= now;
+
+        let due: Vec<(IdType, bool)> = self.inputs.iter_mut()
+            .filter_map(|(id, input)| {
+                let interval = match input.get_mut() {
+                    Some(device) => device.poll_interval(),
+                    None => input.try_lock().unwrap().poll_interval(),
+                };
+                let due = match interval {
+                    _ if force => true,
+                    Some(interval) => self.last_polled.get(id).is_none_or(|last| *last + interval <= now),
+                    None => scheduled,
+                };
+                due.then_some((*id, interval.is_some()))
+            })
+            .collect();
+
+        if !scheduled && due.is_empty() {
+            return Err(());
+        }
+
+        self.observers.poll_start();
+
+        let observed = !self.observers.poll_complete.is_empty();
+        let mut results: Vec<Result<IOEvent, ErrorType>> = Vec::new();
+
+        for (id, independent) in due {
+            let input = self.inputs.get_mut(&id).unwrap();
+            let start = Instant::now();
+
+            // avoid locking when device is not shared
+            let (result, failures) = match input.get_mut() {
+                Some(device) => (device.read_all(), device.consecutive_failures()),
+                None => {
+                    let mut device = input.try_lock().unwrap();
+                    (device.read_all(), device.consecutive_failures())
+                }
+            };
+
+            let elapsed = Duration::from_std(start.elapsed()).unwrap_or(Duration::max_value());
+            self.latencies.entry(id)
+                .and_modify(|latency| latency.record(elapsed))
+                .or_insert_with(|| ReadLatency::new(elapsed));
+            if independent {
+                self.last_polled.insert(id, now);
+            }
+
+            // only notify once, when device crosses the threshold
+            if result.is_err() && failures == FAULT_THRESHOLD {
+                self.observers.device_fault(id);
+            }
+
+            match result {
+                // composite sensors yield multiple events
+                Ok(events) => for event in events {
+                    for sink in self.sinks.iter_mut() {
+                        sink.consume(&event);
+                    }
+                    if observed {
+                        results.push(Ok(event.clone()));
+                    }
+                    handler(Ok(event));
+                },
+                Err(e) => {
+                    if observed {
+                        results.push(Err(e.to_string().into()));
+                    }
+                    failed = true;
+                    handler(Err(e));
+                }
+            }
+        }
+        for sink in self.sinks.iter_mut() {
+            if let Err(e) = sink.flush() {
+                log::warn!("Could not flush sink of '{}': {}", self.name, e);
+            }
+        }
+
+        if force {
+            self.last_execution = now;
+        } else if scheduled {
+            self.last_execution = self.next_schedule(next_execution);
+        }
+        if !failed {
+            self.last_success = Some(clock::now());
+        }
 
-        if next_execution <= Utc::now() {
-            for input in self.inputs.values_mut() {
-                let mut binding = input.try_lock().unwrap();
-                let result = binding.read();
+        self.observers.poll_complete(&results);
+        Ok(())
+    }
+
+    /// Time at which next poll is due
+    ///
+    /// # Returns
+    ///
+    /// `last_execution + interval`, or when aligned, the first interval boundary after
+    /// `last_execution`
+    fn next_execution(&self) -> DateTime<Utc> {
+        if self.align {
+            let since_epoch = self.last_execution.timestamp_nanos();
+            if let Some(interval) = self.interval.num_nanoseconds().filter(|interval| *interval > 0) {
+                let boundary = self.last_execution - Duration::nanoseconds(since_epoch.rem_euclid(interval));
+                return boundary + self.interval;
+            }
+        }
+        self.last_execution + self.interval
+    }
 
-                // Add errors to array
-                if result.is_err() {
-                    errors.push(result.err().unwrap());
+    /// Calculate value of `last_execution` after a poll scheduled for `scheduled`
+    ///
+    /// # Returns
+    ///
+    /// - `scheduled` when policy is [`PollPolicy::CatchUp`]
+    /// - Most recent interval boundary that is not in the future when policy is [`PollPolicy::Skip`]
+    fn next_schedule(&self, scheduled: DateTime<Utc>) -> DateTime<Utc> {
+        match self.policy {
+            PollPolicy::CatchUp => scheduled,
+            PollPolicy::Skip => {
+                let now = clock::now();
+                let behind = (now - scheduled).num_nanoseconds();
+                match (behind, self.interval.num_nanoseconds()) {
+                    (Some(behind), Some(interval)) if interval > 0 => {
+                        scheduled + Duration::nanoseconds(behind - behind % interval)
+                    }
+                    _ => now,
                 }
             }
-            self.last_execution = next_execution;
-            Ok(errors)
-        } else {
-            Err(())
         }
     }
 
     /// Primary constructor.
     ///
-    /// [`Group::set_root()`] or [`Group::set_root_ref()`] should be used to set root path
+    /// [`Group::set_root()`] or [`Group::set_root_ref()`] should be used to set root path.
+    /// Otherwise, root path is resolved by [`data_root()`] when [`Group`] is constructed.
     ///
     /// # Parameters
     ///
@@ -149,19 +460,33 @@ impl Group {
     where
         N: Into<String>
     {
-        let interval = Duration::seconds(5);
-        let last_execution = Utc::now() - interval;
+        let interval = Duration::seconds(INTERVAL_SECS);
+        let last_execution = clock::now() - interval;
 
         let inputs = <DeviceContainer<IdType, Input>>::default();
         let outputs = <DeviceContainer<IdType, Output>>::default();
 
-        let root = RootPath::from(DATA_ROOT);
+        let root = RootPath::from(data_root());
 
         Self {
             name: name.into(),
             interval,
             root,
             last_execution,
+            last_success: None,
+            latencies: HashMap::new(),
+            last_polled: HashMap::new(),
+            policy: PollPolicy::default(),
+            align: false,
+            audit: Vec::new(),
+            subgroups: Vec::new(),
+            explicit_root: false,
+            retention: (None, None),
+            observers: Observers::default(),
+            sinks: Vec::new(),
+            mode: None,
+            paused: None,
+            save_retry: None,
             inputs,
             outputs,
         }
@@ -201,12 +526,78 @@ impl Group {
         group
     }
 
+    /// Alternate constructor which preallocates device containers
+    ///
+    /// Intended for large fixed rosters, so that containers are not reallocated as devices are
+    /// pushed during startup.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of group used for directory/file naming.
+    /// - `capacity`: Expected number of devices in each of the input and output containers
+    ///
+    /// # Returns
+    ///
+    /// Initialized [`Group`] with `name`, default root directory, and empty containers
+    pub fn with_capacity<N>(name: N, capacity: usize) -> Self
+        where
+            N: Into<String>,
+    {
+        let mut group = Self::new(name.into());
+        group.inputs = DeviceContainer::with_capacity(capacity);
+        group.outputs = DeviceContainer::with_capacity(capacity);
+
+        group
+    }
+
     pub fn with_interval<N>(name: N, interval: Duration) -> Self
         where
             N: Into<String>,
     {
         let mut group = Self::new(name.into());
         group.set_interval(interval);
+        // first poll is immediately due regardless of interval
+        group.last_execution = clock::now() - interval;
+
+        group
+    }
+
+    /// Alternate constructor which adopts `root` and `interval` from [`Settings`]
+    ///
+    /// The root path is shared with `settings`, therefore [`Settings::set_root()`] panics while
+    /// the returned [`Group`] exists.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of group used for directory/file naming.
+    /// - `settings`: Global runtime settings
+    ///
+    /// # Returns
+    ///
+    /// Initialized [`Group`] with `name`, `root` and `interval` from `settings`, and empty containers
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use sensd::settings::Settings;
+    /// use sensd::storage::{Group, RootDirectory};
+    ///
+    /// let mut settings = Settings::default();
+    /// settings.set_interval(Duration::seconds(30));
+    ///
+    /// let group = Group::with_settings("main", &settings);
+    ///
+    /// assert_eq!(settings.interval(), *group.interval());
+    /// assert_eq!(settings.root_path(), group.root_dir());
+    /// ```
+    pub fn with_settings<N>(name: N, settings: &Settings) -> Self
+        where
+            N: Into<String>,
+    {
+        let mut group = Self::with_interval(name, settings.interval());
+        group.root = settings.root_path();
+        group.retention = (settings.max_log_age(), settings.max_log_events());
 
         group
     }
@@ -238,15 +629,39 @@ impl Group {
     /// ```
     pub fn push_input(&mut self, mut device: Input) -> &mut Self {
         let id = device.id();
+        let event = AuditEvent::new(AuditAction::Added, IODirection::In, id, device.name());
 
         device.set_parent_dir_ref(self.full_path());
+        apply_retention(&device, self.retention);
 
         self.inputs.insert(id, device.into_deferred())
             .unwrap();
+        self.audit.push(event);
 
         self
     }
 
+    /// Remove [`Input`] from internal collection
+    ///
+    /// An [`AuditEvent`] is recorded if device exists.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: Id of device to remove
+    ///
+    /// # Returns
+    ///
+    /// Removed device, or `None` if no input exists with `id`
+    pub fn remove_input(&mut self, id: IdType) -> Option<Def<Input>> {
+        let device = self.inputs.remove(&id)?;
+        let name = device.try_lock().map(|device| device.name().clone()).unwrap_or_default();
+        self.audit.push(AuditEvent::new(AuditAction::Removed, IODirection::In, id, name));
+        self.latencies.remove(&id);
+        self.last_polled.remove(&id);
+
+        Some(device)
+    }
+
     /// Store [`Output`] in internal collection
     ///
     /// [`Device::set_root()`] is called to pass settings to device.
@@ -270,239 +685,3128 @@ impl Group {
     /// ```
     pub fn push_output(&mut self, mut device: Output) -> &mut Self {
         let id = device.id();
+        let event = AuditEvent::new(AuditAction::Added, IODirection::Out, id, device.name());
 
         device.set_parent_dir_ref(self.full_path());
+        apply_retention(&device, self.retention);
 
         self.outputs.insert(id, device.into_deferred())
             .unwrap();
+        self.audit.push(event);
 
         self
     }
 
-    pub fn attempt_routines(&self) {
-        for device in self.inputs.values() {
-            let mut binding = device.try_lock().unwrap();
-            if let Some(publisher) = binding.publisher_mut() {
-                publisher.attempt_routines()
-            }
-        }
-    }
-
-    //
-    // Getters
-
-
-    #[inline]
-    /// Getter for `interval`
+    /// Remove [`Output`] from internal collection
     ///
-    /// # Notes
+    /// An [`AuditEvent`] is recorded if device exists.
     ///
-    /// Since this is frequently used in iterators and polling, this
-    /// method is marked inline to avoiding jumping in memory.
+    /// # Parameters
+    ///
+    /// - `id`: Id of device to remove
     ///
     /// # Returns
     ///
-    /// Immutable reference to `interval`
-    pub fn interval(&self) -> &Duration {
-        &self.interval
+    /// Removed device, or `None` if no output exists with `id`
+    pub fn remove_output(&mut self, id: IdType) -> Option<Def<Output>> {
+        let device = self.outputs.remove(&id)?;
+        let name = device.try_lock().map(|device| device.name().clone()).unwrap_or_default();
+        self.audit.push(AuditEvent::new(AuditAction::Removed, IODirection::Out, id, name));
+
+        Some(device)
     }
 
-    /// Setter for `interval`
+    /// Rename a device and move its log file accordingly
+    ///
+    /// Since device directories and log filenames are derived from the device name, an existing
+    /// log file is moved to the path derived from `new_name`. If moving the file fails, the name
+    /// change is rolled back so that in-memory and on-disk state do not diverge.
     ///
     /// # Parameters
     ///
-    /// - `interval`: any value that can be coerced into [`Duration`]
-    pub fn set_interval(&mut self, interval: Duration) {
-        self.interval = interval
-    }
-}
-
-/// Only save and load log data since [`Group`] is statically initialized
-/// If `&None` is given to either methods, then current directory is used.
-impl Persistent for Group {
-    /// Save all device logs
+    /// - `id`: Id of device to rename. Both inputs and outputs are searched.
+    /// - `new_name`: New name of device
     ///
-    /// # Errors
+    /// # Returns
     ///
-    /// Returns an error if any single save fails. However, failure is silent and
-    /// does not prevent saving other device logs.
+    /// A `Result` containing:
     ///
-    /// # Panics
+    /// - `Ok` when device was renamed
+    /// - `Err` with [`ContainerError::KeyMissing`] if no device exists with `id`, with
+    ///   [`ContainerError::KeyAmbiguous`] if both an input and an output have `id`, or with the
+    ///   filesystem error which caused the rename to be rolled back
     ///
-    /// Panics when any single input or output device cannot be locked.
+    /// # See Also
     ///
-    /// # Returns
+    /// - [`Group::find_device()`] for searching devices by id
+    pub fn rename_device<N>(&mut self, id: IdType, new_name: N) -> Result<(), ErrorType>
+    where
+        N: Into<String>
+    {
+        match (self.inputs.get(&id), self.outputs.get(&id)) {
+            (Some(input), None) => rename(input, new_name.into()),
+            (None, Some(output)) => rename(output, new_name.into()),
+            (Some(_), Some(_)) => Err(Box::new(ContainerError::KeyAmbiguous {key: id.to_string()})),
+            (None, None) => Err(Box::new(ContainerError::KeyMissing {key: id.to_string()})),
+        }
+    }
+
+    /// Setter for log retention policy
     ///
-    /// A [`Result`] containing:
+    /// Policy is propagated to the logs of all current devices, and is applied to devices added
+    /// later. Subgroups are not affected. Devices or logs that are currently locked are skipped and
+    /// retain their previous policy.
     ///
-    /// - `Ok` that is empty when saving occurred without error.
-    /// - `Err` containing the first error stored. There may be more errors that were
-    ///   not returned. An error occurring does not halt saving other logs.
-    fn save(&self) -> Result<(), ErrorType> {
-        let mut results = Vec::new();
+    /// # Parameters
+    ///
+    /// - `max_age`: Events older than this are discarded
+    /// - `max_events`: Maximum number of events retained per device log
+    ///
+    /// # See Also
+    ///
+    /// - [`Group::with_settings()`] which adopts retention policy from [`Settings`]
+    pub fn set_retention(&mut self, max_age: Option<Duration>, max_events: Option<usize>) {
+        self.retention = (max_age, max_events);
 
         for device in self.inputs.values() {
-            let binding = device.try_lock().expect("Could not lock input");
-            results.push(
-                binding.save());
+            apply_deferred_retention(device, self.retention);
         }
-
         for device in self.outputs.values() {
-            let binding = device.try_lock().expect("Could not lock output");
-            results.push(
-                binding.save());
+            apply_deferred_retention(device, self.retention);
         }
+    }
 
-        check_results(&results)
+    /// Getter for log retention policy
+    ///
+    /// # Returns
+    ///
+    /// Maximum age and maximum number of events applied to device logs
+    pub fn retention(&self) -> (Option<Duration>, Option<usize>) {
+        self.retention
     }
 
-    /// Load all device logs
+    /// Discard logged events according to retention policy of each device log
     ///
-    /// # Errors
+    /// This is intended to be called periodically by the driving loop to bound memory and disk
+    /// usage. Subgroups are included. Devices or logs that are currently locked are skipped.
     ///
-    /// Returns an error if any single load fails. However, failure is silent and does not prevent
-    /// loading other device logs.
+    /// # Returns
     ///
-    /// # Panics
+    /// Total number of events removed
+    pub fn enforce_retention(&self) -> usize {
+        fn enforce<D: Device>(device: &Def<D>) -> usize {
+            device.try_lock().ok()
+                .and_then(|device| device.log())
+                .and_then(|log| log.try_lock().ok().map(|mut log| log.enforce_retention()))
+                .unwrap_or_default()
+        }
+
+        let mut removed = 0;
+        removed += self.inputs.values().map(enforce).sum::<usize>();
+        removed += self.outputs.values().map(enforce).sum::<usize>();
+        removed += self.subgroups.iter().map(Group::enforce_retention).sum::<usize>();
+        removed
+    }
+
+    /// Discard all events held in memory by device logs, including those of subgroups
     ///
-    /// Panics when any single input or output device cannot be locked.
+    /// This is an explicit reset (ie: before beginning a new experiment) and is unrelated to the
+    /// retention policy. Devices themselves are not modified. Devices or logs that are currently
+    /// locked are skipped.
     ///
-    /// # Returns
+    /// # Parameters
     ///
-    /// A [`Result`] containing:
+    /// - `delete_files`: Also delete log files on disk. Failure to delete a file is logged but
+    ///   does not halt clearing other logs.
     ///
-    /// - `Ok` that is empty when loading occurred without error.
-    /// - `Err` containing the first error stored. There may be more errors that were
-    ///   not returned. An error occurring does not halt loading other logs.
-    fn load(&mut self) -> Result<(), ErrorType> {
-        let mut results = Vec::new();
+    /// # Returns
+    ///
+    /// Total number of events cleared
+    pub fn clear_logs(&mut self, delete_files: bool) -> usize {
+        fn clear<D: Device>(device: &Def<D>, delete_files: bool) -> usize {
+            let log = device.try_lock().ok().and_then(|device| device.log());
+            let mut log = match log.as_ref().and_then(|log| log.try_lock().ok()) {
+                Some(log) => log,
+                None => return 0,
+            };
 
-        for device in self.outputs.values() {
-            let mut binding = device.try_lock().unwrap();
-            results.push(
-                binding.load());
+            if delete_files && log.dir().is_some() && log.exists() {
+                if let Err(e) = std::fs::remove_file(log.full_path()) {
+                    log::error!("Could not delete {}: {}", log.full_path().display(), e);
+                }
+            }
+            log.clear()
         }
 
-        for device in self.inputs.values() {
-            let mut binding = device.try_lock().unwrap();
-            results.push(
-                binding.load());
-        }
+        let mut cleared = 0;
+        cleared += self.inputs.values().map(|device| clear(device, delete_files)).sum::<usize>();
+        cleared += self.outputs.values().map(|device| clear(device, delete_files)).sum::<usize>();
+        cleared += self.subgroups.iter_mut().map(|group| group.clear_logs(delete_files)).sum::<usize>();
+        cleared
+    }
 
-        check_results(&results)
+    /// Forget runtime state of all devices, including those of subgroups
+    ///
+    /// Calls [`Input::reset()`] and [`Output::reset_state()`] on each device so that the group
+    /// behaves as if hardware was never read nor written. Logs are not modified. Devices that are
+    /// currently locked are skipped.
+    pub fn reset_all(&mut self) {
+        for input in self.inputs.values() {
+            if let Ok(mut input) = input.try_lock() {
+                input.reset();
+            }
+        }
+        for output in self.outputs.values() {
+            if let Ok(mut output) = output.try_lock() {
+                output.reset_state();
+            }
+        }
+        for group in self.subgroups.iter_mut() {
+            group.reset_all();
+        }
     }
-}
 
-impl Name for Group {
-    /// Getter for `name`
+    /// Generate serializable configuration of group
+    ///
+    /// Devices are ordered by id so that output is stable.
     ///
     /// # Returns
     ///
-    /// Immutable reference to `name`
+    /// [`GroupConfig`] containing name, interval, device configurations, and configurations of
+    /// subgroups
+    pub fn to_config(&self) -> GroupConfig {
+        fn configs<D: Device + Directory>(devices: &DeviceContainer<IdType, D>) -> Vec<DeviceConfig> {
+            let mut configs: Vec<DeviceConfig> = devices.values()
+                .map(|device| device.try_lock().unwrap().to_config())
+                .collect();
+            configs.sort_by_key(|config| config.metadata.id);
+            configs
+        }
+
+        GroupConfig {
+            name: self.name.clone(),
+            interval_ms: self.interval.num_milliseconds(),
+            inputs: configs(&self.inputs),
+            outputs: configs(&self.outputs),
+            subgroups: self.subgroups.iter().map(Group::to_config).collect(),
+        }
+    }
+
+    /// Compare running group against configuration
+    ///
+    /// Intended to preview what applying `other` would change before rebuilding a group. Name,
+    /// polling interval, and device configuration (ie: id, name, kind, log, and [`crate::io::InputConfig`]
+    /// such as validation, deadband, or retry policy) are compared.
+    ///
+    /// # Parameters
+    ///
+    /// - `other`: Configuration to compare against, such as an imported deployment config
+    ///
+    /// # Returns
+    ///
+    /// List of [`ConfigChange`] as returned by [`GroupConfig::diff()`]. Empty if no drift exists.
+    pub fn diff_config(&self, other: &GroupConfig) -> Vec<ConfigChange> {
+        self.to_config().diff(other)
+    }
+
+    /// Rebuild group from configuration
+    ///
+    /// No commands are associated with devices. Root path is resolved as described by
+    /// [`Group::new()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: Configuration generated by [`Group::to_config()`]
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with group with devices built by [`Device::from_config()`]
+    /// - `Err` with [`ContainerError::KeyExists`] if `config` contains duplicate device ids
+    /// - `Err` with [`DeviceError::DirectionMismatch`] if a device direction does not agree with
+    ///   its container
+    pub fn from_config(config: &GroupConfig) -> Result<Self, ErrorType> {
+        let mut group = Self::with_interval(config.name.clone(), Duration::milliseconds(config.interval_ms));
+
+        for input in config.inputs.iter() {
+            let device = Input::from_config(input)?;
+            if group.inputs.contains_key(&device.id()) {
+                return Err(Box::new(ContainerError::KeyExists {key: device.id().to_string()}));
+            }
+            group.push_input(device);
+        }
+        for output in config.outputs.iter() {
+            let device = Output::from_config(output)?;
+            if group.outputs.contains_key(&device.id()) {
+                return Err(Box::new(ContainerError::KeyExists {key: device.id().to_string()}));
+            }
+            group.push_output(device);
+        }
+        for subgroup in config.subgroups.iter() {
+            group.push_subgroup(Group::from_config(subgroup)?);
+        }
+
+        Ok(group)
+    }
+
+    /// Export group configuration as YAML
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with YAML representation of [`Group::to_config()`]
+    /// - `Err` with [`FilesystemError::SerializationError`] if serialization fails
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::{Device, Input, IOKind, Output};
+    /// use sensd::storage::Group;
+    ///
+    /// let mut group = Group::new("veg room");
+    /// group.push_input(Input::new("pH probe", 0, IOKind::PH))
+    ///      .push_output(Output::new("pump", 0, IOKind::Flow));
+    ///
+    /// let yaml = group.export_config().unwrap();
+    /// let imported = Group::import_config(&yaml).unwrap();
+    ///
+    /// assert_eq!(group.to_config(), imported.to_config());
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn export_config(&self) -> Result<String, ErrorType> {
+        serde_yaml::to_string(&self.to_config()).map_err(|e| {
+            let msg = e.to_string();
+            Box::new(FilesystemError::SerializationError {msg}) as ErrorType
+        })
+    }
+
+    /// Build group from YAML configuration
+    ///
+    /// Low-level commands must be attached to devices afterwards.
+    ///
+    /// # Parameters
+    ///
+    /// - `yaml`: Configuration generated by [`Group::export_config()`]
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with group built by [`Group::from_config()`]
+    /// - `Err` with [`FilesystemError::SerializationError`] if `yaml` is invalid
+    /// - `Err` as returned by [`Group::from_config()`] if configuration is inconsistent (ie:
+    ///   duplicate device ids)
+    #[cfg(feature = "yaml")]
+    pub fn import_config(yaml: &str) -> Result<Group, ErrorType> {
+        let config: GroupConfig = serde_yaml::from_str(yaml).map_err(|e| {
+            let msg = e.to_string();
+            Box::new(FilesystemError::SerializationError {msg}) as ErrorType
+        })?;
+        Group::from_config(&config)
+    }
+
+    /// Reconstruct group by scanning a data directory created by a prior run
+    ///
+    /// Devices are inferred from JSON log files found in each device directory beneath
+    /// [`Group::full_path()`], using the [`DeviceMetadata`] stored within each file. The log of
+    /// each device is loaded, as is the audit log. Directories which do not contain a log file are
+    /// scanned as subgroups. Since no command is stored on disk, commands must be attached
+    /// afterwards.
+    ///
+    /// Log files without metadata, and devices with a duplicate id or an
+    /// [`IODirection::InOut`] direction are skipped with a warning.
+    ///
+    /// # Parameters
+    ///
+    /// - `root`: Root directory which contains group directory
+    /// - `name`: Name of group, which is also the name of its directory
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with reconstructed group. Polling interval is not stored on disk and is therefore
+    ///   the default.
+    /// - `Err` if group directory cannot be read, or if a log file cannot be parsed or loaded
+    pub fn from_dir(root: RootPath, name: &str) -> Result<Group, ErrorType> {
+        let mut group = Group::with_root(name, root.deref());
+        let path = group.full_path();
+        group.scan_dir(&path)?;
+
+        Ok(group)
+    }
+
+    /// Add devices and subgroups found in `path`, then load audit log
+    fn scan_dir(&mut self, path: &Path) -> Result<(), ErrorType> {
+        let mut dirs: Vec<PathBuf> = std::fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_dir())
+            .collect();
+        dirs.sort();
+
+        for dir in dirs {
+            match find_log_file(&dir)? {
+                Some(file) => self.push_scanned(&dir, &file)?,
+                None => {
+                    let name = dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+                    let mut subgroup = Group::new(name);
+                    subgroup.inherit_root(path);
+                    subgroup.scan_dir(&dir)?;
+                    if !subgroup.inputs.is_empty() || !subgroup.outputs.is_empty() || !subgroup.subgroups.is_empty() {
+                        self.push_subgroup(subgroup);
+                    }
+                }
+            }
+        }
+
+        // devices were not added during this run
+        self.audit.clear();
+        self.load_audit(Some(path))
+    }
+
+    /// Add device described by metadata stored in log `file`, then load its log from `dir`
+    fn push_scanned(&mut self, dir: &Path, file: &Path) -> Result<(), ErrorType> {
+        let reader = BufReader::new(File::open(file).map_err(FilesystemError::from)?);
+        let contents: serde_json::Value = serde_json::from_reader(reader)
+            .map_err(FilesystemError::from)?;
+        let metadata: Option<DeviceMetadata> = contents.get("metadata")
+            .map(|metadata| serde_json::from_value(metadata.clone()))
+            .transpose()
+            .map_err(FilesystemError::from)?
+            .flatten();
+
+        let metadata = match metadata {
+            Some(metadata) => metadata,
+            None => {
+                log::warn!("{} does not contain device metadata", file.display());
+                return Ok(());
+            }
+        };
+        let config = DeviceConfig {metadata, log: true, input: None};
+        let id = config.metadata.id;
+
+        match config.metadata.direction {
+            IODirection::In if !self.inputs.contains_key(&id) => {
+                let device = Input::from_config(&config)?;
+                device.log().unwrap().try_lock().unwrap().load_from(dir)?;
+                self.push_input(device);
+            }
+            IODirection::Out if !self.outputs.contains_key(&id) => {
+                let device = Output::from_config(&config)?;
+                device.log().unwrap().try_lock().unwrap().load_from(dir)?;
+                self.push_output(device);
+            }
+            _ => log::warn!("Skipping {} found in {}", config.metadata, dir.display()),
+        }
+        Ok(())
+    }
+
+    /// Store a nested [`Group`]
+    ///
+    /// Unless root path of `group` was explicitly set (ie: by [`Group::with_root()`] or
+    /// [`Group::set_root()`]), `group` inherits [`Group::full_path()`] of `self` as its root path,
+    /// so that its directory is nested within the directory of `self`.
+    ///
+    /// # Parameters
+    ///
+    /// - `group`: Subgroup to add
+    ///
+    /// # Returns
+    ///
+    /// Mutable reference to `self`
+    pub fn push_subgroup(&mut self, mut group: Group) -> &mut Self {
+        if !group.explicit_root {
+            group.inherit_root(self.full_path());
+        }
+        self.subgroups.push(group);
+
+        self
+    }
+
+    /// Immutable access to nested groups
+    pub fn subgroups(&self) -> &[Group] {
+        &self.subgroups
+    }
+
+    /// Mutable access to nested groups
+    pub fn subgroups_mut(&mut self) -> &mut [Group] {
+        &mut self.subgroups
+    }
+
+    /// Update root path and propagate to devices and subgroups without marking root as explicit
+    fn inherit_root<P>(&mut self, path: P)
+    where
+        P: AsRef<Path>
+    {
+        self.root = RootPath::from(path);
+
+        let full_path = self.full_path();
+        self.inputs.set_parent_dir(RootPath::from(&full_path));
+        self.outputs.set_parent_dir(RootPath::from(&full_path));
+
+        for subgroup in self.subgroups.iter_mut().filter(|group| !group.explicit_root) {
+            subgroup.inherit_root(&full_path);
+        }
+    }
+
+    /// History of devices added to or removed from group
+    ///
+    /// Events loaded by [`Group::load()`] are included.
+    ///
+    /// # Returns
+    ///
+    /// Slice of [`AuditEvent`] in chronological order
+    pub fn audit_log(&self) -> &[AuditEvent] {
+        &self.audit
+    }
+
+    /// Path to audit log file within group directory
+    ///
+    /// # Parameters
+    ///
+    /// - `dir`: Directory used in place of [`Group::full_path()`]. If `None`, the group directory
+    ///   is used.
+    fn audit_path(&self, dir: Option<&Path>) -> PathBuf {
+        dir.map_or_else(|| self.full_path(), Path::to_path_buf)
+            .join(format!("{}{}", AUDIT_FN, FILETYPE))
+    }
+
+    /// Attempt scheduled routines of all input devices, including those of subgroups
+    ///
+    /// Observers registered by [`Group::on_routine_fired()`] are notified for each device whose
+    /// routines were executed. Nothing is done while group is paused by [`Group::pause()`].
+    pub fn attempt_routines(&self) {
+        if self.is_paused() {
+            return;
+        }
+        for (id, device) in self.inputs.iter() {
+            let mut binding = device.try_lock().unwrap();
+            if let Some(publisher) = binding.publisher_mut() {
+                let fired = publisher.attempt_routines();
+                if fired > 0 {
+                    self.observers.routine_fired(*id, fired);
+                }
+            }
+        }
+        for subgroup in self.subgroups.iter() {
+            subgroup.attempt_routines();
+        }
+    }
+
+    /// Subscribe an action to an input device by id
+    ///
+    /// A [`Publisher`] is created for the input if it does not have one, therefore
+    /// [`Input::init_publisher()`] does not need to be called beforehand.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: Id of input device whose events are passed to `action`
+    /// - `action`: Action to add to publisher of input
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` when action has been subscribed
+    /// - `Err` with [`ContainerError::KeyMissing`] if no input has `id`, or with
+    ///   [`ContainerError::MiscError`] if input is currently locked
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::action::{actions::Threshold, Action, Trigger};
+    /// use sensd::io::{Device, Input, RawValue};
+    /// use sensd::storage::Group;
+    ///
+    /// let mut group = Group::new("");
+    /// group.push_input(Input::new("sensor", 0, None));
+    ///
+    /// let action = Threshold::new("high", RawValue::Float(5.0), Trigger::GT);
+    /// assert!(group.subscribe(0, action.into_boxed()).is_ok());
+    ///
+    /// let action = Threshold::new("high", RawValue::Float(5.0), Trigger::GT);
+    /// assert!(group.subscribe(1, action.into_boxed()).is_err());
+    /// ```
+    pub fn subscribe(&mut self, id: IdType, action: BoxedAction) -> Result<(), ErrorType> {
+        let input = self.inputs.get(&id)
+            .ok_or_else(|| ContainerError::KeyMissing {key: id.to_string()})?;
+        let mut binding = input.try_lock().map_err(|e| ContainerError::MiscError {
+            name: self.name.clone(),
+            msg: e.to_string(),
+        })?;
+
+        binding.publisher_mut()
+            .get_or_insert_with(Publisher::default)
+            .subscribe(action);
+        Ok(())
+    }
+
+    /// Feed recorded events through the [`crate::action::Publisher`] of an input device
+    ///
+    /// This allows action logic to be validated offline against captured data (ie: the events of
+    /// a saved device log). Events are propagated exactly as they would be when read live, however
+    /// the input device is not read and its state and log are not modified. Output devices
+    /// commanded by actions are written to as normal, therefore mock outputs should be used when
+    /// hardware should not be touched.
+    ///
+    /// While each event is handled, [`crate::clock::now()`] returns the timestamp of the event.
+    /// Therefore, routines scheduled by actions are timestamped relative to recorded data, and
+    /// scheduled routines which are due by the timestamp of an event are executed before the
+    /// event is propagated. Observers registered by [`Group::on_routine_fired()`] are notified.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: Id of input device whose publisher receives events
+    /// - `events`: Recorded events. Events are replayed in chronological order.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with number of events which were propagated. This is `0` if the input does not have
+    ///   a publisher.
+    /// - `Err` with [`ContainerError::KeyMissing`] if no input has `id`
+    ///
+    /// # Panics
+    ///
+    /// If input device is locked
+    pub fn replay(&mut self, id: IdType, events: &[IOEvent]) -> Result<usize, ContainerError> {
+        let input = self.inputs.get(&id)
+            .ok_or_else(|| ContainerError::KeyMissing {key: id.to_string()})?
+            .clone();
+        let mut binding = input.try_lock().unwrap();
+        let publisher = match binding.publisher_mut() {
+            Some(publisher) => publisher,
+            None => return Ok(0),
+        };
+
+        let mut events: Vec<&IOEvent> = events.iter().collect();
+        events.sort_by_key(|event| event.timestamp);
+
+        for event in events.iter() {
+            let fired = clock::simulate(event.timestamp, || {
+                let fired = publisher.attempt_routines();
+                publisher.propagate(event);
+                fired
+            });
+            if fired > 0 {
+                self.observers.routine_fired(id, fired);
+            }
+        }
+
+        Ok(events.len())
+    }
+
+    /// Register a callback which is invoked before devices are polled
+    ///
+    /// Observers are only invoked when a poll is due, and are scoped to this group. Subgroups
+    /// maintain their own observers.
+    ///
+    /// # Parameters
+    ///
+    /// - `observer`: Callback to invoke. Multiple observers are called in registration order.
+    ///
+    /// # Returns
+    ///
+    /// Mutable reference to `self`
+    pub fn on_poll_start(&mut self, observer: PollStartObserver) -> &mut Self {
+        self.observers.poll_start.push(observer);
+        self
+    }
+
+    /// Register a callback which is invoked with the result of every read after devices are polled
+    ///
+    /// Since [`DeviceError`] cannot be cloned, errors are passed as their message.
+    ///
+    /// # Parameters
+    ///
+    /// - `observer`: Callback to invoke. Multiple observers are called in registration order.
+    ///
+    /// # Returns
+    ///
+    /// Mutable reference to `self`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::{Device, Input, RawValue};
+    /// use sensd::storage::Group;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let count = Arc::new(AtomicUsize::new(0));
+    /// let observed = count.clone();
+    ///
+    /// let mut group = Group::new("group");
+    /// group.push_input(Input::mock("probe", 0, RawValue::default()))
+    ///      .on_poll_complete(Box::new(move |results| {
+    ///          observed.fetch_add(results.len(), Ordering::SeqCst);
+    ///      }));
+    ///
+    /// group.poll().unwrap();
+    /// assert_eq!(1, count.load(Ordering::SeqCst));
+    /// ```
+    pub fn on_poll_complete(&mut self, observer: PollObserver) -> &mut Self {
+        self.observers.poll_complete.push(observer);
+        self
+    }
+
+    /// Register a callback which is invoked when an input device becomes faulted
+    ///
+    /// A device is faulted once its consecutive failures reach [`FAULT_THRESHOLD`]. Observers are
+    /// invoked once per fault, and again only after the device has recovered and faulted again.
+    ///
+    /// # Parameters
+    ///
+    /// - `observer`: Callback to invoke with device id. Multiple observers are called in
+    ///   registration order.
+    ///
+    /// # Returns
+    ///
+    /// Mutable reference to `self`
+    pub fn on_device_fault(&mut self, observer: FaultObserver) -> &mut Self {
+        self.observers.device_fault.push(observer);
+        self
+    }
+
+    /// Register a callback which is invoked when scheduled routines are executed
+    ///
+    /// # Parameters
+    ///
+    /// - `observer`: Callback to invoke with id of input device and number of routines executed.
+    ///   Multiple observers are called in registration order.
+    ///
+    /// # Returns
+    ///
+    /// Mutable reference to `self`
+    ///
+    /// # See Also
+    ///
+    /// - [`Group::attempt_routines()`]
+    pub fn on_routine_fired(&mut self, observer: RoutineObserver) -> &mut Self {
+        self.observers.routine_fired.push(observer);
+        self
+    }
+
+    /// Add a destination for events generated by [`Group::poll()`] and [`Group::poll_streaming()`]
+    ///
+    /// Sinks are additive: device logs are still updated. Every event is passed to each sink in
+    /// registration order, then all sinks are flushed once polling is complete. Sinks are not
+    /// shared with subgroups.
+    ///
+    /// # Parameters
+    ///
+    /// - `sink`: [`crate::storage::Sink`] to feed events into
+    ///
+    /// # Returns
+    ///
+    /// Mutable reference to `self`
+    pub fn add_sink(&mut self, sink: BoxedSink) -> &mut Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Number of registered sinks
+    pub fn sink_count(&self) -> usize {
+        self.sinks.len()
+    }
+
+    /// Run [`Device::self_test()`] on all devices
+    ///
+    /// This is meant to be called before polling begins to flag misconfigured devices.
+    ///
+    /// # Returns
+    ///
+    /// [`HashMap`] of results keyed by device direction and id. Devices that cannot be locked
+    /// are reported as an error.
+    pub fn self_test_all(&self) -> HashMap<(IODirection, IdType), Result<(), ErrorType>> {
+        fn test<D: Device>(device: &Def<D>) -> Result<(), ErrorType> {
+            match device.try_lock() {
+                Ok(device) => device.self_test(),
+                Err(e) => Err(e.to_string().into()),
+            }
+        }
+
+        let mut results = HashMap::new();
+        for (id, device) in self.inputs.iter() {
+            results.insert((IODirection::In, *id), test(device));
+        }
+        for (id, device) in self.outputs.iter() {
+            results.insert((IODirection::Out, *id), test(device));
+        }
+        results
+    }
+
+    /// Assemble a health report from current device state
+    ///
+    /// Devices that are currently locked are skipped.
+    ///
+    /// # Returns
+    ///
+    /// [`GroupHealth`] describing stale and faulted input devices, routine backlog, and time since
+    /// last successful poll.
+    pub fn health(&self) -> GroupHealth {
+        let now = clock::now();
+        let stale_after = now - *self.interval() * STALE_INTERVALS;
+
+        let mut health = GroupHealth {
+            device_count: self.inputs.len() + self.outputs.len(),
+            last_poll: self.last_success,
+            since_last_poll_ms: self.last_success
+                .map(|last| (now - last).num_milliseconds()),
+            ..Default::default()
+        };
+
+        for (id, device) in self.inputs.iter() {
+            let device = match device.try_lock() {
+                Ok(device) => device,
+                Err(_) => continue,
+            };
+
+            if device.last_read().is_none_or(|last| last < stale_after) {
+                health.stale.push(*id);
+            }
+            if device.consecutive_failures() >= FAULT_THRESHOLD {
+                health.faulted.push(*id);
+            }
+            if let Some(publisher) = device.publisher() {
+                if let Ok(handler) = publisher.handler_ref().try_lock() {
+                    health.pending_routines += handler.scheduled().len();
+                }
+            }
+        }
+
+        health.stale.sort();
+        health.faulted.sort();
+
+        health
+    }
+
+    /// Serialize runtime state of group and all subgroups for diagnostics
+    ///
+    /// Configuration, metadata and last event of every device, pending routines, and health are
+    /// combined into a single JSON document which is suitable for attaching to a bug report. Devices
+    /// are accessed with `try_lock()` so that this never blocks; devices or routine handlers which
+    /// are locked are listed in [`StateDump::unavailable`].
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with pretty-printed JSON of [`StateDump`]
+    /// - `Err` if serialization fails
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::{Device, Input};
+    /// use sensd::storage::{Group, StateDump};
+    ///
+    /// let mut group = Group::new("greenhouse");
+    /// group.push_input(Input::new("sensor", 0, None));
+    ///
+    /// let dump: StateDump = serde_json::from_str(&group.dump_state().unwrap()).unwrap();
+    /// assert_eq!("greenhouse", dump.name);
+    /// assert_eq!(1, dump.inputs.len());
+    /// ```
+    pub fn dump_state(&self) -> Result<String, ErrorType> {
+        Ok(serde_json::to_string_pretty(&self.state_dump())?)
+    }
+
+    /// Build [`StateDump`] of group and all subgroups
+    fn state_dump(&self) -> StateDump {
+        let mut unavailable = Vec::new();
+
+        let mut inputs: Vec<DeviceState> = Vec::new();
+        for (id, device) in self.inputs.iter() {
+            let device = match device.try_lock() {
+                Ok(device) => device,
+                Err(_) => {
+                    unavailable.push(format!("input {}", id));
+                    continue;
+                }
+            };
+
+            let mut routines = Vec::new();
+            if let Some(publisher) = device.publisher() {
+                match publisher.handler_ref().try_lock() {
+                    Ok(handler) => routines.extend(handler.scheduled().iter().map(RoutineState::from)),
+                    Err(_) => unavailable.push(format!("routines of input {}", id)),
+                }
+            }
+
+            inputs.push(DeviceState {
+                config: device.to_config(),
+                state: device.state().clone(),
+                last_event: device.last_event(),
+                routines,
+            });
+        }
+
+        let mut outputs: Vec<DeviceState> = Vec::new();
+        for (id, device) in self.outputs.iter() {
+            match device.try_lock() {
+                Ok(device) => outputs.push(DeviceState {
+                    config: device.to_config(),
+                    state: device.state().clone(),
+                    last_event: device.last_event(),
+                    routines: Vec::new(),
+                }),
+                Err(_) => unavailable.push(format!("output {}", id)),
+            }
+        }
+
+        inputs.sort_by_key(|device| device.config.metadata.id);
+        outputs.sort_by_key(|device| device.config.metadata.id);
+        unavailable.sort();
+
+        StateDump {
+            name: self.name.clone(),
+            generated: clock::now(),
+            interval_ms: self.interval.num_milliseconds(),
+            paused: self.is_paused(),
+            health: self.health(),
+            inputs,
+            outputs,
+            unavailable,
+            subgroups: self.subgroups.iter().map(Group::state_dump).collect(),
+        }
+    }
+
+    /// Select input devices of a given kind
+    ///
+    /// Only devices directly owned by this group are considered; subgroups are not searched.
+    /// Devices that are currently locked are skipped instead of blocking.
+    ///
+    /// # Parameters
+    ///
+    /// - `kind`: Kind of device to select
+    ///
+    /// # Returns
+    ///
+    /// References to every input whose [`DeviceGetters::kind()`] is `kind`
+    pub fn inputs_by_kind(&self, kind: IOKind) -> Vec<&Def<Input>> {
+        self.inputs.values()
+            .filter(|device| device.try_lock().is_ok_and(|device| device.kind() == kind))
+            .collect()
+    }
+
+    /// Select output devices of a given kind
+    ///
+    /// Output counterpart of [`Group::inputs_by_kind()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `kind`: Kind of device to select
+    ///
+    /// # Returns
+    ///
+    /// References to every output whose [`DeviceGetters::kind()`] is `kind`
+    pub fn outputs_by_kind(&self, kind: IOKind) -> Vec<&Def<Output>> {
+        self.outputs.values()
+            .filter(|device| device.try_lock().is_ok_and(|device| device.kind() == kind))
+            .collect()
+    }
+
+    /// Select devices of a given kind in both directions
+    ///
+    /// # Parameters
+    ///
+    /// - `kind`: Kind of device to select
+    ///
+    /// # Returns
+    ///
+    /// Inputs followed by outputs whose [`DeviceGetters::kind()`] is `kind`
+    ///
+    /// # See Also
+    ///
+    /// - [`Group::inputs_by_kind()`] for restrictions
+    pub fn devices_by_kind(&self, kind: IOKind) -> Vec<DeviceType> {
+        self.inputs_by_kind(kind).into_iter()
+            .map(|device| DeviceType::Input(device.clone()))
+            .chain(self.outputs_by_kind(kind).into_iter()
+                .map(|device| DeviceType::Output(device.clone())))
+            .collect()
+    }
+
+    /// Find a device by id without knowing its direction
+    ///
+    /// Inputs and outputs are stored in separate containers and may share an id. In that case, the
+    /// input is returned. Subgroups are not searched.
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: Id of device to find
+    ///
+    /// # Returns
+    ///
+    /// `Some` with shared reference to device, or `None` if no input or output has `id`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::{Device, IODirection, Input, Output};
+    /// use sensd::storage::Group;
+    ///
+    /// let mut group = Group::new("");
+    /// group
+    ///     .push_input(Input::new("sensor", 0, None))
+    ///     .push_output(Output::new("pump", 1, None));
+    ///
+    /// assert_eq!(IODirection::Out, group.find_device(1).unwrap().direction());
+    /// assert!(group.find_device(2).is_none());
+    /// ```
+    pub fn find_device(&self, id: IdType) -> Option<DeviceType> {
+        self.inputs.get(&id)
+            .map(|device| DeviceType::Input(device.clone()))
+            .or_else(|| self.outputs.get(&id).map(|device| DeviceType::Output(device.clone())))
+    }
+
+    /// Duration of the most recent read of each input device
+    ///
+    /// Only reads made by [`Group::poll()`] or [`Group::poll_streaming()`] are timed, and
+    /// subgroups are not included. This is intended to identify slow devices which limit the
+    /// polling interval.
+    ///
+    /// # Returns
+    ///
+    /// [`HashMap`] of read durations keyed by input id
+    pub fn last_poll_latencies(&self) -> HashMap<IdType, Duration> {
+        self.latencies.iter()
+            .map(|(id, latency)| (*id, latency.last))
+            .collect()
+    }
+
+    /// Running statistics of read durations of each input device
+    ///
+    /// # Returns
+    ///
+    /// [`ReadLatency`] keyed by input id
+    ///
+    /// # See Also
+    ///
+    /// - [`Group::last_poll_latencies()`] for restrictions
+    pub fn read_latencies(&self) -> &HashMap<IdType, ReadLatency> {
+        &self.latencies
+    }
+
+    /// Total number of events held in memory by all device logs
+    ///
+    /// Devices or logs that are currently locked are skipped instead of blocking.
+    ///
+    /// # Returns
+    ///
+    /// Sum of events stored in each device [`crate::storage::Log`]
+    pub fn total_event_count(&self) -> usize {
+        self.event_count_by_device()
+            .values()
+            .sum()
+    }
+
+    /// Number of events held in memory by each device log
+    ///
+    /// Since input and output devices may share an id, entries are keyed by both direction and id.
+    /// Devices without a log, and devices or logs that are currently locked, are skipped.
+    ///
+    /// # Returns
+    ///
+    /// [`HashMap`] of event counts keyed by device direction and id
+    pub fn event_count_by_device(&self) -> HashMap<(IODirection, IdType), usize> {
+        let mut counts = HashMap::new();
+
+        let logs = self.inputs.iter()
+            .filter_map(|(id, device)| Some(((IODirection::In, *id), device.try_lock().ok()?.log())))
+            .chain(self.outputs.iter()
+                .filter_map(|(id, device)| Some(((IODirection::Out, *id), device.try_lock().ok()?.log()))));
+
+        for (key, log) in logs {
+            if let Some(count) = log.and_then(|log| Some(log.try_lock().ok()?.iter().count())) {
+                counts.insert(key, count);
+            }
+        }
+
+        counts
+    }
+
+    /// Gather events from every device log into a single chronological stream
+    ///
+    /// Events are sorted by timestamp in ascending order. When devices share a timestamp, events
+    /// from inputs are ordered before events from outputs, then by ascending device id. Devices
+    /// without a log, and devices or logs that are currently locked, are skipped.
+    ///
+    /// # Returns
+    ///
+    /// Vector of [`MergedEvent`] which include the direction and id of the source device
+    pub fn merged_log(&self) -> Vec<MergedEvent> {
+        let logs = self.inputs.iter()
+            .filter_map(|(id, device)| Some(((IODirection::In, *id), device.try_lock().ok()?.log())))
+            .chain(self.outputs.iter()
+                .filter_map(|(id, device)| Some(((IODirection::Out, *id), device.try_lock().ok()?.log()))));
+
+        let mut merged = Vec::new();
+        for ((direction, id), log) in logs {
+            if let Some(log) = log.as_ref().and_then(|log| log.try_lock().ok()) {
+                merged.extend(log.events()
+                    .map(|event| MergedEvent { direction, id, event: event.clone() }));
+            }
+        }
+
+        merged.sort_by(|a, b| {
+            a.event.timestamp.cmp(&b.event.timestamp)
+                .then((a.direction == IODirection::Out).cmp(&(b.direction == IODirection::Out)))
+                .then(a.id.cmp(&b.id))
+        });
+        merged
+    }
+
+    /// Write merged event stream as newline-delimited JSON
+    ///
+    /// Each line is a single serialized [`MergedEvent`]. Any existing file is overwritten.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: path of file to write to. Parent directories are created if necessary.
+    ///
+    /// # Returns
+    ///
+    /// `Result` containing an error if serialization or writing fails
+    ///
+    /// # See Also
+    ///
+    /// - [`Group::merged_log()`] for ordering of events
+    pub fn export_merged<P>(&self, path: P) -> Result<(), ErrorType>
+    where
+        P: AsRef<Path>
+    {
+        let mut writer = BufWriter::new(writable_or_create(path).map_err(FilesystemError::from)?);
+
+        for event in self.merged_log() {
+            serde_json::to_writer(&mut writer, &event).map_err(FilesystemError::from)?;
+            writeln!(writer).map_err(FilesystemError::from)?;
+        }
+        writer.flush().map_err(FilesystemError::from)?;
+
+        Ok(())
+    }
+
+    /// Render logged events in InfluxDB line protocol
+    ///
+    /// Each event of [`Group::merged_log()`] becomes a single line, using the group name as
+    /// measurement, the device direction, id and kind as tags, and the timestamp in nanoseconds.
+    /// Direction and kind are written by variant name, as when serialized:
+    ///
+    /// ```text
+    /// reservoir,direction=In,id=0,kind=PH value=6.5 1680000000000000000
+    /// ```
+    ///
+    /// Numeric values are written to the `value` field as floats, and binary values are written
+    /// as `0` or `1`, so that the type of the field never conflicts between devices.
+    /// [`RawValue::Text`] values are written to the string field `text`. Events with a value that
+    /// is not finite cannot be represented and are skipped. Subgroups follow, each under a
+    /// measurement of their own name.
+    ///
+    /// Since Influx requires a measurement, group name should not be empty.
+    ///
+    /// # Returns
+    ///
+    /// Newline-terminated lines ready to be written to the Influx write API
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::{Device, IOEvent, Input, IOKind, RawValue};
+    /// use sensd::storage::{Chronicle, Group};
+    /// use chrono::{TimeZone, Utc};
+    ///
+    /// let mut group = Group::new("reservoir");
+    /// group.push_input(Input::new("probe", 0, IOKind::PH).init_log());
+    ///
+    /// let timestamp = Utc.timestamp_opt(1_680_000_000, 0).unwrap();
+    /// let log = group.inputs.get(&0).unwrap().try_lock().unwrap().log().unwrap();
+    /// log.try_lock().unwrap().push(IOEvent::with_timestamp(timestamp, RawValue::Float(6.5))).unwrap();
+    ///
+    /// assert_eq!(
+    ///     "reservoir,direction=In,id=0,kind=PH value=6.5 1680000000000000000\n",
+    ///     group.export_influx(),
+    /// );
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`Group::export_merged()`] for exporting events as newline-delimited JSON
+    pub fn export_influx(&self) -> String {
+        let kinds: HashMap<(IODirection, IdType), IOKind> = self.inputs.iter()
+            .filter_map(|(id, device)| Some(((IODirection::In, *id), device.try_lock().ok()?.metadata().kind)))
+            .chain(self.outputs.iter()
+                .filter_map(|(id, device)| Some(((IODirection::Out, *id), device.try_lock().ok()?.metadata().kind))))
+            .collect();
+        let measurement = influx_escape(&self.name, &[',', ' ']);
+
+        let mut lines = String::new();
+        for merged in self.merged_log() {
+            let field = match influx_field(&merged.event.value) {
+                Some(field) => field,
+                None => continue,
+            };
+            let kind = merged.event.kind
+                .or_else(|| kinds.get(&(merged.direction, merged.id)).copied())
+                .unwrap_or_default();
+
+            lines.push_str(&format!(
+                "{},direction={:?},id={},kind={:?} {} {}\n",
+                measurement,
+                merged.direction,
+                merged.id,
+                kind,
+                field,
+                merged.event.timestamp.timestamp_nanos(),
+            ));
+        }
+
+        for subgroup in self.subgroups.iter() {
+            lines.push_str(&subgroup.export_influx());
+        }
+
+        lines
+    }
+
+    //
+    // Getters
+
+
+    #[inline]
+    /// Getter for `interval`
+    ///
+    /// # Notes
+    ///
+    /// Since this is frequently used in iterators and polling, this
+    /// method is marked inline to avoiding jumping in memory.
+    ///
+    /// # Returns
+    ///
+    /// Immutable reference to `interval`
+    pub fn interval(&self) -> &Duration {
+        &self.interval
+    }
+
+    /// Setter for `interval`
+    ///
+    /// # Parameters
+    ///
+    /// - `interval`: any value that can be coerced into [`Duration`]
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval
+    }
+
+    /// Time remaining until next poll is due
+    ///
+    /// This allows a driving loop to sleep precisely until [`Group::poll()`] will execute instead
+    /// of waking at a fixed frequency. When routines are scheduled, callers should sleep for the
+    /// lesser of this value and the time until the next routine.
+    ///
+    /// # Returns
+    ///
+    /// `last_execution + interval - now` (or time until next boundary when aligned by
+    /// [`Group::set_align_to_interval()`]), clamped to zero when a poll is already due. Inputs
+    /// with their own interval set by [`Input::set_poll_interval()`] are considered, unless
+    /// locked. When subgroups exist, the least time of this group and all subgroups is returned.
+    /// While paused by [`Group::pause()`], `interval` is returned since no poll will be executed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use sensd::storage::Group;
+    ///
+    /// let mut group = Group::with_interval("", Duration::seconds(10));
+    ///
+    /// // first poll is immediately due
+    /// assert_eq!(Duration::zero(), group.time_until_poll());
+    ///
+    /// group.poll().unwrap();
+    /// assert!(group.time_until_poll() > Duration::seconds(9));
+    /// ```
+    pub fn time_until_poll(&self) -> Duration {
+        if self.is_paused() {
+            return self.interval;
+        }
+
+        let now = clock::now();
+        let mut remaining = self.next_execution() - now;
+
+        // inputs with their own interval may be due before group
+        for (id, input) in self.inputs.iter() {
+            let interval = match input.try_lock() {
+                Ok(device) => device.poll_interval(),
+                Err(_) => continue,
+            };
+            if let Some(interval) = interval {
+                let next = self.last_polled.get(id).map_or(now, |last| *last + interval);
+                remaining = remaining.min(next - now);
+            }
+        }
+
+        self.subgroups.iter()
+            .map(Group::time_until_poll)
+            .fold(remaining, Duration::min)
+            .max(Duration::zero())
+    }
+
+    /// Create group directory and restrict permissions of created directories and files
+    ///
+    /// By default, directories and files are created with permissions determined by the umask of
+    /// the process (commonly `0o755` for directories and `0o644` for files). This allows, for
+    /// example, the data directory to be made group-writable so that another service account can
+    /// read logs.
+    ///
+    /// `mode` is applied to the group directory immediately, then to the group directory and all
+    /// of its contents (including device directories and logs) after every save. Directories
+    /// receive `mode` while files receive `mode` without execute bits. Log and audit files are
+    /// created with these permissions, so that they are never more permissive than `mode`.
+    /// Subgroups keep the mode set by their own call to this method, otherwise they inherit
+    /// `mode`. On non-Unix platforms, the directory is created but `mode` is ignored.
+    ///
+    /// # Security
+    ///
+    /// Any account granted write access may modify or delete stored logs, and world-readable modes
+    /// (eg: `0o755`) expose recorded data to all local users. Prefer the most restrictive mode
+    /// which satisfies all consumers, such as `0o750` or `0o770` combined with a shared Unix group.
+    ///
+    /// # Parameters
+    ///
+    /// - `mode`: Unix permission bits of directories (eg: `0o770`)
+    ///
+    /// # Panics
+    ///
+    /// If directory cannot be created. See [`Directory::init_dir_ref()`].
+    ///
+    /// # Returns
+    ///
+    /// Mutable reference to `self`, allowing method chaining
+    pub fn init_dir_with_mode(&mut self, mode: u32) -> &mut Self {
+        self.mode = Some(mode);
+        self.init_dir_ref();
+
+        let mut results = Vec::new();
+        self.collect_modes(&mut results, None);
+        for e in results.into_iter().filter_map(Result::err) {
+            log::warn!("Could not set permissions of '{}': {}", self.full_path().display(), e);
+        }
+        self
+    }
+
+    /// Permissions set by [`Group::init_dir_with_mode()`]
+    ///
+    /// # Returns
+    ///
+    /// `Some` with Unix permission bits of directories, or `None` if permissions are not managed
+    pub fn dir_mode(&self) -> Option<u32> {
+        self.mode
+    }
+
+    /// Retry failed saves of device logs with exponential backoff
+    ///
+    /// When saving the log of a device fails (eg: a transient disk error), the save is retried up
+    /// to `retries` times before the error is recorded in the result of [`Group::save_all()`],
+    /// [`Group::save()`] or [`Group::save_concurrent()`]. The delay before the first retry is `backoff`, and is
+    /// doubled before each subsequent retry. Each retry is logged at `warn` level.
+    ///
+    /// Saving is blocked while retrying, so total delay should be kept well below
+    /// [`Group::interval()`]. Devices are unlocked while waiting to retry. Subgroups use their own
+    /// retry policy.
+    ///
+    /// # Parameters
+    ///
+    /// - `retries`: Maximum number of retries. `0` disables retrying.
+    /// - `backoff`: Delay before first retry
+    ///
+    /// # Returns
+    ///
+    /// Mutable reference to `self`, allowing method chaining
+    pub fn set_save_retry(&mut self, retries: u32, backoff: Duration) -> &mut Self {
+        self.save_retry = Some((retries, backoff));
+        self
+    }
+
+    /// Getter for retry policy of device saves
+    ///
+    /// # Returns
+    ///
+    /// Maximum number of retries and delay before first retry, or `None` if failed saves are not
+    /// retried
+    ///
+    /// # See Also
+    ///
+    /// - [`Group::set_save_retry()`] for how retries are performed
+    pub fn save_retry(&self) -> Option<(u32, Duration)> {
+        self.save_retry
+    }
+
+    /// Suspend polling and execution of routines
+    ///
+    /// This is intended for maintenance, where devices should not be accessed but group state
+    /// should be kept. While paused, [`Group::poll()`] and [`Group::attempt_routines()`] return
+    /// without accessing devices of this group or its subgroups. Logs, devices, and scheduled
+    /// routines are untouched. Calling this while already paused has no effect.
+    ///
+    /// # See Also
+    ///
+    /// - [`Group::resume()`] to continue polling
+    pub fn pause(&mut self) {
+        if self.paused.is_none() {
+            self.paused = Some(clock::now());
+        }
+    }
+
+    /// Continue polling and execution of routines after [`Group::pause()`]
+    ///
+    /// Time spent paused is treated as elapsed: the schedule of this group, of its subgroups, and
+    /// of inputs with their own interval is shifted by the paused duration, so that intervals
+    /// missed while paused are not caught up regardless of [`Group::poll_policy()`]. Subgroups
+    /// which are paused themselves are left to be shifted by their own call to
+    /// [`Group::resume()`]. Routines which became due while paused are executed by the next call
+    /// to [`Group::attempt_routines()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use sensd::storage::Group;
+    ///
+    /// let mut group = Group::with_interval("", Duration::seconds(10));
+    /// group.poll().unwrap();
+    ///
+    /// group.pause();
+    /// assert!(group.is_paused());
+    /// assert!(group.poll().unwrap().is_empty());
+    ///
+    /// group.resume();
+    /// assert!(!group.is_paused());
+    /// assert!(group.time_until_poll() > Duration::seconds(9));
+    /// ```
+    pub fn resume(&mut self) {
+        if let Some(paused) = self.paused.take() {
+            self.shift_schedule(clock::now() - paused);
+        }
+    }
+
+    /// Delay schedule of this group, its inputs, and subgroups which are not paused by `span`
+    fn shift_schedule(&mut self, span: Duration) {
+        self.last_execution += span;
+        for last in self.last_polled.values_mut() {
+            *last += span;
+        }
+        for subgroup in self.subgroups.iter_mut().filter(|subgroup| !subgroup.is_paused()) {
+            subgroup.shift_schedule(span);
+        }
+    }
+
+    /// Whether group is paused by [`Group::pause()`]
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    /// Drive polling and scheduled routines until `proceed` returns `false`
+    ///
+    /// Each iteration calls [`Group::poll()`] and [`Group::attempt_routines()`], then sleeps for
+    /// the lesser of [`Group::time_until_poll()`] and `frequency`. Errors returned by devices are
+    /// reported via [`log::error!`].
+    ///
+    /// # Parameters
+    ///
+    /// - `frequency`: Maximum time to sleep between iterations. This should be short enough for
+    ///   routines to be executed close to their scheduled time.
+    /// - `watchdog`: Optional [`Watchdog`] which receives a heartbeat after every iteration
+    /// - `proceed`: Called before every iteration. Loop exits when `false` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Duration;
+    /// use sensd::storage::{Group, Watchdog};
+    ///
+    /// let mut group = Group::with_interval("", Duration::milliseconds(5));
+    /// let watchdog = Watchdog::new(Duration::seconds(1), || panic!("polling stalled"));
+    ///
+    /// let mut iterations = 0;
+    /// group.run(Duration::milliseconds(1), Some(&watchdog), |_| {
+    ///     iterations += 1;
+    ///     iterations <= 10
+    /// });
+    ///
+    /// assert!(!watchdog.is_stalled());
+    /// ```
+    pub fn run<F>(&mut self, frequency: Duration, watchdog: Option<&Watchdog>, mut proceed: F)
+    where
+        F: FnMut(&Group) -> bool
+    {
+        let frequency = frequency.to_std().unwrap_or_default();
+        while proceed(self) {
+            if let Ok(errors) = self.poll() {
+                for error in errors {
+                    log::error!("{}", error);
+                }
+            }
+            self.attempt_routines();
+
+            if let Some(watchdog) = watchdog {
+                watchdog.heartbeat();
+            }
+
+            let until_poll = self.time_until_poll().to_std().unwrap_or_default();
+            std::thread::sleep(until_poll.min(frequency));
+        }
+    }
+
+    /// Getter for `policy`
+    ///
+    /// # Returns
+    ///
+    /// [`PollPolicy`] used by [`Group::poll()`] when intervals have been missed
+    pub fn poll_policy(&self) -> PollPolicy {
+        self.policy
+    }
+
+    /// Setter for `policy`
+    ///
+    /// # Parameters
+    ///
+    /// - `policy`: Handling of missed intervals by [`Group::poll()`]
+    pub fn set_poll_policy(&mut self, policy: PollPolicy) {
+        self.policy = policy
+    }
+
+    /// Getter for `align`
+    ///
+    /// # See Also
+    ///
+    /// - [`Group::set_align_to_interval()`]
+    pub fn align_to_interval(&self) -> bool {
+        self.align
+    }
+
+    /// Setter for `align`
+    ///
+    /// By default, each poll is scheduled one interval after the previous one, so that a delayed
+    /// caller shifts all following polls. When aligned, polls are instead scheduled at fixed
+    /// wall-clock boundaries which are multiples of `interval` since the Unix epoch (eg: `:00`,
+    /// `:05`, `:10` of each minute for an interval of 5 seconds). A poll executed late is still
+    /// recorded at the time it was actually read, but the following poll remains on a boundary.
+    ///
+    /// # Falling Behind
+    ///
+    /// When a caller misses one or more boundaries, [`Group::poll_policy()`] applies:
+    /// [`PollPolicy::Skip`] reads once and resumes at the next future boundary, while
+    /// [`PollPolicy::CatchUp`] reads once per missed boundary on consecutive calls.
+    ///
+    /// # Parameters
+    ///
+    /// - `enabled`: Whether polls should be aligned to interval boundaries
+    pub fn set_align_to_interval(&mut self, enabled: bool) {
+        self.align = enabled
+    }
+}
+
+/// Only save and load log data since [`Group`] is statically initialized
+/// If `&None` is given to either methods, then current directory is used.
+impl Persistent for Group {
+    /// Save all device logs and audit log
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any single save fails. However, failure is silent and
+    /// does not prevent saving other device logs.
+    ///
+    /// Failed device saves are first retried according to [`Group::set_save_retry()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics when any single input or output device cannot be locked.
+    ///
+    /// # Returns
+    ///
+    /// A [`Result`] containing:
+    ///
+    /// - `Ok` that is empty when saving occurred without error.
+    /// - `Err` containing the first error stored. There may be more errors that were
+    ///   not returned. An error occurring does not halt saving other logs.
+    fn save(&self) -> Result<(), ErrorType> {
+        check_results(&self.save_results(None, None))
+    }
+
+    /// Load all device logs and audit log
+    ///
+    /// Loaded audit events are placed before any events recorded since initialization. A missing
+    /// audit log file is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any single load fails. However, failure is silent and does not prevent
+    /// loading other device logs.
+    ///
+    /// # Panics
+    ///
+    /// Panics when any single input or output device cannot be locked.
+    ///
+    /// # Returns
+    ///
+    /// A [`Result`] containing:
+    ///
+    /// - `Ok` that is empty when loading occurred without error.
+    /// - `Err` containing the first error stored. There may be more errors that were
+    ///   not returned. An error occurring does not halt loading other logs.
+    fn load(&mut self) -> Result<(), ErrorType> {
+        check_results(&self.load_results(None))
+    }
+
+    /// Save all device logs and audit log beneath an explicit root directory
+    ///
+    /// `path` is used in place of [`Group::root_dir()`] and the directory structure of the group,
+    /// its devices, and subgroups is recreated beneath it. This is useful for writing a snapshot
+    /// to a backup directory. Subgroups are always nested within the directory of this group, even
+    /// if they have an explicit root. Root directory of group is not changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics when any single input or output device cannot be locked.
+    ///
+    /// # Returns
+    ///
+    /// A [`Result`] containing:
+    ///
+    /// - `Ok` that is empty when saving occurred without error.
+    /// - `Err` containing the first error that occurred. Remaining errors are logged. An error
+    ///   occurring does not halt saving other logs.
+    ///
+    /// # See Also
+    ///
+    /// - [`Group::save_all()`] for returning every error
+    fn save_to(&self, path: &Path) -> Result<(), ErrorType> {
+        first_error(self.save_results(Some(path), None))
+    }
+
+    /// Load all device logs and audit log from beneath an explicit root directory
+    ///
+    /// This is the counterpart of [`Group::save_to()`] and expects the same directory structure.
+    ///
+    /// # Panics
+    ///
+    /// Panics when any single input or output device cannot be locked.
+    ///
+    /// # Returns
+    ///
+    /// A [`Result`] containing:
+    ///
+    /// - `Ok` that is empty when loading occurred without error.
+    /// - `Err` containing the first error that occurred. Remaining errors are logged. An error
+    ///   occurring does not halt loading other logs.
+    ///
+    /// # See Also
+    ///
+    /// - [`Group::load_all()`] for returning every error
+    fn load_from(&mut self, path: &Path) -> Result<(), ErrorType> {
+        first_error(self.load_results(Some(path)))
+    }
+}
+
+impl Group {
+    /// Save all device logs and audit log, returning every error
+    ///
+    /// This is the detailed counterpart of [`Persistent::save()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics when any single input or output device cannot be locked.
+    ///
+    /// # Returns
+    ///
+    /// A [`Result`] containing:
+    ///
+    /// - `Ok` when all saves succeeded
+    /// - `Err` with all errors that occurred. An error occurring does not halt saving other logs.
+    pub fn save_all(&self) -> Result<(), Vec<ErrorType>> {
+        into_errors(self.save_results(None, None))
+    }
+
+    /// Load all device logs and audit log, returning every error
+    ///
+    /// This is the detailed counterpart of [`Persistent::load()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics when any single input or output device cannot be locked.
+    ///
+    /// # Returns
+    ///
+    /// A [`Result`] containing:
+    ///
+    /// - `Ok` when all loads succeeded
+    /// - `Err` with all errors that occurred. An error occurring does not halt loading other logs.
+    pub fn load_all(&mut self) -> Result<(), Vec<ErrorType>> {
+        into_errors(self.load_results(None))
+    }
+
+    /// Save all device logs and audit log using a small pool of threads
+    ///
+    /// Device logs of this group and all subgroups are distributed among at most
+    /// [`SAVE_THREADS`] worker threads, which is useful when many devices are stored on slow
+    /// storage. Audit logs are saved on the calling thread. Results are aggregated by
+    /// [`check_results()`] in the same manner as [`Persistent::save()`].
+    ///
+    /// Since every device log is written to a distinct file, the file backend must remain safe
+    /// under concurrent writes to distinct files.
+    ///
+    /// # Panics
+    ///
+    /// Panics when any single input or output device cannot be locked.
+    ///
+    /// # Returns
+    ///
+    /// A [`Result`] containing:
+    ///
+    /// - `Ok` that is empty when saving occurred without error.
+    /// - `Err` containing the first error stored. An error occurring does not halt saving other
+    ///   logs.
+    pub fn save_concurrent(&self) -> Result<(), ErrorType> {
+        let mut jobs = Vec::new();
+        let mut results = Vec::new();
+        self.collect_save_jobs(&mut jobs, &mut results, None);
+
+        let workers = thread::available_parallelism()
+            .map_or(1, |count| count.get())
+            .min(SAVE_THREADS)
+            .min(jobs.len());
+        let next = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| scope.spawn(|| {
+                    let mut results = Vec::new();
+                    while let Some((job, retry)) = jobs.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        results.push(job.save(*retry));
+                    }
+                    results
+                }))
+                .collect();
+
+            for handle in handles {
+                let saved = handle.join().expect("Save thread panicked");
+                results.extend(saved.into_iter()
+                    .map(|result| result.map_err(|e| Box::new(e) as ErrorType)));
+            }
+        });
+
+        self.collect_modes(&mut results, None);
+
+        check_results(&results)
+    }
+
+    /// Gather device logs of self and subgroups, saving audit logs immediately
+    ///
+    /// `inherited` is the mode of the nearest parent which manages permissions.
+    fn collect_save_jobs(&self, jobs: &mut Vec<(SaveJob, (u32, Duration))>, results: &mut Vec<Result<(), ErrorType>>, inherited: Option<u32>) {
+        let mode = self.mode.or(inherited);
+        let retry = self.save_retry.unwrap_or((0, Duration::zero()));
+        for device in self.inputs.values() {
+            set_log_mode(&*device.try_lock().expect("Could not lock input"), mode);
+            jobs.push((SaveJob::Input(device.clone()), retry));
+        }
+        for device in self.outputs.values() {
+            set_log_mode(&*device.try_lock().expect("Could not lock output"), mode);
+            jobs.push((SaveJob::Output(device.clone()), retry));
+        }
+
+        results.push(self.save_audit(None, mode));
+
+        for subgroup in self.subgroups.iter() {
+            subgroup.collect_save_jobs(jobs, results, mode);
+        }
+    }
+
+    /// Apply permissions of this group and all subgroups
+    ///
+    /// `inherited` is the mode of the nearest parent which manages permissions.
+    fn collect_modes(&self, results: &mut Vec<Result<(), ErrorType>>, inherited: Option<u32>) {
+        results.push(self.apply_mode(None, inherited));
+        for subgroup in self.subgroups.iter() {
+            subgroup.collect_modes(results, self.mode.or(inherited));
+        }
+    }
+
+    /// Save inputs, outputs, audit log, then subgroups
+    ///
+    /// # Parameters
+    ///
+    /// - `root`: Directory used in place of [`Group::root_dir()`]. If `None`, default locations
+    ///   are used.
+    /// - `inherited`: Mode of the nearest parent which manages permissions
+    fn save_results(&self, root: Option<&Path>, inherited: Option<u32>) -> Vec<Result<(), ErrorType>> {
+        let mut results = Vec::new();
+        let dir = root.map(|root| root.join(self.dir_name()));
+        let mode = self.mode.or(inherited);
+
+        let retry = self.save_retry.unwrap_or((0, Duration::zero()));
+
+        for device in self.inputs.values() {
+            set_log_mode(&*device.try_lock().expect("Could not lock input"), mode);
+            results.push(save_device(device, retry, |binding| match &dir {
+                Some(dir) => binding.save_to(&dir.join(binding.dir_name())),
+                None => binding.save(),
+            }));
+        }
+
+        for device in self.outputs.values() {
+            set_log_mode(&*device.try_lock().expect("Could not lock output"), mode);
+            results.push(save_device(device, retry, |binding| match &dir {
+                Some(dir) => binding.save_to(&dir.join(binding.dir_name())),
+                None => binding.save(),
+            }));
+        }
+
+        results.push(self.save_audit(dir.as_deref(), mode));
+
+        for subgroup in self.subgroups.iter() {
+            results.append(&mut subgroup.save_results(dir.as_deref(), mode));
+        }
+
+        results.push(self.apply_mode(dir.as_deref(), inherited));
+
+        results
+    }
+
+    /// Apply permissions set by [`Group::init_dir_with_mode()`] to group directory and its contents
+    ///
+    /// Subgroup directories are skipped since subgroups apply their own, or inherited, mode.
+    ///
+    /// # Parameters
+    ///
+    /// - `dir`: Directory used in place of [`Group::full_path()`]
+    /// - `inherited`: Mode of the nearest parent which manages permissions
+    fn apply_mode(&self, dir: Option<&Path>, inherited: Option<u32>) -> Result<(), ErrorType> {
+        match self.mode.or(inherited) {
+            Some(mode) => {
+                let subgroups: Vec<PathBuf> = self.subgroups.iter()
+                    .map(|subgroup| match dir {
+                        Some(dir) => dir.join(subgroup.dir_name()),
+                        None => subgroup.full_path(),
+                    })
+                    .collect();
+                let dir = dir.map_or_else(|| self.full_path(), Path::to_path_buf);
+                set_permissions_recursive(&dir, mode, &subgroups).map_err(FilesystemError::from)?;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Load outputs, inputs, audit log, then subgroups
+    ///
+    /// # Parameters
+    ///
+    /// - `root`: Directory used in place of [`Group::root_dir()`]. If `None`, default locations
+    ///   are used.
+    fn load_results(&mut self, root: Option<&Path>) -> Vec<Result<(), ErrorType>> {
+        let mut results = Vec::new();
+        let dir = root.map(|root| root.join(self.dir_name()));
+
+        for device in self.outputs.values() {
+            let mut binding = device.try_lock().unwrap();
+            results.push(match &dir {
+                Some(dir) => {
+                    let path = dir.join(binding.dir_name());
+                    binding.load_from(&path)
+                }
+                None => binding.load(),
+            });
+        }
+
+        for device in self.inputs.values() {
+            let mut binding = device.try_lock().unwrap();
+            results.push(match &dir {
+                Some(dir) => {
+                    let path = dir.join(binding.dir_name());
+                    binding.load_from(&path)
+                }
+                None => binding.load(),
+            });
+        }
+
+        results.push(self.load_audit(dir.as_deref()));
+
+        for subgroup in self.subgroups.iter_mut() {
+            results.append(&mut subgroup.load_results(dir.as_deref()));
+        }
+
+        results
+    }
+
+    /// Write audit log as JSON
+    ///
+    /// # Parameters
+    ///
+    /// - `dir`: Directory used in place of [`Group::full_path()`]
+    /// - `mode`: Permissions of created file and directories
+    fn save_audit(&self, dir: Option<&Path>, mode: Option<u32>) -> Result<(), ErrorType> {
+        let writer = BufWriter::new(writable_or_create_with_mode(self.audit_path(dir), mode).map_err(FilesystemError::from)?);
+        serde_json::to_writer(writer, &self.audit).map_err(FilesystemError::from)?;
+        Ok(())
+    }
+
+    /// Read audit log and prepend to events recorded since initialization
+    ///
+    /// # Parameters
+    ///
+    /// - `dir`: Directory used in place of [`Group::full_path()`]
+    fn load_audit(&mut self, dir: Option<&Path>) -> Result<(), ErrorType> {
+        let path = self.audit_path(dir);
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let reader = BufReader::new(File::open(path).map_err(FilesystemError::from)?);
+        let mut loaded: Vec<AuditEvent> = serde_json::from_reader(reader)
+            .map_err(FilesystemError::from)?;
+
+        loaded.append(&mut self.audit);
+        self.audit = loaded;
+        Ok(())
+    }
+}
+
+/// Set retention policy of log belonging to `device`
+///
+/// Log is skipped if it is currently locked.
+fn apply_retention<D: Device>(device: &D, (max_age, max_events): (Option<Duration>, Option<usize>)) {
+    if let Some(log) = device.log() {
+        match log.try_lock() {
+            Ok(mut log) => { log.set_retention_ref(max_age, max_events); },
+            Err(_) => log::warn!("Could not set retention policy of log for {}: log is locked", device.metadata()),
+        }
+    }
+}
+
+/// Set retention policy of log belonging to deferred `device`
+///
+/// Device is skipped if it is currently locked.
+fn apply_deferred_retention<D: Device>(device: &Def<D>, retention: (Option<Duration>, Option<usize>)) {
+    match device.try_lock() {
+        Ok(device) => apply_retention(&*device, retention),
+        Err(_) => log::warn!("Could not set retention policy: device is locked"),
+    }
+}
+
+/// Rename `device` and move existing log file, rolling back name if move fails
+fn rename<D>(device: &Def<D>, name: String) -> Result<(), ErrorType>
+where
+    D: Device + Directory
+{
+    let mut device = device.try_lock().map_err(|e| e.to_string())?;
+
+    let old_name = device.name().clone();
+    let old_path = device.log().and_then(|log| {
+        let log = log.try_lock().unwrap();
+        log.dir().map(|_| log.full_path())
+    });
+
+    set_device_name(&mut *device, name);
+
+    if let Some(old_path) = old_path.filter(|path| path.exists()) {
+        let new_path = {
+            let log = device.log().unwrap();
+            let log = log.try_lock().unwrap();
+            log.full_path()
+        };
+
+        let moved = std::fs::create_dir_all(new_path.parent().unwrap())
+            .and_then(|_| std::fs::rename(&old_path, &new_path));
+        if let Err(e) = moved {
+            set_device_name(&mut *device, old_name);
+            return Err(Box::new(e));
+        }
+
+        // previous device directory is only removed when empty
+        let _ = std::fs::remove_dir(old_path.parent().unwrap());
+    }
+
+    Ok(())
+}
+
+/// Update name of `device`, the metadata of its log, and the directory of its log
+fn set_device_name<D>(device: &mut D, name: String)
+where
+    D: Device + Directory
+{
+    device.set_name(name);
+
+    if let Some(log) = device.log() {
+        let mut log = log.try_lock().unwrap();
+        *log = std::mem::take(&mut *log).set_metadata(device.metadata().clone());
+    }
+    if let Some(parent) = device.parent_dir() {
+        device.set_parent_dir_ref(parent);
+    }
+}
+
+/// Create files of device log with `mode`, leaving log unchanged when `mode` is `None`
+fn set_log_mode<D: Device>(device: &D, mode: Option<u32>) {
+    if mode.is_none() {
+        return;
+    }
+    if let Some(log) = device.log() {
+        log.try_lock().expect("Could not lock log").set_mode_ref(mode);
+    }
+}
+
+/// Device whose log is saved by a worker thread in [`Group::save_concurrent()`]
+enum SaveJob {
+    Input(Def<Input>),
+    Output(Def<Output>),
+}
+
+impl SaveJob {
+    /// Save device log, retrying failed attempts
+    ///
+    /// Errors are converted to [`FilesystemError::SaveError`] since [`ErrorType`] cannot be sent
+    /// between threads.
+    ///
+    /// # Parameters
+    ///
+    /// - `retry`: Maximum number of retries and delay before first retry
+    fn save(&self, retry: (u32, Duration)) -> Result<(), FilesystemError> {
+        let (name, result) = match self {
+            Self::Input(device) => {
+                let name = device.try_lock().expect("Could not lock input").name().clone();
+                (name, save_device(device, retry, |binding| binding.save()))
+            }
+            Self::Output(device) => {
+                let name = device.try_lock().expect("Could not lock output").name().clone();
+                (name, save_device(device, retry, |binding| binding.save()))
+            }
+        };
+        result.map_err(|e| FilesystemError::SaveError {name, msg: e.to_string()})
+    }
+}
+
+/// Save log of device, retrying failed attempts with exponential backoff
+///
+/// `device` is only locked during each attempt, so that it may be read or written by other
+/// threads while waiting to retry.
+///
+/// # Parameters
+///
+/// - `device`: Device whose log is saved
+/// - `retry`: Maximum number of retries and delay before first retry
+/// - `save`: Single attempt at saving log of locked device
+///
+/// # Panics
+///
+/// If `device` is poisoned
+fn save_device<D, F>(device: &Def<D>, retry: (u32, Duration), mut save: F) -> Result<(), ErrorType>
+where
+    D: Device,
+    F: FnMut(&D) -> Result<(), ErrorType>,
+{
+    let (retries, backoff) = retry;
+    let context = format!("Save of {}", device.lock().expect("Could not lock device").metadata());
+    retry_with_backoff(&context, retries, backoff, || {
+        save(&device.lock().expect("Could not lock device"))
+    })
+}
+
+/// Escape special characters of InfluxDB line protocol
+///
+/// # Parameters
+///
+/// - `text`: Measurement, tag value, or string field value
+/// - `special`: Characters which are prefixed with a backslash
+fn influx_escape(text: &str, special: &[char]) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if special.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Render value as an InfluxDB line protocol field
+///
+/// # Returns
+///
+/// `Some` with `value` field, or `text` field for [`RawValue::Text`]. `None` if value is not
+/// finite.
+fn influx_field(value: &RawValue) -> Option<String> {
+    match value {
+        RawValue::Binary(value) => Some(format!("value={}", u8::from(*value))),
+        RawValue::Float(value) | RawValue::Percent(value) => {
+            value.is_finite().then(|| format!("value={}", value))
+        }
+        RawValue::Text(text) => Some(format!("text=\"{}\"", influx_escape(text, &['"', '\\']))),
+        value => Some(format!("value={}", value)),
+    }
+}
+
+/// Consume `results`, keeping only errors
+///
+/// Unlike [`crate::helpers::check_results()`], no errors are discarded so that callers can report
+/// exactly which operations failed.
+fn into_errors(results: Vec<Result<(), ErrorType>>) -> Result<(), Vec<ErrorType>> {
+    let errors: Vec<ErrorType> = results.into_iter()
+        .filter_map(Result::err)
+        .collect();
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors),
+    }
+}
+
+/// Return first error of `results`, logging any remaining errors
+fn first_error(results: Vec<Result<(), ErrorType>>) -> Result<(), ErrorType> {
+    into_errors(results).map_err(|errors| {
+        let mut errors = errors.into_iter();
+        let first = errors.next().unwrap();
+        errors.for_each(|e| log::error!("{}", e));
+        first
+    })
+}
+
+impl Name for Group {
+    /// Getter for `name`
+    ///
+    /// # Returns
+    ///
+    /// Immutable reference to `name`
     fn name(&self) -> &String {
         &self.name
     }
 
-    /// Setter for `name`
-    ///
-    /// # Parameters
-    ///
-    /// - `name`: new name for group. Uses `Into<_>` to coerce into `String`.
-    fn set_name<S>(&mut self, name: S)
-        where
-            S: Into<String>
-    {
-        self.name = name.into();
+    /// Setter for `name`
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: new name for group. Uses `Into<_>` to coerce into `String`.
+    fn set_name<S>(&mut self, name: S)
+        where
+            S: Into<String>
+    {
+        self.name = name.into();
+    }
+}
+
+impl Directory for Group {
+    fn parent_dir(&self) -> Option<PathBuf> {
+        Some(self.root_dir().clone().deref())
+    }
+
+    fn set_parent_dir_ref<P>(&mut self, path: P) -> &mut Self
+        where
+            Self: Sized,
+            P: AsRef<Path>,
+    {
+        self.set_root_ref(path)
+    }
+}
+
+impl RootDirectory for Group {
+    /// Getter for `root_path`
+    ///
+    /// This field represents the top-most directory and is where all dedicated directories
+    /// for [`Group`]'s are located. For retrieving a path to save or retrieve data,
+    /// use [`Group::full_path()`].
+    ///
+    /// # Returns
+    ///
+    /// `Option` of [`RootPath`] representing root data path of [`Group`] if set.
+    fn root_dir(&self) -> RootPath {
+        self.root.clone()
+    }
+
+    /// Setter for `root_path`
+    ///
+    /// This does not take ownership of `self`, unlike [`Group::set_root()`].
+    ///
+    /// Propagates changes to internal device containers using [`DeviceContainer::set_parent_dir()`],
+    /// and to subgroups which inherit their root path. Root path is marked as explicitly set so
+    /// that it is not overridden by [`Group::push_subgroup()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `root`: New path to global root dir
+    fn set_root_ref<P>(&mut self, path: P) -> &mut Self
+        where
+            P: AsRef<Path>
+    {
+        self.inherit_root(path);
+        self.explicit_root = true;
+
+        self
+    }
+}
+
+/// Find a JSON log file within `dir`
+///
+/// # Returns
+///
+/// First log file in lexical order, or `None` if `dir` does not contain a log file
+fn find_log_file(dir: &Path) -> Result<Option<PathBuf>, ErrorType> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.starts_with(LOG_FN_PREFIX) && name.ends_with(FILETYPE)
+        })
+        .collect();
+    files.sort();
+    Ok(files.into_iter().next())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use std::fs::remove_dir_all;
+    use std::path::{Path, PathBuf};
+
+    use crate::clock::MockClock;
+    use crate::errors::{ContainerError, DeviceError};
+    use crate::helpers::Def;
+    use crate::io::{Device, DeviceGetters, DeviceType, Input, IODirection, IOEvent, IOKind, Output, RawValue};
+    use crate::settings::{data_root, Settings, FAULT_THRESHOLD};
+    use crate::storage::{AuditAction, Chronicle, ConfigChange, Directory, Document, Group, Log, MergedEvent, Persistent, PollPolicy, RootDirectory, RootPath};
+    use crate::name::Name;
+
+    const DIR_PATH: &str = "/tmp/sensd_tests";
+
+    #[test]
+    /// Test that constructor accepts `name` as `&str` or `String`
+    fn new_name_parameter() {
+        Group::new("as &str");
+        Group::new(String::from("as String"));
+    }
+
+    #[test]
+    /// Test that alternate constructor sets root
+    fn with_root() {
+
+        let group = Group::with_root(
+            "",
+            DIR_PATH);
+        assert_eq!(RootPath::from(DIR_PATH), group.root_dir());
+    }
+
+    #[test]
+    fn with_interval() {
+        let interval = Duration::nanoseconds(30);
+
+        let group = Group::with_interval(
+            "",
+            interval);
+        assert!(interval.eq(group.interval()))
+    }
+
+    /// Count number of consecutive polls executed by a caller which has missed `missed` intervals
+    fn polls_after_delay(policy: PollPolicy, missed: i32) -> usize {
+        let clock = MockClock::new(Utc::now());
+        let interval = Duration::seconds(1);
+        let mut group = Group::with_interval("", interval);
+        group.set_poll_policy(policy);
+        group.push_input(Input::mock("", 0, RawValue::default()));
+
+        // simulate delayed caller
+        group.last_execution = clock.now() - interval * missed;
+
+        let mut count = 0;
+        while group.poll().is_ok() {
+            count += 1;
+            assert!(count <= missed as usize, "Runaway polling");
+        }
+        count
+    }
+
+    #[test]
+    fn poll_skips_missed_intervals() {
+        assert_eq!(PollPolicy::Skip, Group::new("").poll_policy());
+
+        assert_eq!(1, polls_after_delay(PollPolicy::Skip, 10));
+    }
+
+    #[test]
+    fn poll_catches_up_missed_intervals() {
+        assert_eq!(10, polls_after_delay(PollPolicy::CatchUp, 10));
+    }
+
+    #[test]
+    fn align_to_interval() {
+        let interval = Duration::hours(1);
+        let is_aligned = |time: DateTime<Utc>| time.timestamp() % 3600 == 0 && time.timestamp_subsec_nanos() == 0;
+
+        // unaligned starting time
+        let clock = MockClock::new(Utc.with_ymd_and_hms(2023, 1, 1, 12, 20, 0).unwrap());
+        let mut group = Group::with_interval("", interval);
+        assert!(!group.align_to_interval());
+        group.set_align_to_interval(true);
+        group.push_input(Input::mock("", 0, RawValue::default()));
+
+        // first poll is due immediately
+        assert_eq!(Duration::zero(), group.time_until_poll());
+        group.poll().unwrap();
+        assert!(is_aligned(group.last_execution));
+        assert!(is_aligned(group.next_execution()));
+        assert!(group.time_until_poll() <= interval);
+        assert!(group.poll().is_err());
+
+        // missed boundaries resume on a boundary
+        for policy in [PollPolicy::Skip, PollPolicy::CatchUp] {
+            group.set_poll_policy(policy);
+            group.last_execution = clock.now() - interval * 3 - Duration::minutes(20);
+            group.poll().unwrap();
+            assert!(is_aligned(group.last_execution));
+        }
+
+        // unaligned schedule is relative to last execution
+        group.set_align_to_interval(false);
+        group.last_execution = clock.now() - Duration::minutes(20);
+        assert_eq!(group.last_execution + interval, group.next_execution());
+    }
+
+    #[test]
+    fn save_to_and_load_from() {
+        const PATH: &str = "/tmp/sensd_tests/save_to";
+        const BACKUP: &str = "/tmp/sensd_tests/save_to_backup";
+
+        let mut group = Group::with_root("group", PATH);
+        group.push_input(Input::mock("a", 0, RawValue::default()).init_log());
+        let mut subgroup = Group::new("sub");
+        subgroup.push_input(Input::mock("b", 1, RawValue::default()).init_log());
+        group.push_subgroup(subgroup);
+
+        group.inputs.get(&0).unwrap().try_lock().unwrap().read().unwrap();
+        group.subgroups()[0].inputs.get(&1).unwrap().try_lock().unwrap().read().unwrap();
+        group.save_to(Path::new(BACKUP)).unwrap();
+
+        // default location is untouched
+        assert!(!Path::new(PATH).exists());
+        assert!(Path::new(BACKUP).join("group").join("sub").exists());
+
+        let mut restored = Group::with_root("group", PATH);
+        restored.push_input(Input::mock("a", 0, RawValue::default()).init_log());
+        let mut subgroup = Group::new("sub");
+        subgroup.push_input(Input::mock("b", 1, RawValue::default()).init_log());
+        restored.push_subgroup(subgroup);
+
+        assert!(restored.load_results(Some(Path::new(BACKUP))).iter().all(Result::is_ok));
+        assert_eq!(1, restored.total_event_count());
+        assert_eq!(1, restored.subgroups()[0].total_event_count());
+        // device added events are recorded in audit log
+        assert!(restored.audit_log().len() > 1);
+
+        // logs which already contain events are not overwritten
+        assert!(restored.load_from(Path::new(BACKUP)).is_err());
+
+        remove_dir_all(BACKUP).unwrap();
+
+        // unwritable backup directory
+        std::fs::create_dir_all(PATH).unwrap();
+        let blocked = Path::new(PATH).join("blocked");
+        std::fs::write(&blocked, "").unwrap();
+        assert!(group.save_to(&blocked).is_err());
+
+        remove_dir_all(PATH).unwrap();
+    }
+
+    #[test]
+    fn from_dir() {
+        const PATH: &str = "/tmp/sensd_tests/from_dir";
+
+        let mut group = Group::with_root("group", PATH);
+        group.push_input(Input::mock("a", 0, RawValue::default()).init_log());
+        group.push_output(Output::mock("b", 1).init_log());
+        let mut subgroup = Group::new("sub");
+        subgroup.push_input(Input::mock("c", 2, RawValue::default()).init_log());
+        group.push_subgroup(subgroup);
+
+        group.inputs.get(&0).unwrap().try_lock().unwrap().read().unwrap();
+        group.inputs.get(&0).unwrap().try_lock().unwrap().read().unwrap();
+        group.subgroups()[0].inputs.get(&2).unwrap().try_lock().unwrap().read().unwrap();
+        group.save().unwrap();
+
+        let restored = Group::from_dir(RootPath::from(PATH), "group").unwrap();
+        assert_eq!(group.to_config().inputs, restored.to_config().inputs);
+        assert_eq!(group.to_config().outputs, restored.to_config().outputs);
+        assert_eq!(1, restored.subgroups().len());
+        assert_eq!(group.subgroups()[0].to_config().inputs, restored.subgroups()[0].to_config().inputs);
+        assert_eq!(2, restored.total_event_count());
+        assert_eq!(1, restored.subgroups()[0].total_event_count());
+        assert_eq!(group.full_path(), restored.full_path());
+        assert_eq!(group.subgroups()[0].full_path(), restored.subgroups()[0].full_path());
+        // audit log is loaded rather than recorded during reconstruction
+        assert_eq!(group.audit_log().len(), restored.audit_log().len());
+
+        assert!(Group::from_dir(RootPath::from(PATH), "missing").is_err());
+
+        remove_dir_all(PATH).unwrap();
+    }
+
+    #[test]
+    fn clear_logs() {
+        const PATH: &str = "/tmp/sensd_tests/clear_logs";
+
+        let clock = MockClock::new(Utc::now());
+        let mut group = Group::with_root("group", PATH);
+        group
+            .push_input(Input::mock("a", 0, RawValue::default()).init_log())
+            .push_output(Output::new("b", 0, None).init_log());
+        let mut subgroup = Group::new("sub");
+        subgroup.push_input(Input::mock("c", 1, RawValue::default()).init_log());
+        group.push_subgroup(subgroup);
+
+        for _ in 0..3 {
+            group.inputs.get(&0).unwrap().try_lock().unwrap().read().unwrap();
+            clock.advance(Duration::seconds(1));
+        }
+        group.subgroups()[0].inputs.get(&1).unwrap().try_lock().unwrap().read().unwrap();
+        group.save().unwrap();
+        assert_eq!(4, group.total_event_count() + group.subgroups()[0].total_event_count());
+
+        // files are retained
+        assert_eq!(4, group.clear_logs(false));
+        assert_eq!(0, group.total_event_count());
+        assert_eq!(0, group.subgroups()[0].total_event_count());
+
+        let log = group.inputs.get(&0).unwrap().try_lock().unwrap().log().unwrap();
+        let path = log.try_lock().unwrap().full_path();
+        assert!(path.exists());
+
+        // files are deleted
+        group.inputs.get(&0).unwrap().try_lock().unwrap().read().unwrap();
+        assert_eq!(1, group.clear_logs(true));
+        assert!(!path.exists());
+
+        remove_dir_all(PATH).unwrap();
+    }
+
+    #[test]
+    fn reset_all() {
+        let mut group = Group::new("group");
+        group
+            .push_input(Input::mock("a", 0, RawValue::default()).init_log())
+            .push_output(Output::mock("b", 0));
+        let mut subgroup = Group::new("sub");
+        subgroup.push_input(Input::mock("c", 1, RawValue::default()));
+        group.push_subgroup(subgroup);
+
+        group.inputs.get(&0).unwrap().try_lock().unwrap().read().unwrap();
+        group.outputs.get(&0).unwrap().try_lock().unwrap().write(RawValue::Binary(true)).unwrap();
+        group.subgroups()[0].inputs.get(&1).unwrap().try_lock().unwrap().read().unwrap();
+
+        group.reset_all();
+
+        let input = group.inputs.get(&0).unwrap().try_lock().unwrap();
+        assert!(input.state().is_none());
+        assert!(input.last_read().is_none());
+        assert_eq!(1, input.log().unwrap().try_lock().unwrap().iter().count());
+        drop(input);
+        assert!(group.outputs.get(&0).unwrap().try_lock().unwrap().state().is_none());
+        assert!(group.subgroups()[0].inputs.get(&1).unwrap().try_lock().unwrap().state().is_none());
+    }
+
+    #[test]
+    fn devices_by_kind() {
+        let mut group = Group::new("");
+        group
+            .push_input(Input::new("pH 1", 0, IOKind::PH))
+            .push_input(Input::new("pH 2", 1, IOKind::PH))
+            .push_input(Input::new("temperature", 2, IOKind::Temperature))
+            .push_output(Output::new("pH dosing", 0, IOKind::PH));
+
+        assert_eq!(2, group.inputs_by_kind(IOKind::PH).len());
+        assert_eq!(1, group.outputs_by_kind(IOKind::PH).len());
+        assert!(group.outputs_by_kind(IOKind::Temperature).is_empty());
+
+        let directions: Vec<IODirection> = group.devices_by_kind(IOKind::PH).iter()
+            .map(DeviceType::direction)
+            .collect();
+        assert_eq!(vec![IODirection::In, IODirection::In, IODirection::Out], directions);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn init_dir_with_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        const PATH: &str = "/tmp/sensd_tests/init_dir_with_mode";
+        let _ = remove_dir_all(PATH);
+
+        let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        let mut group = Group::with_root("group", PATH);
+        group.init_dir_with_mode(0o770);
+        assert_eq!(Some(0o770), group.dir_mode());
+        assert_eq!(0o770, mode(&group.full_path()));
+
+        group.push_input(Input::new("input", 0, None).init_log());
+        group.inputs.get(&0).unwrap().try_lock().unwrap().push_to_log(&IOEvent::new(RawValue::Int(1)));
+        group.save().unwrap();
+
+        let binding = group.inputs.get(&0).unwrap().try_lock().unwrap();
+        let log = binding.log().unwrap();
+        let log = log.try_lock().unwrap();
+        assert_eq!(0o770, mode(&binding.full_path()));
+        assert_eq!(0o660, mode(&log.full_path()));
+        assert_eq!(Some(0o770), log.mode());
+        drop(log);
+        drop(binding);
+
+        // subgroups keep their own mode, otherwise inherit mode of parent
+        let mut restricted = Group::new("restricted");
+        restricted.push_input(Input::new("input", 1, None).init_log());
+        let mut shared = Group::new("shared");
+        shared.push_input(Input::new("input", 2, None).init_log());
+        group.push_subgroup(restricted).push_subgroup(shared);
+        group.subgroups_mut()[0].init_dir_with_mode(0o700);
+
+        for save in [Group::save, Group::save_concurrent] {
+            for subgroup in group.subgroups() {
+                let input = subgroup.inputs.values().next().unwrap().try_lock().unwrap();
+                input.push_to_log(&IOEvent::new(RawValue::Int(1)));
+            }
+            save(&group).unwrap();
+
+            let (restricted, shared) = (&group.subgroups()[0], &group.subgroups()[1]);
+            assert_eq!(0o700, mode(&restricted.full_path()));
+            assert_eq!(0o770, mode(&shared.full_path()));
+
+            let binding = restricted.inputs.get(&1).unwrap().try_lock().unwrap();
+            assert_eq!(0o700, mode(&binding.full_path()));
+            assert_eq!(0o600, mode(&binding.log().unwrap().try_lock().unwrap().full_path()));
+            let binding = shared.inputs.get(&2).unwrap().try_lock().unwrap();
+            assert_eq!(0o660, mode(&binding.log().unwrap().try_lock().unwrap().full_path()));
+        }
+
+        assert!(Group::new("").dir_mode().is_none());
+
+        remove_dir_all(PATH).unwrap();
+    }
+
+    #[test]
+    fn subscribe() {
+        use crate::action::actions::Threshold;
+        use crate::action::{Action, Trigger};
+        use crate::io::IdType;
+
+        let mut group = Group::new("");
+        group
+            .push_input(Input::mock("sensor", 0, RawValue::default()))
+            .push_input(Input::mock("probe", 1, RawValue::default()).init_publisher())
+            .push_output(Output::mock("pump", 2));
+
+        for id in [0, 1, 1] {
+            let action = Threshold::new("high", RawValue::Float(5.0), Trigger::GT);
+            group.subscribe(id, action.into_boxed()).unwrap();
+        }
+
+        let subscribers = |id: IdType| {
+            let binding = group.inputs.get(&id).unwrap().try_lock().unwrap();
+            binding.publisher().as_ref().map_or(0, |publisher| publisher.subscribers().len())
+        };
+        assert_eq!(1, subscribers(0));
+        assert_eq!(2, subscribers(1));
+
+        // outputs cannot be subscribed to
+        let action = Threshold::new("high", RawValue::Float(5.0), Trigger::GT);
+        assert!(group.subscribe(2, action.into_boxed()).is_err());
+
+        // locked input
+        let input = group.inputs.get(&0).unwrap().clone();
+        let _binding = input.try_lock().unwrap();
+        let action = Threshold::new("high", RawValue::Float(5.0), Trigger::GT);
+        assert!(group.subscribe(0, action.into_boxed()).is_err());
+    }
+
+    #[test]
+    fn poll_once_now() {
+        let clock = MockClock::new(Utc::now());
+        let mut group = Group::with_interval("", Duration::seconds(10));
+        group
+            .push_input(Input::mock("a", 0, RawValue::default()).init_log())
+            .push_input(Input::mock("b", 1, RawValue::default()).set_poll_interval(Some(Duration::hours(1))).init_log())
+            .push_input(Input::new("c", 2, None));
+        let mut subgroup = Group::with_interval("sub", Duration::hours(1));
+        subgroup.push_input(Input::mock("d", 3, RawValue::default()).init_log());
+        group.push_subgroup(subgroup);
+
+        assert!(group.poll().is_ok());
+
+        clock.advance(Duration::seconds(5));
+        let results = group.poll_once_now();
+        assert_eq!(4, results.len());
+        assert_eq!(1, results.iter().filter(|result| result.is_err()).count());
+        assert_eq!(4, group.total_event_count());
+        assert_eq!(2, group.subgroups()[0].total_event_count());
+
+        // schedule is restarted
+        assert_eq!(Duration::seconds(10), group.time_until_poll());
+        clock.advance(Duration::seconds(5));
+        assert!(group.poll().is_err());
+
+        group.pause();
+        assert!(group.poll_once_now().is_empty());
+    }
+
+    #[test]
+    fn dump_state() {
+        use crate::storage::StateDump;
+
+        let mut group = Group::new("main");
+        let output = Output::mock("pump", 1).init_log();
+        let routine = output.create_routine(RawValue::Binary(false), Duration::hours(1));
+        let input = Input::mock("probe", 0, RawValue::default()).init_log().init_publisher();
+        input.publisher().as_ref().unwrap().handler_ref().try_lock().unwrap().push(routine);
+        group
+            .push_input(input)
+            .push_input(Input::mock("locked", 2, RawValue::default()))
+            .push_output(output);
+        let mut subgroup = Group::new("sub");
+        subgroup.push_input(Input::mock("nested", 3, RawValue::default()));
+        group.push_subgroup(subgroup);
+
+        group.poll_once_now();
+
+        let locked = group.inputs.get(&2).unwrap().clone();
+        let _binding = locked.try_lock().unwrap();
+
+        let dump: StateDump = serde_json::from_str(&group.dump_state().unwrap()).unwrap();
+        assert_eq!("main", dump.name);
+        assert_eq!(vec!["input 2"], dump.unavailable);
+
+        assert_eq!(1, dump.inputs.len());
+        let probe = &dump.inputs[0];
+        assert_eq!("probe", probe.config.metadata.name);
+        assert_eq!(probe.state, probe.last_event.as_ref().map(|event| event.value.clone()));
+        assert_eq!(1, probe.routines.len());
+        assert_eq!(RawValue::Binary(false), probe.routines[0].value);
+        assert_eq!(1, dump.health.pending_routines);
+
+        assert_eq!(1, dump.outputs.len());
+        assert!(dump.outputs[0].last_event.is_none());
+
+        assert_eq!(1, dump.subgroups.len());
+        assert_eq!("nested", dump.subgroups[0].inputs[0].config.metadata.name);
+    }
+
+    #[test]
+    fn find_device() {
+        let mut group = Group::new("");
+        group
+            .push_input(Input::new("sensor", 0, None))
+            .push_output(Output::new("pump", 0, None))
+            .push_output(Output::new("valve", 1, None));
+
+        // inputs take precedence when ids are shared
+        match group.find_device(0) {
+            Some(DeviceType::Input(device)) => assert_eq!("sensor", device.try_lock().unwrap().name()),
+            _ => panic!("expected input"),
+        }
+        match group.find_device(1) {
+            Some(DeviceType::Output(device)) => assert_eq!("valve", device.try_lock().unwrap().name()),
+            _ => panic!("expected output"),
+        }
+        assert!(group.find_device(2).is_none());
+    }
+
+    #[test]
+    fn poll_latencies() {
+        let clock = MockClock::new(Utc::now());
+        let mut group = Group::new("");
+        group
+            .push_input(Input::mock("a", 0, RawValue::default()))
+            .push_input(Input::new("b", 1, None));
+        assert!(group.last_poll_latencies().is_empty());
+
+        group.poll().unwrap();
+        let latencies = group.last_poll_latencies();
+        assert_eq!(2, latencies.len());
+        assert!(latencies.values().all(|latency| *latency >= Duration::zero()));
+
+        clock.advance(*group.interval());
+        group.poll().unwrap();
+        assert!(group.read_latencies().values().all(|latency| latency.count == 2));
+    }
+
+    #[test]
+    fn poll_streaming() {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let mut group = Group::new("");
+        group
+            .push_input(Input::mock("a", 0, RawValue::default()))
+            .push_input(Input::new("b", 1, None));
+        let mut subgroup = Group::new("sub");
+        subgroup.push_input(Input::mock("c", 2, RawValue::default()));
+        group.push_subgroup(subgroup);
+
+        assert!(group.poll_streaming(&tx));
+
+        // receiver can be moved to another thread
+        let results = std::thread::spawn(move || rx.try_iter().collect::<Vec<_>>())
+            .join()
+            .unwrap();
+        assert_eq!(3, results.len());
+        assert_eq!(1, results.iter().filter(|result| result.is_err()).count());
+
+        // poll is not due
+        let (tx, rx) = std::sync::mpsc::channel();
+        assert!(!group.poll_streaming(&tx));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn time_until_poll() {
+        let clock = MockClock::new(Utc::now());
+        let interval = Duration::seconds(30);
+        let mut group = Group::with_interval("", interval);
+        assert_eq!(Duration::zero(), group.time_until_poll());
+
+        // overdue polls are clamped to zero
+        group.last_execution = clock.now() - interval * 3;
+        assert_eq!(Duration::zero(), group.time_until_poll());
+
+        group.last_execution = clock.now() - Duration::seconds(10);
+        assert_eq!(Duration::seconds(20), group.time_until_poll());
+
+        clock.advance(Duration::seconds(15));
+        assert_eq!(Duration::seconds(5), group.time_until_poll());
+    }
+
+    #[test]
+    fn audit_log() {
+        const PATH: &str = "/tmp/sensd_tests/audit_log";
+
+        let mut group = Group::with_root("group", PATH);
+        group
+            .push_input(Input::new("probe", 0, None))
+            .push_output(Output::new("pump", 0, None));
+
+        assert!(group.remove_input(0).is_some());
+        assert!(group.remove_input(0).is_none());
+
+        let log = group.audit_log();
+        assert_eq!(3, log.len());
+        assert_eq!((AuditAction::Added, IODirection::In, "probe"), (log[0].action, log[0].direction, log[0].name.as_str()));
+        assert_eq!((AuditAction::Added, IODirection::Out, "pump"), (log[1].action, log[1].direction, log[1].name.as_str()));
+        assert_eq!((AuditAction::Removed, IODirection::In, "probe"), (log[2].action, log[2].direction, log[2].name.as_str()));
+
+        group.save().unwrap();
+
+        // history is restored before new events
+        let mut restored = Group::with_root("group", PATH);
+        restored.push_output(Output::new("pump", 0, None));
+        restored.load().unwrap();
+
+        assert_eq!(4, restored.audit_log().len());
+        assert_eq!(group.audit_log(), &restored.audit_log()[..3]);
+
+        remove_dir_all(PATH).unwrap();
+    }
+
+    #[test]
+    fn rename_device() {
+        const PATH: &str = "/tmp/sensd_tests/rename_device";
+
+        let mut group = Group::with_root("group", PATH);
+        group.push_input(Input::mock("old name", 0, RawValue::default()).init_log());
+        group.inputs.get(&0).unwrap().try_lock().unwrap().read().unwrap();
+        group.save().unwrap();
+
+        let old_path = {
+            let binding = group.inputs.get(&0).unwrap().try_lock().unwrap();
+            let log = binding.log().unwrap();
+            let path = log.try_lock().unwrap().full_path();
+            path
+        };
+        assert!(old_path.exists());
+
+        assert!(group.rename_device(1, "missing").is_err());
+        group.rename_device(0, "new name").unwrap();
+
+        let binding = group.inputs.get(&0).unwrap().try_lock().unwrap();
+        assert_eq!("new name", binding.name());
+        let log = binding.log().unwrap();
+        let new_path = log.try_lock().unwrap().full_path();
+        assert_eq!("new name", log.try_lock().unwrap().name());
+        assert!(new_path.exists());
+        assert!(!old_path.exists());
+        assert!(!old_path.parent().unwrap().exists());
+        drop(binding);
+
+        // renamed log can be loaded
+        let mut restored = Group::with_root("group", PATH);
+        restored.push_input(Input::mock("new name", 0, RawValue::default()).init_log());
+        restored.load().unwrap();
+        assert_eq!(1, restored.total_event_count());
+
+        // outputs are searched
+        group.push_output(Output::new("old output", 1, None));
+        group.rename_device(1, "new output").unwrap();
+        assert_eq!("new output", group.outputs.get(&1).unwrap().try_lock().unwrap().name());
+
+        // id shared by an input and an output is ambiguous
+        group.push_output(Output::new("shared", 0, None));
+        let error = group.rename_device(0, "ambiguous").unwrap_err();
+        assert!(matches!(error.downcast_ref::<ContainerError>(), Some(ContainerError::KeyAmbiguous {..})));
+        assert_eq!("new name", group.inputs.get(&0).unwrap().try_lock().unwrap().name());
+
+        remove_dir_all(PATH).unwrap();
+    }
+
+    #[test]
+    fn rename_device_rolls_back() {
+        const PATH: &str = "/tmp/sensd_tests/rename_device_rolls_back";
+
+        let mut group = Group::with_root("group", PATH);
+        group.push_input(Input::mock("old name", 0, RawValue::default()).init_log());
+        group.inputs.get(&0).unwrap().try_lock().unwrap().read().unwrap();
+        group.save().unwrap();
+
+        // block creation of new device directory
+        std::fs::write(group.full_path().join("blocked"), "").unwrap();
+
+        assert!(group.rename_device(0, "blocked").is_err());
+
+        let binding = group.inputs.get(&0).unwrap().try_lock().unwrap();
+        assert_eq!("old name", binding.name());
+        let log = binding.log().unwrap();
+        assert!(log.try_lock().unwrap().exists());
+        drop(binding);
+
+        remove_dir_all(PATH).unwrap();
+    }
+
+    #[test]
+    fn subgroups() {
+        const PATH: &str = "/tmp/sensd_tests/subgroups";
+
+        let clock = MockClock::new(Utc::now());
+        let mut veg = Group::with_interval("veg", Duration::seconds(1));
+        veg.push_input(Input::mock("probe", 0, RawValue::default()).init_log());
+        let mut flower = Group::with_interval("flower", Duration::hours(1));
+        flower.push_input(Input::mock("probe", 0, RawValue::default()).init_log());
+        let other = Group::with_root("other", "/tmp/sensd_tests/other_root");
+
+        let mut controller = Group::with_interval("controller", Duration::hours(1));
+        controller
+            .push_subgroup(veg)
+            .push_subgroup(flower)
+            .push_subgroup(other);
+        controller.set_root_ref(PATH);
+
+        // subgroups inherit root unless explicitly set
+        let expected = PathBuf::from(PATH).join("controller").join("veg");
+        assert_eq!(expected, controller.subgroups()[0].full_path());
+        assert_eq!(PathBuf::from("/tmp/sensd_tests/other_root/other"), controller.subgroups()[2].full_path());
+        let binding = controller.subgroups()[0].inputs.get(&0).unwrap().try_lock().unwrap();
+        assert_eq!(expected.join("probe"), binding.full_path());
+        drop(binding);
+
+        // all groups are due
+        assert!(controller.poll().is_ok());
+        assert_eq!(2, controller.total_event_count() + controller.subgroups().iter()
+            .map(|group| group.total_event_count())
+            .sum::<usize>());
+
+        // only `veg` is due after its interval
+        assert!(controller.poll().is_err());
+        clock.advance(Duration::seconds(1));
+        assert_eq!(Duration::zero(), controller.time_until_poll());
+        assert!(controller.poll().is_ok());
+        assert_eq!(2, controller.subgroups()[0].total_event_count());
+        assert_eq!(1, controller.subgroups()[1].total_event_count());
+
+        controller.subgroups_mut()[2].set_root_ref(PATH);
+        controller.save().unwrap();
+        assert!(expected.join("probe").exists());
+
+        remove_dir_all(PATH).unwrap();
     }
-}
 
-impl Directory for Group {
-    fn parent_dir(&self) -> Option<PathBuf> {
-        Some(self.root_dir().clone().deref())
+    #[test]
+    fn enforce_retention() {
+        let mut settings = Settings::default();
+        settings.set_retention(None, Some(2));
+
+        let mut group = Group::with_settings("", &settings);
+        assert_eq!((None, Some(2)), group.retention());
+        group.push_input(Input::mock("", 0, RawValue::default()).init_log());
+        group.push_output(Output::new("", 0, None).init_log());
+
+        let clock = MockClock::new(Utc::now());
+        let now = clock.now();
+        for device in [group.inputs.get(&0).unwrap().try_lock().unwrap().log(),
+                       group.outputs.get(&0).unwrap().try_lock().unwrap().log()] {
+            let log = device.unwrap();
+            let mut log = log.try_lock().unwrap();
+            for offset in 0..5 {
+                log.push(IOEvent::with_timestamp(now - Duration::hours(offset), RawValue::Int(0))).unwrap();
+            }
+            assert_eq!((None, Some(2)), log.retention());
+        }
+
+        assert_eq!(6, group.enforce_retention());
+        assert_eq!(4, group.total_event_count());
+
+        group.set_retention(Some(Duration::minutes(30)), None);
+        assert_eq!(2, group.enforce_retention());
+        assert_eq!(2, group.total_event_count());
+
+        // locked devices and logs are skipped
+        let device = group.inputs.get(&0).unwrap().clone();
+        let binding = device.try_lock().unwrap();
+        let log = group.outputs.values().next().unwrap().try_lock().unwrap().log().unwrap();
+        let log_binding = log.try_lock().unwrap();
+
+        group.set_retention(None, Some(1));
+        assert_eq!((Some(Duration::minutes(30)), None), log_binding.retention());
+        assert_eq!((Some(Duration::minutes(30)), None), binding.log().unwrap().try_lock().unwrap().retention());
+        drop(log_binding);
+        drop(binding);
+
+        group.set_retention(None, Some(1));
+        assert_eq!((None, Some(1)), log.try_lock().unwrap().retention());
     }
 
-    fn set_parent_dir_ref<P>(&mut self, path: P) -> &mut Self
-        where
-            Self: Sized,
-            P: AsRef<Path>,
-    {
-        self.set_root_ref(path)
+    #[test]
+    fn config_round_trip() {
+        let mut child = Group::with_interval("child", Duration::seconds(30));
+        child.push_input(Input::new("probe", 0, IOKind::Temperature).init_log());
+
+        let mut group = Group::with_interval("group", Duration::milliseconds(1500));
+        group
+            .push_input(Input::new("b", 1, IOKind::PH))
+            .push_input(Input::new("a", 0, IOKind::EC).init_log())
+            .push_output(Output::new("pump", 0, IOKind::Flow))
+            .push_subgroup(child);
+
+        let config = group.to_config();
+        assert_eq!(1500, config.interval_ms);
+        assert_eq!(vec![0, 1], config.inputs.iter().map(|c| c.metadata.id).collect::<Vec<_>>());
+        assert_eq!(1, config.subgroups.len());
+
+        let rebuilt = Group::from_config(&config).unwrap();
+        assert_eq!(config, rebuilt.to_config());
+        assert_eq!(Duration::seconds(30), *rebuilt.subgroups()[0].interval());
     }
-}
 
-impl RootDirectory for Group {
-    /// Getter for `root_path`
-    ///
-    /// This field represents the top-most directory and is where all dedicated directories
-    /// for [`Group`]'s are located. For retrieving a path to save or retrieve data,
-    /// use [`Group::full_path()`].
-    ///
-    /// # Returns
-    ///
-    /// `Option` of [`RootPath`] representing root data path of [`Group`] if set.
-    fn root_dir(&self) -> RootPath {
-        self.root.clone()
+    #[test]
+    fn from_config_invalid() {
+        let mut group = Group::new("group");
+        group.push_input(Input::new("a", 0, IOKind::PH));
+        group.push_subgroup(Group::new("child"));
+
+        // duplicate id
+        let mut config = group.to_config();
+        config.inputs.push(Input::new("b", 0, IOKind::EC).to_config());
+        let error = Group::from_config(&config).err().unwrap();
+        assert!(matches!(error.downcast_ref::<ContainerError>(), Some(ContainerError::KeyExists {..})));
+
+        // direction mismatch within subgroup
+        let mut config = group.to_config();
+        config.subgroups[0].outputs.push(Input::new("b", 0, IOKind::EC).to_config());
+        let error = Group::from_config(&config).err().unwrap();
+        assert!(matches!(error.downcast_ref::<DeviceError>(), Some(DeviceError::DirectionMismatch {..})));
+
+        // bounds outside of unit range
+        let mut config = group.to_config();
+        config.inputs[0].metadata.bounds = Some((-3.0, 1000.0));
+        let error = Group::from_config(&config).err().unwrap();
+        assert!(matches!(error.downcast_ref::<DeviceError>(), Some(DeviceError::InvalidBounds {..})));
     }
 
-    /// Setter for `root_path`
-    ///
-    /// This does not take ownership of `self`, unlike [`Group::set_root()`].
-    ///
-    /// Propagates changes to internal device containers using [`DeviceContainer::set_parent_dir()`]
-    ///
-    /// # Parameters
-    ///
-    /// - `root`: New path to global root dir
-    fn set_root_ref<P>(&mut self, path: P) -> &mut Self
-        where
-            P: AsRef<Path>
-    {
-        let root = RootPath::from(path);
-        self.root = root.clone();
+    #[test]
+    fn diff_config() {
+        let mut group = Group::with_interval("group", Duration::seconds(1));
+        group
+            .push_input(Input::new("a", 0, IOKind::PH))
+            .push_input(Input::new("b", 1, IOKind::EC))
+            .push_output(Output::new("pump", 0, IOKind::Flow))
+            .push_subgroup(Group::new("child"));
 
-        self.inputs.set_parent_dir(root.clone());
-        self.outputs.set_parent_dir(root.clone());
+        assert!(group.diff_config(&group.to_config()).is_empty());
 
-        self
+        let mut config = group.to_config();
+        config.interval_ms = 2000;
+        config.inputs.remove(0);
+        config.inputs[0].metadata.name = String::from("renamed");
+        config.outputs.push(Output::new("light", 1, IOKind::Light).to_config());
+        config.subgroups[0].interval_ms = 500;
+
+        let current = group.to_config();
+        let changes = group.diff_config(&config);
+        assert_eq!(vec![
+            ConfigChange::Interval { current: 1000, other: 2000 },
+            ConfigChange::Removed(current.inputs[0].clone()),
+            ConfigChange::Modified { current: current.inputs[1].clone(), other: config.inputs[0].clone() },
+            ConfigChange::Added(config.outputs[1].clone()),
+            ConfigChange::Subgroup {
+                name: String::from("child"),
+                changes: vec![ConfigChange::Interval { current: current.subgroups[0].interval_ms, other: 500 }],
+            },
+        ], changes);
+
+        // drift of input settings is reported
+        let mut config = group.to_config();
+        config.inputs[0].input.as_mut().unwrap().validate = true;
+        assert!(matches!(group.diff_config(&config)[..], [ConfigChange::Modified {..}]));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use chrono::Duration;
-    use std::fs::remove_dir_all;
-    use std::path::{Path, PathBuf};
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml_round_trip() {
+        let mut group = Group::new("group");
+        group
+            .push_input(Input::new("probe", 0, IOKind::PH).init_log())
+            .push_output(Output::new("pump", 0, IOKind::Flow));
 
-    use crate::io::{Device, Input, IOKind, Output};
-    use crate::storage::{Directory, Group, RootDirectory, RootPath};
+        let yaml = group.export_config().unwrap();
+        assert!(yaml.contains("name: group"));
 
-    const DIR_PATH: &str = "/tmp/sensd_tests";
+        let imported = Group::import_config(&yaml).unwrap();
+        assert_eq!(group.to_config(), imported.to_config());
+
+        assert!(Group::import_config("name: [").is_err());
+
+        // hand-edited duplicate id is rejected
+        let duplicate = yaml.replacen("outputs:", "outputs:\n- metadata:\n    name: valve\n    id: 0\n    kind: Flow\n    direction: Out\n  log: false", 1);
+        let error = Group::import_config(&duplicate).err().unwrap();
+        assert!(matches!(error.downcast_ref::<ContainerError>(), Some(ContainerError::KeyExists {..})));
+    }
 
     #[test]
-    /// Test that constructor accepts `name` as `&str` or `String`
-    fn new_name_parameter() {
-        Group::new("as &str");
-        Group::new(String::from("as String"));
+    fn load_all_collects_errors() {
+        const PATH: &str = "/tmp/sensd_tests/load_all_collects_errors";
+
+        let mut group = Group::with_root("group", PATH);
+        group
+            .push_input(Input::new("a", 0, None).init_log())
+            .push_input(Input::new("b", 1, None).init_log())
+            .push_input(Input::new("c", 2, None));
+
+        // no log files exist for the two inputs with logs
+        assert_eq!(2, group.load_all().unwrap_err().len());
+
+        group.inputs.get(&0).unwrap().try_lock().unwrap().read().ok();
+        group.save_all().unwrap();
+
+        let mut restored = Group::with_root("group", PATH);
+        restored.push_input(Input::new("a", 0, None).init_log());
+        assert!(restored.load_all().is_ok());
+
+        remove_dir_all(PATH).unwrap();
     }
 
     #[test]
-    /// Test that alternate constructor sets root
-    fn with_root() {
+    fn save_concurrent() {
+        const PATH: &str = "/tmp/sensd_tests/save_concurrent";
 
-        let group = Group::with_root(
-            "",
-            DIR_PATH);
-        assert_eq!(RootPath::from(DIR_PATH), group.root_dir());
+        let mut group = Group::with_root("group", PATH);
+        for id in 0..8 {
+            group.push_input(Input::new(format!("input {id}"), id, None).init_log());
+        }
+        group.push_output(Output::new("output", 8, None).init_log());
+
+        group.save_concurrent().unwrap();
+
+        let mut restored = Group::with_root("group", PATH);
+        for id in 0..8 {
+            restored.push_input(Input::new(format!("input {id}"), id, None).init_log());
+        }
+        restored.push_output(Output::new("output", 8, None).init_log());
+        assert!(restored.load_all().is_ok());
+
+        remove_dir_all(PATH).unwrap();
     }
 
     #[test]
-    fn with_interval() {
-        let interval = Duration::nanoseconds(30);
+    fn save_retry() {
+        use std::fs::{create_dir_all, remove_file, File};
 
-        let group = Group::with_interval(
-            "",
-            interval);
-        assert!(interval.eq(group.interval()))
+        const PATH: &str = "/tmp/sensd_tests/save_retry";
+        let _ = remove_dir_all(PATH);
+
+        let mut group = Group::with_root("group", PATH);
+        group.push_input(Input::new("input", 0, None).init_log());
+        assert!(group.save_retry().is_none());
+
+        // block log directory with a file
+        let (dir, filename) = {
+            let binding = group.inputs.get(&0).unwrap().try_lock().unwrap();
+            let filename = binding.log().unwrap().try_lock().unwrap().filename();
+            (binding.full_path(), filename)
+        };
+        create_dir_all(dir.parent().unwrap()).unwrap();
+        File::create(&dir).unwrap();
+
+        // failure is recorded without retries
+        assert_eq!(1, group.save_all().unwrap_err().len());
+
+        // failure is recorded once when retries are exhausted
+        group.set_save_retry(2, Duration::milliseconds(1));
+        assert_eq!(Some((2, Duration::milliseconds(1))), group.save_retry());
+        assert_eq!(1, group.save_all().unwrap_err().len());
+
+        remove_file(&dir).unwrap();
+        group.save_concurrent().unwrap();
+        assert!(dir.join(&filename).is_file());
+
+        remove_dir_all(PATH).unwrap();
+    }
+
+    #[test]
+    fn save_device() {
+        let device = Input::new("input", 0, None).into_deferred();
+
+        // transient failures are retried
+        let mut attempts = 0;
+        let result = super::save_device(&device, (3, Duration::milliseconds(1)), |_| {
+            attempts += 1;
+            match attempts {
+                1 | 2 => Err("transient".into()),
+                _ => Ok(()),
+            }
+        });
+        assert!(result.is_ok());
+        assert_eq!(3, attempts);
+
+        // error of last attempt is returned
+        let mut attempts = 0;
+        let result = super::save_device(&device, (1, Duration::milliseconds(1)), |_| {
+            attempts += 1;
+            Err("persistent".into())
+        });
+        assert_eq!("persistent", result.unwrap_err().to_string());
+        assert_eq!(2, attempts);
+    }
+
+    #[test]
+    fn default_root() {
+        // environment is not mutated since tests run in parallel
+        assert_eq!(RootPath::from(data_root()), Group::new("").root_dir());
+
+        // explicit root takes precedence
+        assert_eq!(RootPath::from(DIR_PATH), Group::with_root("", DIR_PATH).root_dir());
+    }
+
+    #[test]
+    fn with_settings() {
+        let mut settings = Settings::default();
+        settings.set_root(DIR_PATH);
+        settings.set_interval(Duration::seconds(30));
+
+        let group = Group::with_settings("", &settings);
+        assert_eq!(Duration::seconds(30), *group.interval());
+        assert_eq!(RootPath::from(DIR_PATH), group.root_dir());
     }
 
     #[test]
@@ -599,6 +3903,426 @@ mod tests {
         group.push_output(Output::new("", 0, None));
     }
 
+    #[test]
+    fn with_capacity() {
+        let mut group = Group::with_capacity("name", 16);
+        assert_eq!("name", group.name());
+        assert!(group.inputs.capacity() >= 16);
+        assert!(group.outputs.capacity() >= 16);
+
+        group.push_input(Input::new("", 0, None));
+        assert_eq!(1, group.inputs.len());
+    }
+
+    #[test]
+    fn health() {
+        let clock = MockClock::new(Utc::now());
+        let mut group = Group::with_interval("name", Duration::seconds(1));
+        group
+            .push_input(Input::mock("", 0, RawValue::default()))
+            .push_input(Input::new("", 1, None))
+            .push_output(Output::mock("", 0));
+
+        let health = group.health();
+        assert_eq!(3, health.device_count);
+        assert_eq!(vec![0, 1], health.stale);
+        assert!(health.faulted.is_empty());
+        assert!(health.last_poll.is_none());
+
+        for _ in 0..FAULT_THRESHOLD {
+            clock.advance(Duration::seconds(1));
+            group.poll().unwrap();
+        }
+
+        let health = group.health();
+        assert_eq!(vec![1], health.faulted);
+        assert!(!health.is_healthy());
+        // no poll has fully succeeded
+        assert!(health.last_poll.is_none());
+
+        assert!(serde_json::to_string(&health).is_ok());
+    }
+
+    #[test]
+    fn device_poll_interval() {
+        let clock = MockClock::new(Utc::now());
+        let mut group = Group::with_interval("", Duration::milliseconds(50));
+        group
+            .push_input(Input::mock("flow", 0, RawValue::default()).set_poll_interval(Some(Duration::milliseconds(5))).init_log())
+            .push_input(Input::mock("temp", 1, RawValue::default()).init_log())
+            .push_input(Input::mock("slow", 2, RawValue::default()).set_poll_interval(Some(Duration::hours(1))).init_log());
+
+        // all devices are due initially
+        assert_eq!(Duration::zero(), group.time_until_poll());
+        assert!(group.poll().is_ok());
+
+        // only fast device is due before group interval elapses
+        assert_eq!(Duration::milliseconds(5), group.time_until_poll());
+        clock.advance(Duration::milliseconds(4));
+        assert!(group.poll().is_err());
+
+        for _ in 0..15 {
+            clock.advance(Duration::milliseconds(6));
+            assert!(group.poll().is_ok());
+        }
+
+        let counts = group.event_count_by_device();
+        assert_eq!(16, counts[&(IODirection::In, 0)]);
+        assert_eq!(2, counts[&(IODirection::In, 1)]);
+        assert_eq!(1, counts[&(IODirection::In, 2)]);
+    }
+
+    #[test]
+    fn pause() {
+        let clock = MockClock::new(Utc::now());
+        let mut group = Group::with_interval("", Duration::milliseconds(5));
+        group.set_poll_policy(PollPolicy::CatchUp);
+
+        let output = Output::mock("pump", 1).init_log().into_deferred();
+        let input = Input::mock("probe", 0, RawValue::default()).init_log().init_publisher();
+        let handler = input.publisher().as_ref().unwrap().handler_ref();
+        handler.try_lock().unwrap().push(
+            output.try_lock().unwrap().create_routine(RawValue::Binary(true), Duration::zero())
+        );
+        group.push_input(input);
+
+        let mut subgroup = Group::with_interval("sub", Duration::milliseconds(5));
+        subgroup.set_poll_policy(PollPolicy::CatchUp);
+        subgroup.push_input(Input::mock("sub", 2, RawValue::default()).init_log());
+        group.push_subgroup(subgroup);
+
+        group.pause();
+        assert!(group.is_paused());
+
+        // no reads occur and routines are not executed
+        for _ in 0..5 {
+            clock.advance(Duration::milliseconds(6));
+            assert!(group.poll().unwrap().is_empty());
+            group.attempt_routines();
+        }
+        assert_eq!(0, group.total_event_count() + group.subgroups()[0].total_event_count());
+        assert_eq!(1, handler.try_lock().unwrap().scheduled().len());
+        assert_eq!(group.interval, group.time_until_poll());
+
+        // paused span is treated as elapsed, so missed intervals are not caught up
+        group.resume();
+        assert!(!group.is_paused());
+        assert!(group.poll().is_ok());
+        assert!(group.poll().is_err());
+        assert!(group.subgroups_mut()[0].poll().is_err());
+
+        group.attempt_routines();
+        assert!(handler.try_lock().unwrap().scheduled().is_empty());
+        assert_eq!(1, group.total_event_count());
+        assert_eq!(1, group.subgroups()[0].total_event_count());
+    }
+
+    #[test]
+    fn replay() {
+        use crate::action::actions::Threshold;
+        use crate::action::{Action, Trigger};
+        use crate::clock;
+        use crate::storage::Chronicle;
+        use chrono::TimeZone;
+
+        let origin = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let output = Output::mock("pump", 1).init_log().into_deferred();
+        let action = Threshold::with_output("high", RawValue::Float(5.0), Trigger::GT, output.clone());
+
+        let mut input = Input::mock("probe", 0, RawValue::default()).init_publisher();
+        input.publisher_mut().as_mut().unwrap().subscribe(action.into_boxed());
+        let handler = input.publisher().as_ref().unwrap().handler_ref();
+
+        // routine is scheduled relative to recorded data
+        let routine = clock::simulate(origin, || {
+            output.try_lock().unwrap().create_routine(RawValue::Binary(false), Duration::seconds(30))
+        });
+        handler.try_lock().unwrap().push(routine);
+
+        let mut group = Group::new("");
+        group.push_input(input);
+
+        let events: Vec<IOEvent> = [(20, 6.0), (10, 4.0), (60, 7.0)].into_iter()
+            .map(|(secs, value)| IOEvent::with_timestamp(origin + Duration::seconds(secs), RawValue::Float(value)))
+            .collect();
+        assert_eq!(Ok(3), group.replay(0, &events).map_err(|e| e.to_string()));
+
+        // actions were evaluated chronologically at recorded timestamps
+        let binding = output.try_lock().unwrap();
+        let log = binding.log().unwrap();
+        let written: Vec<(i64, RawValue)> = log.try_lock().unwrap().events()
+            .map(|event| ((event.timestamp - origin).num_seconds(), event.value.clone()))
+            .collect();
+        assert_eq!(vec![
+            (10, RawValue::Binary(false)),
+            (20, RawValue::Binary(true)),
+            (30, RawValue::Binary(false)),
+            (60, RawValue::Binary(true)),
+        ], written);
+        assert!(handler.try_lock().unwrap().scheduled().is_empty());
+        drop(binding);
+
+        // input is untouched
+        let binding = group.inputs.get(&0).unwrap().try_lock().unwrap();
+        assert!(binding.state().is_none());
+        drop(binding);
+
+        assert!(group.replay(2, &events).is_err());
+    }
+
+    #[test]
+    fn sinks() {
+        use std::sync::{Arc, Mutex};
+        use crate::storage::{FileSink, NullSink, Sink};
+
+        const PATH: &str = "/tmp/sensd_tests/sinks/events.jsonl";
+        let _ = remove_dir_all("/tmp/sensd_tests/sinks");
+
+        /// Sink which counts consumed events and flushes
+        struct Counter(Arc<Mutex<(usize, usize)>>);
+
+        impl Sink for Counter {
+            fn consume(&mut self, _event: &IOEvent) {
+                self.0.lock().unwrap().0 += 1;
+            }
+
+            fn flush(&mut self) -> Result<(), crate::errors::ErrorType> {
+                self.0.lock().unwrap().1 += 1;
+                Ok(())
+            }
+        }
+
+        let counts = Arc::new(Mutex::new((0, 0)));
+
+        let clock = MockClock::new(Utc::now());
+        let mut group = Group::with_interval("name", Duration::seconds(1));
+        group
+            .push_input(Input::mock("a", 0, RawValue::default()).init_log())
+            .push_input(Input::mock("b", 1, RawValue::default()).init_log());
+        group
+            .add_sink(Box::new(Counter(counts.clone())))
+            .add_sink(Box::new(FileSink::new(PATH).unwrap()))
+            .add_sink(Box::new(NullSink));
+        assert_eq!(3, group.sink_count());
+
+        for _ in 0..2 {
+            clock.advance(Duration::seconds(1));
+            group.poll().unwrap();
+        }
+
+        // every event is consumed and sinks are flushed once per poll
+        assert_eq!((4, 2), *counts.lock().unwrap());
+        assert_eq!(4, std::fs::read_to_string(PATH).unwrap().lines().count());
+
+        // device logs are unaffected
+        assert_eq!(4, group.total_event_count());
+
+        remove_dir_all("/tmp/sensd_tests/sinks").unwrap();
+    }
+
+    #[test]
+    fn observers() {
+        use std::sync::{Arc, Mutex};
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let input = Input::mock("", 0, RawValue::default()).init_publisher();
+        let handler = input.publisher().as_ref().unwrap().handler_ref();
+
+        let clock = MockClock::new(Utc::now());
+        let mut group = Group::with_interval("name", Duration::seconds(1));
+        group
+            .push_input(input)
+            .push_input(Input::new("", 1, None));
+
+        let (a, b, c, d) = (calls.clone(), calls.clone(), calls.clone(), calls.clone());
+        group
+            .on_poll_start(Box::new(move || a.lock().unwrap().push(String::from("start"))))
+            .on_poll_complete(Box::new(move |results| {
+                let failed = results.iter().filter(|result| result.is_err()).count();
+                b.lock().unwrap().push(format!("complete {}/{}", failed, results.len()));
+            }))
+            .on_poll_complete(Box::new(move |_| c.lock().unwrap().push(String::from("second"))))
+            .on_device_fault(Box::new(move |id| d.lock().unwrap().push(format!("fault {}", id))));
+
+        for _ in 0..FAULT_THRESHOLD + 1 {
+            clock.advance(Duration::seconds(1));
+            group.poll().unwrap();
+        }
+
+        let calls = calls.lock().unwrap();
+        let poll = ["start", "complete 1/2", "second"];
+        assert_eq!((FAULT_THRESHOLD as usize + 1) * 3 + 1, calls.len());
+        assert_eq!(poll, calls[..3]);
+        // fault is only reported once, before poll completes
+        let fault = (FAULT_THRESHOLD as usize - 1) * 3 + 1;
+        assert_eq!("fault 1", calls[fault]);
+        assert_eq!(1, calls.iter().filter(|call| call.starts_with("fault")).count());
+        drop(calls);
+
+        // routines
+        let fired = Arc::new(Mutex::new(Vec::new()));
+        let observed = fired.clone();
+        group.on_routine_fired(Box::new(move |id, count| observed.lock().unwrap().push((id, count))));
+
+        let output = Output::mock("", 0).init_log();
+        handler.try_lock().unwrap().push(output.create_routine(RawValue::Binary(true), Duration::zero()));
+        handler.try_lock().unwrap().push(output.create_routine(RawValue::Binary(false), Duration::seconds(1)));
+
+        clock.advance(Duration::seconds(1));
+        group.attempt_routines();
+        group.attempt_routines();
+        assert_eq!(vec![(0, 2)], *fired.lock().unwrap());
+    }
+
+    #[test]
+    fn self_test_all() {
+        let mut group = Group::new("name");
+        group
+            .push_input(Input::mock("", 0, RawValue::default()))
+            .push_input(Input::new("", 1, None))
+            .push_output(Output::mock("", 0))
+            .push_output(Output::new("", 1, None));
+
+        let results = group.self_test_all();
+
+        assert_eq!(4, results.len());
+        assert!(results[&(IODirection::In, 0)].is_ok());
+        assert!(results[&(IODirection::In, 1)].is_err());
+        assert!(results[&(IODirection::Out, 0)].is_ok());
+        assert!(results[&(IODirection::Out, 1)].is_err());
+    }
+
+    #[test]
+    fn export_influx() {
+        use chrono::TimeZone;
+
+        let mut group = Group::new("main tank");
+        group
+            .push_input(Input::new("", 0, IOKind::Flow).init_log())
+            .push_output(Output::new("", 0, IOKind::Unassigned).init_log());
+        let mut subgroup = Group::new("dosing");
+        subgroup.push_input(Input::new("", 1, IOKind::PH).init_log());
+        group.push_subgroup(subgroup);
+
+        let timestamp = Utc.timestamp_opt(1_680_000_000, 500).unwrap();
+        let push = |log: Option<Def<Log>>, offset: i64, value: RawValue| {
+            let event = IOEvent::with_timestamp(timestamp + Duration::seconds(offset), value);
+            log.unwrap().try_lock().unwrap().push(event).unwrap();
+        };
+        let input = group.inputs.get(&0).unwrap().try_lock().unwrap().log();
+        let output = group.outputs.get(&0).unwrap().try_lock().unwrap().log();
+        let child = group.subgroups()[0].inputs.get(&1).unwrap().try_lock().unwrap().log();
+
+        push(input.clone(), 0, RawValue::Int(3));
+        push(output.clone(), 0, RawValue::Binary(true));
+        push(input.clone(), 1, RawValue::Text(String::from("say \"hi\"")));
+        // not finite
+        push(input.clone(), 2, RawValue::Float(f32::NAN));
+        push(output, 3, RawValue::percent(42.5));
+        push(child, 0, RawValue::Float(6.5));
+
+        // kind of composite event overrides device kind
+        let mut event = IOEvent::with_timestamp(timestamp + Duration::seconds(4), RawValue::Float(21.5));
+        event.kind = Some(IOKind::Temperature);
+        input.unwrap().try_lock().unwrap().push(event).unwrap();
+
+        let expected = [
+            "main\\ tank,direction=In,id=0,kind=Flow value=3 1680000000000000500",
+            "main\\ tank,direction=Out,id=0,kind=Unassigned value=1 1680000000000000500",
+            "main\\ tank,direction=In,id=0,kind=Flow text=\"say \\\"hi\\\"\" 1680000001000000500",
+            "main\\ tank,direction=Out,id=0,kind=Unassigned value=42.5 1680000003000000500",
+            "main\\ tank,direction=In,id=0,kind=Temperature value=21.5 1680000004000000500",
+            "dosing,direction=In,id=1,kind=PH value=6.5 1680000000000000500",
+        ];
+        let exported = group.export_influx();
+        assert_eq!(expected.to_vec(), exported.lines().collect::<Vec<_>>());
+        assert!(exported.ends_with('\n'));
+
+        assert!(Group::new("empty").export_influx().is_empty());
+    }
+
+    #[test]
+    fn total_event_count() {
+        let clock = MockClock::new(Utc::now());
+        let mut group = Group::new("name");
+        group
+            .push_input(Input::mock("", 0, RawValue::default()).init_log())
+            .push_input(Input::mock("", 1, RawValue::default()).init_log())
+            .push_output(Output::mock("", 0).init_log())
+            .push_output(Output::mock("", 1));
+
+        assert_eq!(0, group.total_event_count());
+
+        for _ in 0..3 {
+            group.inputs.get(&0).unwrap().try_lock().unwrap().read().unwrap();
+            clock.advance(Duration::seconds(1));
+        }
+        group.outputs.get(&0).unwrap().try_lock().unwrap()
+            .write(RawValue::Binary(true)).unwrap();
+
+        assert_eq!(4, group.total_event_count());
+
+        let counts = group.event_count_by_device();
+        assert_eq!(3, counts[&(IODirection::In, 0)]);
+        assert_eq!(0, counts[&(IODirection::In, 1)]);
+        assert_eq!(1, counts[&(IODirection::Out, 0)]);
+        // output without log is skipped
+        assert!(!counts.contains_key(&(IODirection::Out, 1)));
+    }
+
+    #[test]
+    fn merged_log() {
+        let mut group = Group::new("name");
+        group
+            .push_input(Input::mock("", 0, RawValue::default()).init_log())
+            .push_input(Input::mock("", 1, RawValue::default()).init_log())
+            .push_output(Output::mock("", 0).init_log());
+
+        let clock = MockClock::new(Utc::now());
+        let now = clock.now();
+        let push = |log: Option<Def<Log>>, offset: i64, value: i32| {
+            let event = IOEvent::with_timestamp(now + Duration::seconds(offset), RawValue::Int(value));
+            log.unwrap().try_lock().unwrap().push(event).unwrap();
+        };
+        let input0 = group.inputs.get(&0).unwrap().try_lock().unwrap().log();
+        let input1 = group.inputs.get(&1).unwrap().try_lock().unwrap().log();
+        let output0 = group.outputs.get(&0).unwrap().try_lock().unwrap().log();
+
+        push(input1.clone(), 2, 3);
+        push(output0.clone(), 1, 2);
+        push(input0.clone(), 0, 0);
+        // shared timestamp
+        push(input1, 1, 1);
+        push(output0, 3, 4);
+        push(input0, 3, 4);
+
+        let merged = group.merged_log();
+        let values: Vec<RawValue> = merged.iter().map(|event| event.event.value.clone()).collect();
+        let expected: Vec<RawValue> = [0, 1, 2, 3, 4, 4].into_iter().map(RawValue::Int).collect();
+        assert_eq!(expected, values);
+
+        // inputs are ordered before outputs on shared timestamp
+        assert_eq!((IODirection::In, 1), (merged[1].direction, merged[1].id));
+        assert_eq!((IODirection::Out, 0), (merged[2].direction, merged[2].id));
+        assert_eq!((IODirection::In, 0), (merged[4].direction, merged[4].id));
+        assert_eq!((IODirection::Out, 0), (merged[5].direction, merged[5].id));
+
+        // export as NDJSON
+        let path = Path::new(DIR_PATH).join("merged_log").join("merged.ndjson");
+        group.export_merged(&path).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(merged.len(), lines.len());
+
+        let first: MergedEvent = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(merged[0].event.timestamp, first.event.timestamp);
+        assert_eq!(merged[0].id, first.id);
+
+        remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
     /// Test [`Group::full_path()`]
     #[test]
     fn test_dir() {
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::io::IdType;
+
+/// Structured health report of a [`crate::storage::Group`]
+///
+/// Only input devices are considered when determining `stale` and `faulted` devices since output
+/// devices are not polled.
+///
+/// # See Also
+///
+/// - [`crate::storage::Group::health()`] for generating a report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct GroupHealth {
+    /// Total number of input and output devices
+    pub device_count: usize,
+
+    /// Ids of input devices that have not been successfully read within
+    /// [`crate::settings::STALE_INTERVALS`] polling intervals
+    pub stale: Vec<IdType>,
+
+    /// Ids of input devices with at least [`crate::settings::FAULT_THRESHOLD`] consecutive failed reads
+    pub faulted: Vec<IdType>,
+
+    /// Number of scheduled [`crate::action::Routine`]s that have not been executed
+    pub pending_routines: usize,
+
+    /// Timestamp of last poll where all reads succeeded. `None` if no poll has succeeded.
+    pub last_poll: Option<DateTime<Utc>>,
+
+    /// Milliseconds elapsed since `last_poll`. `None` if no poll has succeeded.
+    pub since_last_poll_ms: Option<i64>,
+}
+
+impl GroupHealth {
+    /// Check if any device is stale or faulted
+    ///
+    /// # Returns
+    ///
+    /// `true` if there are no stale or faulted devices
+    pub fn is_healthy(&self) -> bool {
+        self.stale.is_empty() && self.faulted.is_empty()
+    }
+}
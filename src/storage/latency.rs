@@ -0,0 +1,64 @@
+use chrono::Duration;
+
+/// Running statistics of read durations for a single input device
+///
+/// # See Also
+///
+/// - [`crate::storage::Group::read_latencies()`] for retrieving statistics
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReadLatency {
+    /// Duration of most recent read
+    pub last: Duration,
+
+    /// Longest read duration
+    pub max: Duration,
+
+    /// Mean read duration
+    pub mean: Duration,
+
+    /// Number of recorded reads
+    pub count: u32,
+}
+
+impl ReadLatency {
+    /// Constructor from a single read duration
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            last: duration,
+            max: duration,
+            mean: duration,
+            count: 1,
+        }
+    }
+
+    /// Add duration of a read to running statistics
+    ///
+    /// # Parameters
+    ///
+    /// - `duration`: Time taken by read
+    pub fn record(&mut self, duration: Duration) {
+        self.count += 1;
+        self.last = duration;
+        self.max = self.max.max(duration);
+        self.mean = self.mean + (duration - self.mean) / self.count as i32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::storage::ReadLatency;
+
+    #[test]
+    fn record() {
+        let mut latency = ReadLatency::new(Duration::milliseconds(10));
+        latency.record(Duration::milliseconds(30));
+        latency.record(Duration::milliseconds(20));
+
+        assert_eq!(Duration::milliseconds(20), latency.last);
+        assert_eq!(Duration::milliseconds(30), latency.max);
+        assert_eq!(Duration::milliseconds(20), latency.mean);
+        assert_eq!(3, latency.count);
+    }
+}
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+
+use crate::storage::FILETYPE;
+
+#[cfg(feature = "bincode")]
+use std::io::{Read, Write};
+
+#[cfg(feature = "bincode")]
+use chrono::{DateTime, TimeZone, Utc};
+
+#[cfg(feature = "bincode")]
+use crate::errors::FilesystemError;
+#[cfg(feature = "bincode")]
+use crate::io::{IOEvent, IOKind, RawValue};
+#[cfg(feature = "bincode")]
+use crate::storage::EventCollection;
+
+/// Leading bytes of a binary log file
+pub const BINARY_MAGIC: &[u8; 4] = b"SNSD";
+
+/// Format version of binary log files, written directly after [`BINARY_MAGIC`]
+pub const BINARY_VERSION: u8 = 1;
+
+/// Filetype suffix of binary log files
+pub const BINARY_FILETYPE: &str = ".bin";
+
+/// File format used by [`crate::storage::Log`] when saving and loading
+///
+/// The backend is not stored in the log file itself. Therefore, a log must be configured with the
+/// same backend that was used to save it.
+///
+/// # See Also
+///
+/// - [`crate::storage::Log::set_backend()`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LogBackend {
+    /// Versioned JSON as described by [`crate::settings::LOG_VERSION`]
+    #[default]
+    Json,
+
+    /// Compact binary encoding by `bincode`
+    ///
+    /// Files begin with [`BINARY_MAGIC`] and [`BINARY_VERSION`] so that files of an unknown format
+    /// are rejected before decoding. Device metadata is not stored.
+    #[cfg(feature = "bincode")]
+    Bincode,
+}
+
+impl LogBackend {
+    /// Filetype suffix of files written by backend
+    pub fn filetype(&self) -> &'static str {
+        match self {
+            Self::Json => FILETYPE,
+            #[cfg(feature = "bincode")]
+            Self::Bincode => BINARY_FILETYPE,
+        }
+    }
+}
+
+/// Value as stored by [`LogBackend::Bincode`]
+///
+/// [`RawValue`] cannot be used directly since its deserializer is self-describing.
+#[cfg(feature = "bincode")]
+#[derive(Serialize, Deserialize)]
+enum BinaryValue {
+    Binary(bool),
+    PosInt8(u8),
+    Int8(i8),
+    PosInt(u32),
+    Int(i32),
+    Float(f32),
+    Text(String),
+    // appended so that existing files remain readable
+    Percent(f32),
+}
+
+/// Event as stored by [`LogBackend::Bincode`]
+#[cfg(feature = "bincode")]
+#[derive(Serialize, Deserialize)]
+struct BinaryEvent {
+    secs: i64,
+    nanos: u32,
+    value: BinaryValue,
+    tags: Vec<String>,
+    kind: Option<IOKind>,
+}
+
+#[cfg(feature = "bincode")]
+impl From<&IOEvent> for BinaryEvent {
+    fn from(event: &IOEvent) -> Self {
+        let value = match &event.value {
+            RawValue::Binary(value) => BinaryValue::Binary(*value),
+            RawValue::PosInt8(value) => BinaryValue::PosInt8(*value),
+            RawValue::Int8(value) => BinaryValue::Int8(*value),
+            RawValue::PosInt(value) => BinaryValue::PosInt(*value),
+            RawValue::Int(value) => BinaryValue::Int(*value),
+            RawValue::Float(value) => BinaryValue::Float(*value),
+            RawValue::Percent(value) => BinaryValue::Percent(*value),
+            RawValue::Text(value) => BinaryValue::Text(value.clone()),
+        };
+        BinaryEvent {
+            secs: event.timestamp.timestamp(),
+            nanos: event.timestamp.timestamp_subsec_nanos(),
+            value,
+            tags: event.tags.clone(),
+            kind: event.kind,
+        }
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl TryFrom<BinaryEvent> for IOEvent {
+    type Error = FilesystemError;
+
+    fn try_from(event: BinaryEvent) -> Result<Self, Self::Error> {
+        let timestamp: DateTime<Utc> = Utc.timestamp_opt(event.secs, event.nanos)
+            .single()
+            .ok_or_else(|| FilesystemError::SerializationError {
+                msg: format!("Invalid timestamp: {}.{}", event.secs, event.nanos)
+            })?;
+        let value = match event.value {
+            BinaryValue::Binary(value) => RawValue::Binary(value),
+            BinaryValue::PosInt8(value) => RawValue::PosInt8(value),
+            BinaryValue::Int8(value) => RawValue::Int8(value),
+            BinaryValue::PosInt(value) => RawValue::PosInt(value),
+            BinaryValue::Int(value) => RawValue::Int(value),
+            BinaryValue::Float(value) => RawValue::Float(value),
+            BinaryValue::Percent(value) => RawValue::percent(value),
+            BinaryValue::Text(value) => RawValue::Text(value),
+        };
+
+        let mut decoded = IOEvent::with_timestamp(timestamp, value);
+        decoded.tags = event.tags;
+        decoded.kind = event.kind;
+        Ok(decoded)
+    }
+}
+
+/// Write header and events in the format of [`LogBackend::Bincode`]
+#[cfg(feature = "bincode")]
+pub(crate) fn write_binary<'a, W, I>(mut writer: W, events: I) -> Result<(), FilesystemError>
+where
+    W: Write,
+    I: Iterator<Item = &'a IOEvent>,
+{
+    let events: Vec<BinaryEvent> = events.map(BinaryEvent::from).collect();
+
+    writer.write_all(BINARY_MAGIC)?;
+    writer.write_all(&[BINARY_VERSION])?;
+    bincode::serialize_into(&mut writer, &events)
+        .map_err(|e| FilesystemError::SerializationError {msg: e.to_string()})?;
+    Ok(writer.flush()?)
+}
+
+/// Read events written by [`write_binary()`]
+///
+/// # Returns
+///
+/// A `Result` containing:
+///
+/// - `Ok`: with events stored in file
+/// - `Err`: with [`FilesystemError::UnrecognizedFormat`] if file does not begin with
+///   [`BINARY_MAGIC`], [`FilesystemError::UnsupportedVersion`] if version is unknown, or
+///   [`FilesystemError::SerializationError`] if contents cannot be decoded
+#[cfg(feature = "bincode")]
+pub(crate) fn read_binary<R: Read>(mut reader: R) -> Result<EventCollection, FilesystemError> {
+    let mut header = [0u8; 5];
+    reader.read_exact(&mut header).map_err(|_| FilesystemError::UnrecognizedFormat {
+        msg: String::from("file is too short to contain header")
+    })?;
+
+    if &header[..4] != BINARY_MAGIC {
+        return Err(FilesystemError::UnrecognizedFormat {
+            msg: String::from("missing binary log header")
+        });
+    }
+    if header[4] != BINARY_VERSION {
+        return Err(FilesystemError::UnsupportedVersion {version: header[4] as u64});
+    }
+
+    let events: Vec<BinaryEvent> = bincode::deserialize_from(reader)
+        .map_err(|e| FilesystemError::SerializationError {msg: e.to_string()})?;
+
+    events.into_iter()
+        .map(|event| IOEvent::try_from(event).map(|event| (event.timestamp, event)))
+        .collect()
+}
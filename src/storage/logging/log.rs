@@ -1,16 +1,17 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::{Entry, Iter};
+use std::collections::BTreeSet;
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
-use std::ops::Deref;
+use std::io::{BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 
+use crate::clock;
 use crate::errors::{ContainerError, ErrorType, FilesystemError};
-use crate::helpers::writable_or_create;
-use crate::io::{DeviceMetadata, IdType, IOEvent};
+use crate::helpers::{slugify, writable_or_create_with_mode};
+use crate::io::{Aggregation, DeviceMetadata, IdType, IOEvent, NumericBinaryEvent};
 use crate::settings;
-use crate::storage::{EventCollection, Persistent, FILETYPE, Document};
+use crate::storage::{EventCollection, Persistent, FILETYPE, Document, LogBackend, LogStats};
 
 
 /// A record of [`IOEvent`]s from a single device keyed by datetime
@@ -31,10 +32,81 @@ pub struct Log {
     /// This field is not serialized
     dir: Option<PathBuf>,
 
+    #[serde(skip)]
+    /// Serialize [`crate::io::RawValue::Binary`] as `0`/`1` instead of `false`/`true`
+    ///
+    /// This field is not serialized
+    numeric_binary: bool,
+
+    #[serde(skip)]
+    /// Save as human-readable JSON instead of compact JSON
+    ///
+    /// This field is not serialized
+    pretty: bool,
+
+    #[serde(skip)]
+    /// File format used when saving and loading
+    ///
+    /// This field is not serialized
+    backend: LogBackend,
+
+    #[serde(skip)]
+    /// Maximum number of events retained in memory
+    ///
+    /// When set, log is ephemeral and is never saved to or loaded from disk. This field is not
+    /// serialized.
+    capacity: Option<usize>,
+
+    #[serde(skip)]
+    /// Timestamps of events in chronological order, only maintained when `capacity` is set
+    ///
+    /// Allows the oldest event to be evicted without scanning all events. Index is rebuilt by
+    /// [`Log::evict()`] when it is out of sync (ie: after events were inserted by
+    /// [`Log::entry()`]). This field is not serialized.
+    order: BTreeSet<DateTime<Utc>>,
+
+    #[serde(skip)]
+    /// Unix permission bits of directories created when saving
+    ///
+    /// This field is not serialized
+    mode: Option<u32>,
+
+    #[serde(skip)]
+    /// Events older than this are discarded by [`Log::enforce_retention()`]
+    ///
+    /// This field is not serialized
+    max_age: Option<Duration>,
+
+    #[serde(skip)]
+    /// Maximum number of events retained by [`Log::enforce_retention()`]
+    ///
+    /// This field is not serialized
+    max_events: Option<usize>,
+
     /// Collection of `IOEvent` objects
     log: EventCollection,
 }
 
+/// Versioned representation of [`Log`] written to disk
+///
+/// # See Also
+///
+/// - [`settings::LOG_VERSION`] for description of format versions
+#[derive(Serialize)]
+struct LogFileRef<'a, E> {
+    version: u64,
+    metadata: Option<&'a DeviceMetadata>,
+    events: Vec<E>,
+}
+
+/// Versioned representation of [`Log`] read from disk
+///
+/// Stored metadata is ignored since it is already known by the loading [`Log`].
+#[derive(Deserialize)]
+struct LogFile {
+    events: Vec<IOEvent>,
+}
+
 impl Log {
 
     /// Constructor for [`Log`]
@@ -52,6 +124,165 @@ impl Log {
             .set_metadata(metadata.clone())
     }
 
+    /// Constructor for an in-memory [`Log`] which only retains the latest events
+    ///
+    /// When a pushed event exceeds `capacity`, the event with the oldest timestamp is evicted.
+    /// Events are never persisted: [`Persistent::save()`] and [`Persistent::load()`] do nothing.
+    /// This is intended for devices with limited storage where only a live window of recent events
+    /// is needed (eg: for a dashboard).
+    ///
+    /// # Parameters
+    ///
+    /// - `metadata`: Reference to [`DeviceMetadata`] of originating device
+    /// - `capacity`: Maximum number of events to retain
+    ///
+    /// # Returns
+    ///
+    /// Empty log with identity attributes belonging to given device.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use sensd::io::{DeviceMetadata, IOEvent, RawValue};
+    /// use sensd::storage::Log;
+    ///
+    /// let mut log = Log::circular(&DeviceMetadata::default(), 2);
+    ///
+    /// let now = Utc::now();
+    /// for i in 0..3 {
+    ///     log.push(IOEvent::with_timestamp(now + Duration::seconds(i), RawValue::Int(i as i32))).unwrap();
+    /// }
+    ///
+    /// let values: Vec<RawValue> = log.events().map(|event| event.value.clone()).collect();
+    /// assert_eq!(vec![RawValue::Int(1), RawValue::Int(2)], values);
+    /// ```
+    pub fn circular(metadata: &DeviceMetadata, capacity: usize) -> Self {
+        assert!(capacity > 0, "Log capacity must be non-zero");
+
+        let mut log = Self::with_metadata(metadata);
+        log.capacity = Some(capacity);
+        log
+    }
+
+    /// Getter for `capacity`
+    ///
+    /// # Returns
+    ///
+    /// An `Option` with:
+    /// - `None` if log is unbounded and persisted
+    /// - `Some` with maximum number of retained events if log is in-memory only
+    ///
+    /// # See Also
+    ///
+    /// - [`Log::circular()`]
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Builder method for setting retention policy
+    ///
+    /// Unlike [`Log::circular()`], events are not discarded when pushed. Instead, policy is
+    /// applied by [`Log::enforce_retention()`], and log is still persisted.
+    ///
+    /// # Parameters
+    ///
+    /// - `max_age`: Events older than this are discarded
+    /// - `max_events`: Maximum number of most recent events to retain
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    pub fn set_retention(mut self, max_age: Option<Duration>, max_events: Option<usize>) -> Self {
+        self.set_retention_ref(max_age, max_events);
+        self
+    }
+
+    /// Setter for retention policy
+    ///
+    /// # See Also
+    ///
+    /// - [`Log::set_retention()`]
+    pub fn set_retention_ref(&mut self, max_age: Option<Duration>, max_events: Option<usize>) -> &mut Self {
+        self.max_age = max_age;
+        self.max_events = max_events;
+        self
+    }
+
+    /// Getter for retention policy
+    ///
+    /// # Returns
+    ///
+    /// Maximum age and maximum number of events
+    pub fn retention(&self) -> (Option<Duration>, Option<usize>) {
+        (self.max_age, self.max_events)
+    }
+
+    /// Discard events according to retention policy
+    ///
+    /// Events older than `max_age` are removed first, then the oldest events are removed until no
+    /// more than `max_events` remain.
+    ///
+    /// # Returns
+    ///
+    /// Number of events removed
+    pub fn enforce_retention(&mut self) -> usize {
+        let count = self.log.len();
+
+        self.order.clear();
+
+        if let Some(max_age) = self.max_age {
+            let since = clock::now() - max_age;
+            self.log.retain(|timestamp, _| *timestamp >= since);
+        }
+        if let Some(max_events) = self.max_events {
+            if self.log.len() > max_events {
+                let mut timestamps: Vec<DateTime<Utc>> = self.log.keys().copied().collect();
+                timestamps.sort();
+                for timestamp in &timestamps[..timestamps.len() - max_events] {
+                    self.log.remove(timestamp);
+                }
+            }
+        }
+
+        count - self.log.len()
+    }
+
+    /// Discard all events held in memory
+    ///
+    /// Log file is not modified. Metadata and settings are retained.
+    ///
+    /// # Returns
+    ///
+    /// Number of events removed
+    pub fn clear(&mut self) -> usize {
+        let count = self.log.len();
+        self.log.clear();
+        self.order.clear();
+        count
+    }
+
+    /// Evict events with the oldest timestamps until log does not exceed `capacity`
+    fn evict(&mut self) {
+        let capacity = match self.capacity {
+            Some(capacity) => capacity,
+            None => return,
+        };
+        if self.order.len() != self.log.len() {
+            self.order = self.log.keys().copied().collect();
+        }
+        while self.log.len() > capacity {
+            match self.order.pop_first() {
+                Some(oldest) => self.log.remove(&oldest),
+                None => break,
+            };
+        }
+    }
+
     /// Getter for device metadata
     ///
     /// # Returns
@@ -107,6 +338,135 @@ impl Log {
         self
     }
 
+    /// Builder method for serializing binary values as numbers
+    ///
+    /// By default, [`crate::io::RawValue::Binary`] values are saved as JSON booleans. When enabled,
+    /// values are saved as `1` and `0` instead. Both forms are accepted by [`Log::load()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `enabled`: Whether binary values should be saved as numbers
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    pub fn set_numeric_binary(mut self, enabled: bool) -> Self {
+        self.numeric_binary = enabled;
+        self
+    }
+
+    /// Getter for `numeric_binary` flag
+    ///
+    /// # See Also
+    ///
+    /// - [`Log::set_numeric_binary()`]
+    pub fn numeric_binary(&self) -> bool {
+        self.numeric_binary
+    }
+
+    /// Builder method for saving log as human-readable JSON
+    ///
+    /// By default, logs are saved as compact JSON to conserve space. Both forms are accepted by
+    /// [`Log::load()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `enabled`: Whether log should be saved with indentation and newlines
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    pub fn set_pretty(mut self, enabled: bool) -> Self {
+        self.pretty = enabled;
+        self
+    }
+
+    /// Getter for `pretty` flag
+    ///
+    /// # See Also
+    ///
+    /// - [`Log::set_pretty()`]
+    pub fn pretty(&self) -> bool {
+        self.pretty
+    }
+
+    /// Builder method for permissions of files and directories created when saving
+    ///
+    /// Files receive `mode` without execute bits. Permissions of existing files are not changed.
+    /// This is set by [`crate::storage::Group`] for logs of its devices when permissions are
+    /// managed by [`crate::storage::Group::init_dir_with_mode()`].
+    ///
+    /// # Parameters
+    ///
+    /// - `mode`: Unix permission bits of directories (eg: `0o770`). If `None`, permissions are
+    ///   determined by the umask of the process.
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    pub fn set_mode(mut self, mode: Option<u32>) -> Self {
+        self.set_mode_ref(mode);
+        self
+    }
+
+    /// Setter for permissions of created files and directories
+    ///
+    /// # See Also
+    ///
+    /// - [`Log::set_mode()`]
+    pub fn set_mode_ref(&mut self, mode: Option<u32>) -> &mut Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Getter for permissions of created files and directories
+    ///
+    /// # See Also
+    ///
+    /// - [`Log::set_mode()`]
+    pub fn mode(&self) -> Option<u32> {
+        self.mode
+    }
+
+    /// Builder method for selecting file format
+    ///
+    /// By default, logs are saved as JSON. A log must be loaded with the same backend that it was
+    /// saved with. Since the filetype differs between backends, files of another backend are not
+    /// overwritten. [`Log::set_pretty()`] and [`Log::set_numeric_binary()`] only apply to JSON.
+    ///
+    /// # Parameters
+    ///
+    /// - `backend`: File format to use
+    ///
+    /// # Returns
+    ///
+    /// Ownership of `self` to allow method chaining
+    pub fn set_backend(mut self, backend: LogBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Getter for `backend`
+    ///
+    /// # See Also
+    ///
+    /// - [`Log::set_backend()`]
+    pub fn backend(&self) -> LogBackend {
+        self.backend
+    }
+
+    /// Serialize `value` as JSON according to `pretty` flag
+    fn write_json<W, T>(&self, writer: W, value: &T) -> serde_json::Result<()>
+    where
+        W: Write,
+        T: Serialize + ?Sized,
+    {
+        match self.pretty {
+            true => serde_json::to_writer_pretty(writer, value),
+            false => serde_json::to_writer(writer, value),
+        }
+    }
+
     /// Iterator over keys and values
     ///
     /// # Returns
@@ -116,6 +476,69 @@ impl Log {
         self.log.iter()
     }
 
+    /// Mutable reference to event stored at `timestamp`
+    ///
+    /// This allows an event to be amended in-place without removing and inserting.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no event exists at `timestamp`
+    pub fn get_mut(&mut self, timestamp: &DateTime<Utc>) -> Option<&mut IOEvent> {
+        self.log.get_mut(timestamp)
+    }
+
+    /// In-place access to entry of underlying [`EventCollection`]
+    ///
+    /// Unlike [`Log::push()`], existing events are not rejected. Callers are responsible for
+    /// ensuring that the `timestamp` of an inserted [`IOEvent`] matches `timestamp`.
+    pub fn entry(&mut self, timestamp: DateTime<Utc>) -> Entry<'_, DateTime<Utc>, IOEvent> {
+        self.log.entry(timestamp)
+    }
+
+    /// Iterator over events in chronological order
+    ///
+    /// # Returns
+    ///
+    /// Iterator of [`IOEvent`] references sorted by timestamp in ascending order.
+    pub fn events(&self) -> impl Iterator<Item = &IOEvent> {
+        let mut events: Vec<&IOEvent> = self.log.values().collect();
+        events.sort_by_key(|event| event.timestamp);
+        events.into_iter()
+    }
+
+    /// Iterator over events marked with `tag` in chronological order
+    ///
+    /// # See Also
+    ///
+    /// - [`IOEvent::tag()`] for marking events
+    pub fn events_with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a IOEvent> {
+        self.events().filter(move |event| event.has_tag(tag))
+    }
+
+    /// Get most recent event
+    ///
+    /// # Returns
+    ///
+    /// An `Option` that is:
+    ///
+    /// - `Some` with a reference to the [`IOEvent`] with the latest timestamp
+    /// - `None` if log is empty
+    pub fn latest(&self) -> Option<&IOEvent> {
+        self.log.values().max_by_key(|event| event.timestamp)
+    }
+
+    /// Get oldest event
+    ///
+    /// # Returns
+    ///
+    /// An `Option` that is:
+    ///
+    /// - `Some` with a reference to the [`IOEvent`] with the earliest timestamp
+    /// - `None` if log is empty
+    pub fn first(&self) -> Option<&IOEvent> {
+        self.log.values().min_by_key(|event| event.timestamp)
+    }
+
     /// Push a new event to log
     ///
     /// # Parameters
@@ -127,15 +550,29 @@ impl Log {
     /// A `Result` that contains:
     ///
     /// - `Ok`: with a reference to inserted log is inserted when [`IOEvent.timestamp`] does not exist in log
-    /// - `Err`: with an [`ErrorKind::ContainerError`] error if timestamp already exists in log
+    /// - `Err`: with an [`ErrorKind::ContainerError`] error if timestamp already exists in log, or
+    ///   if log is full and `event` is older than all retained events.
+    ///
+    /// # See Also
+    ///
+    /// - [`Log::circular()`] for logs which evict the oldest event when capacity is exceeded
     pub fn push(
         &mut self,
         event: IOEvent,
     ) -> Result<&mut IOEvent, ContainerError> {
-        match self.log.entry(event.timestamp) {
-            Entry::Occupied(_) => Err(ContainerError::KeyExists { key: event.timestamp.to_string()}),
-            Entry::Vacant(entry) => Ok(entry.insert(event)),
+        let timestamp = event.timestamp;
+        match self.log.entry(timestamp) {
+            Entry::Occupied(_) => return Err(ContainerError::KeyExists { key: timestamp.to_string()}),
+            Entry::Vacant(entry) => { entry.insert(event); }
+        }
+        if self.capacity.is_some() {
+            self.order.insert(timestamp);
         }
+        self.evict();
+
+        // a pushed event older than the retained window is immediately evicted
+        self.log.get_mut(&timestamp)
+            .ok_or(ContainerError::Expired { key: timestamp.to_string() })
     }
 
     /// Extend current [`Log`] with [`EventCollection`] from another [`Log`]
@@ -155,6 +592,115 @@ impl Log {
         }
 
         self.log.extend(other.log.clone());
+        self.evict();
+    }
+
+    /// Reduce events to one aggregated event per fixed time bucket
+    ///
+    /// Buckets are aligned to the Unix epoch, so that output of consecutive calls line up. Each
+    /// aggregated event is timestamped at the start of its bucket, and buckets without events are
+    /// skipped. Tags are not retained.
+    ///
+    /// Numeric values are combined by [`Aggregation::aggregate()`], and binary values are combined
+    /// by majority vote. Since [`crate::io::RawValue::Text`] cannot be aggregated, buckets
+    /// containing text or differing kinds carry the last value within the bucket.
+    ///
+    /// # Parameters
+    ///
+    /// - `bucket`: Width of each bucket
+    /// - `agg`: Method of combining events within a bucket
+    ///
+    /// # Returns
+    ///
+    /// Aggregated events in chronological order
+    ///
+    /// # Panics
+    ///
+    /// If `bucket` is shorter than one millisecond.
+    pub fn downsample(&self, bucket: Duration, agg: Aggregation) -> Vec<IOEvent> {
+        let width = bucket.num_milliseconds();
+        assert!(width > 0, "Bucket must be at least one millisecond");
+
+        let start_of = |timestamp: DateTime<Utc>| {
+            let offset = Duration::milliseconds(timestamp.timestamp_millis().rem_euclid(width))
+                + Duration::nanoseconds((timestamp.timestamp_subsec_nanos() % 1_000_000) as i64);
+            timestamp - offset
+        };
+
+        let mut downsampled = Vec::new();
+        let mut current: Option<DateTime<Utc>> = None;
+        let mut samples = Vec::new();
+
+        let mut flush = |start: DateTime<Utc>, samples: &mut Vec<_>| {
+            let value = agg.aggregate(samples).or_else(|| samples.last().cloned());
+            if let Some(value) = value {
+                downsampled.push(IOEvent::with_timestamp(start, value));
+            }
+            samples.clear();
+        };
+
+        for event in self.events() {
+            let start = start_of(event.timestamp);
+            if let Some(previous) = current {
+                if previous != start {
+                    flush(previous, &mut samples);
+                }
+            }
+            current = Some(start);
+            samples.push(event.value.clone());
+        }
+        if let Some(previous) = current {
+            flush(previous, &mut samples);
+        }
+
+        downsampled
+    }
+
+    /// Summarize numeric values stored in log
+    ///
+    /// Non-numeric values (ie: [`crate::io::RawValue::Binary`] and [`crate::io::RawValue::Text`]) are
+    /// excluded from `min`, `max`, `mean` and `span`, but are counted in [`LogStats::non_numeric`].
+    ///
+    /// # Parameters
+    ///
+    /// - `window`: Only consider events newer than `now - window`. If `None`, all events are
+    ///   considered.
+    ///
+    /// # Returns
+    ///
+    /// [`LogStats`] summarizing events within `window`
+    pub fn statistics(&self, window: Option<Duration>) -> LogStats {
+        let since = window.map(|window| clock::now() - window);
+
+        let mut stats = LogStats::default();
+        let mut sum = 0.0;
+
+        let events = self.log.values()
+            .filter(|event| since.is_none_or(|since| event.timestamp >= since));
+        for event in events {
+            let value = match event.value.as_f64() {
+                Some(value) => value,
+                None => {
+                    stats.non_numeric += 1;
+                    continue;
+                }
+            };
+
+            stats.count += 1;
+            sum += value;
+            stats.min = Some(stats.min.map_or(value, |min| min.min(value)));
+            stats.max = Some(stats.max.map_or(value, |max| max.max(value)));
+            stats.span = Some(match stats.span {
+                None => (event.timestamp, event.timestamp),
+                Some((first, last)) => (first.min(event.timestamp), last.max(event.timestamp)),
+            });
+        }
+
+        if stats.count > 0 {
+            stats.mean = Some(sum / stats.count as f64);
+        }
+
+        stats
     }
 }
 
@@ -162,6 +708,10 @@ impl Log {
 impl Persistent for Log {
     /// Save log to disk in JSON format
     ///
+    /// Saving is silent: nothing is printed and log is serialized exactly once. Files are written in
+    /// the format given by [`settings::LOG_VERSION`], unless another format is selected by
+    /// [`Log::set_backend()`].
+    ///
     /// # Issues
     ///
     /// - See [#126](https://github.com/PoorRican/sensd/issues/126) which implements validation of `path`.
@@ -172,28 +722,24 @@ impl Persistent for Log {
     ///
     /// - `Ok`: with `()` when log is not empty, and serialization and write to disk is successful.
     /// - `Err`: with appropriate error when `Log` is empty *OR*
-    ///   when an error is returned by[`serde_json::to_writer_pretty()`].
+    ///   when an error is returned by [`serde_json::to_writer()`] or [`serde_json::to_writer_pretty()`].
     ///
     /// # See Also
     ///
     /// - [`Log::full_path()`] explains usage of `path` parameter.
+    /// - [`Log::set_pretty()`] for choosing between compact and human-readable JSON
+    /// - [`Log::circular()`] for in-memory logs which are never saved
     fn save(&self) -> Result<(), ErrorType> {
-        let file = writable_or_create(self.full_path());
-        let writer = BufWriter::new(file);
-
-        match serde_json::to_writer_pretty(writer, &self) {
-            Ok(_) => println!("Saved"),
-            Err(e) => {
-                let msg = e.to_string();
-                return Err(
-                    Box::new(FilesystemError::SerializationError {msg}));
-            }
+        if self.capacity.is_some() {
+            return Ok(());
         }
-        Ok(())
+        self.save_to(self.dir().expect("No directory is associated"))
     }
 
     /// Load log from JSON file
     ///
+    /// Files written in any previous format version are upgraded when read.
+    ///
     /// # Parameters
     ///
     /// - `path`: path to read and load from. This path should not include a filename.
@@ -213,21 +759,75 @@ impl Persistent for Log {
     /// # See Also
     ///
     /// - [`Log::full_path()`] explains usage of `path` parameter.
+    /// - [`Log::migrate()`] which is called before reading to locate files with legacy naming
+    /// - [`Log::circular()`] for in-memory logs which are never loaded
     fn load(&mut self) -> Result<(), ErrorType> {
+        if self.capacity.is_some() {
+            return Ok(());
+        }
         if self.log.is_empty() {
-            let file = File::open(self.full_path().deref())?;
+            self.migrate()?;
+        }
+        let dir = self.dir().expect("No directory is associated").clone();
+        self.load_from(&dir)
+    }
+
+    /// Save log to [`Document::filename()`] within `path`
+    ///
+    /// This is the explicit counterpart of [`Persistent::save()`] and is useful for writing
+    /// backups. [`Log::dir()`] is not changed.
+    fn save_to(&self, path: &Path) -> Result<(), ErrorType> {
+        if self.capacity.is_some() {
+            return Ok(());
+        }
+
+        let file = writable_or_create_with_mode(path.join(self.filename()), self.mode).map_err(FilesystemError::from)?;
+        let writer = BufWriter::new(file);
+
+        #[cfg(feature = "bincode")]
+        if self.backend == LogBackend::Bincode {
+            return super::backend::write_binary(writer, self.events())
+                .map_err(|e| Box::new(e) as ErrorType);
+        }
+
+        let version = settings::LOG_VERSION;
+        let metadata = self.metadata();
+        let result = match self.numeric_binary {
+            false => self.write_json(writer, &LogFileRef {
+                version,
+                metadata,
+                events: self.events().collect(),
+            }),
+            true => self.write_json(writer, &LogFileRef {
+                version,
+                metadata,
+                events: self.events().map(NumericBinaryEvent::from).collect(),
+            }),
+        };
+
+        Ok(result.map_err(FilesystemError::from)?)
+    }
+
+    /// Load log from [`Document::filename()`] within `path`
+    ///
+    /// This is the explicit counterpart of [`Persistent::load()`]. Unlike the default form, files
+    /// with legacy naming are not migrated.
+    fn load_from(&mut self, path: &Path) -> Result<(), ErrorType> {
+        if self.capacity.is_some() {
+            Ok(())
+        } else if self.log.is_empty() {
+            let file = File::open(path.join(self.filename())).map_err(FilesystemError::from)?;
             let reader = BufReader::new(file);
 
-            let buff: Log = match serde_json::from_reader(reader) {
-                Ok(data) => data,
-                Err(e) => {
-                    let msg = e.to_string();
-                    return Err(
-                        Box::new(FilesystemError::SerializationError {msg})
-                    )
-                }
-            };
-            self.log = buff.log;
+            #[cfg(feature = "bincode")]
+            if self.backend == LogBackend::Bincode {
+                self.log = super::backend::read_binary(reader)?;
+                return Ok(());
+            }
+
+            let contents: serde_json::Value = serde_json::from_reader(reader)
+                .map_err(FilesystemError::from)?;
+            self.log = upgrade_format(contents)?;
             Ok(())
         } else {
             Err(Box::new(ContainerError::ContainerNotEmpty))
@@ -235,6 +835,40 @@ impl Persistent for Log {
     }
 }
 
+/// Extract events from a deserialized log file of any supported format version
+///
+/// Files without a `version` field are treated as version 1.
+///
+/// # Returns
+///
+/// A `Result` containing:
+///
+/// - `Ok`: with events stored in file
+/// - `Err`: with [`FilesystemError::UnsupportedVersion`] if version is unknown,
+///   [`FilesystemError::SerializationError`] if version is invalid, or
+///   [`FilesystemError::JsonError`] if contents do not match format
+fn upgrade_format(contents: serde_json::Value) -> Result<EventCollection, FilesystemError> {
+    let version = match contents.get("version") {
+        None => 1,
+        Some(version) => version.as_u64().ok_or_else(|| {
+            FilesystemError::SerializationError {msg: format!("Invalid version: {}", version)}
+        })?,
+    };
+
+    let events = match version {
+        1 => serde_json::from_value::<Log>(contents).map(|log| log.log),
+        // version 2 differs only by legacy form of values, which is accepted when deserializing
+        2 | 3 => serde_json::from_value::<LogFile>(contents).map(|file| {
+            file.events.into_iter()
+                .map(|event| (event.timestamp, event))
+                .collect()
+        }),
+        _ => return Err(FilesystemError::UnsupportedVersion {version}),
+    };
+
+    Ok(events?)
+}
+
 /// - See [#126](https://github.com/PoorRican/sensd/issues/126) which implements validation of `path`.
 impl Document for Log {
     fn dir(&self) -> Option<&PathBuf> {
@@ -252,29 +886,83 @@ impl Document for Log {
 
     /// Generate generic filename based on settings, owner, and id
     ///
+    /// Device name is passed through [`slugify()`] so that it is safe to use in filenames. Since
+    /// multiple devices may share a name, id is always included for uniqueness. If name contains
+    /// no alphanumeric characters, then only id is used (eg: "log_3.json").
+    ///
     /// # Returns
     ///
-    /// A formatted filename as [`String`] with JSON filetype prefix.
+    /// A formatted filename as [`String`] with JSON filetype prefix (eg: "log_reservoir_ph_3.json").
     ///
     /// # See Also
     ///
     /// - [`FILETYPE`] for definition of filetype suffix
     fn filename(&self) -> String {
+        let slug = slugify(self.name());
+        match slug.is_empty() {
+            true => format!("{}{}{}", settings::LOG_FN_PREFIX, self.id(), self.backend.filetype()),
+            false => format!("{}{}_{}{}", settings::LOG_FN_PREFIX, slug, self.id(), self.backend.filetype()),
+        }
+    }
+}
+
+impl Log {
+    /// Filename used before device names were sanitized
+    ///
+    /// # Returns
+    ///
+    /// Formatted filename containing unsanitized device name
+    fn legacy_filename(&self) -> String {
         format!(
             "{}_{}_{}{}",
             settings::LOG_FN_PREFIX,
             self.name(),
-            self.id().to_string().as_str(),
+            self.id(),
             FILETYPE
         )
     }
+
+    /// Rename a log file with legacy naming to current naming scheme
+    ///
+    /// Nothing is done if a file already exists at [`Log::full_path()`], if no legacy file exists,
+    /// or if [`Log::backend()`] is not JSON.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok(true)` if a legacy file was renamed
+    /// - `Ok(false)` if no migration was necessary
+    /// - `Err` if renaming failed
+    ///
+    /// # Panics
+    ///
+    /// If no directory is associated
+    pub fn migrate(&self) -> Result<bool, ErrorType> {
+        // legacy files are always JSON
+        if self.backend != LogBackend::Json {
+            return Ok(false);
+        }
+
+        let path = self.full_path();
+        let legacy = self.dir()
+            .expect("No directory is associated")
+            .join(self.legacy_filename());
+
+        if path.exists() || legacy == path || !legacy.exists() {
+            return Ok(false);
+        }
+        std::fs::rename(legacy, path)?;
+        Ok(true)
+    }
 }
 
 // Testing
 #[cfg(test)]
 mod tests {
-    use crate::io::{IOKind, RawValue, IOEvent, DeviceMetadata, IODirection};
-    use crate::storage::{Document, Log, Persistent};
+    use chrono::Utc;
+    use crate::io::{Aggregation, IOKind, RawValue, IOEvent, DeviceMetadata, IODirection};
+    use crate::storage::{Document, Log, LogStats, Persistent};
     use std::path::Path;
     use std::time::Duration;
     use std::{fs, thread};
@@ -343,6 +1031,197 @@ mod tests {
         fs::remove_file(filename).unwrap();
     }
 
+    #[test]
+    fn save_to_and_load_from() {
+        const TMP_DIR: &str = "/tmp/sensd/save_to";
+        const BACKUP_DIR: &str = "/tmp/sensd/save_to/backup";
+
+        let metadata = DeviceMetadata::new("backup", 0, IOKind::Unassigned, IODirection::In);
+
+        let log = generate_log(3, &metadata).set_dir(TMP_DIR);
+        log.save_to(Path::new(BACKUP_DIR)).unwrap();
+
+        // default location is unaffected
+        assert!(!log.exists());
+        assert_eq!(Some(&std::path::PathBuf::from(TMP_DIR)), log.dir());
+
+        let mut loaded = Log::with_metadata(&metadata).set_dir(TMP_DIR);
+        loaded.load_from(Path::new(BACKUP_DIR)).unwrap();
+        assert_eq!(3, loaded.iter().count());
+
+        fs::remove_dir_all(BACKUP_DIR).unwrap();
+    }
+
+    #[test]
+    fn filename() {
+        let metadata = DeviceMetadata::new("Reservoir pH / #3", 3, IOKind::PH, IODirection::In);
+        assert_eq!("log_reservoir_ph_3_3.json", Log::with_metadata(&metadata).filename());
+
+        let metadata = DeviceMetadata::new("../", 4, IOKind::PH, IODirection::In);
+        assert_eq!("log_4.json", Log::with_metadata(&metadata).filename());
+    }
+
+    #[test]
+    fn migrate_legacy_file() {
+        const TMP_DIR: &str = "/tmp/sensd/migrate_legacy_file";
+
+        let metadata = DeviceMetadata::new("Legacy Name", 2, IOKind::PH, IODirection::In);
+
+        let log = generate_log(3, &metadata).set_dir(TMP_DIR);
+        log.save().unwrap();
+        let legacy = Path::new(TMP_DIR).join(log.legacy_filename());
+        fs::rename(log.full_path(), &legacy).unwrap();
+
+        let mut loaded = Log::with_metadata(&metadata).set_dir(TMP_DIR);
+        loaded.load().unwrap();
+        assert_eq!(3, loaded.iter().count());
+
+        assert!(loaded.exists());
+        assert!(!legacy.exists());
+        assert!(!loaded.migrate().unwrap());
+
+        fs::remove_dir_all(TMP_DIR).unwrap();
+    }
+
+    #[test]
+    fn pretty_and_compact() {
+        const TMP_DIR: &str = "/tmp/sensd/pretty_and_compact";
+
+        let metadata = DeviceMetadata::new("format", 0, IOKind::Unassigned, IODirection::In);
+
+        for pretty in [true, false] {
+            let log = generate_log(5, &metadata)
+                .set_pretty(pretty)
+                .set_dir(TMP_DIR);
+            log.save().unwrap();
+
+            let contents = fs::read_to_string(log.full_path()).unwrap();
+            assert_eq!(pretty, contents.contains('\n'));
+
+            let mut loaded = Log::with_metadata(&metadata).set_dir(TMP_DIR);
+            loaded.load().unwrap();
+            assert_eq!(5, loaded.iter().count());
+        }
+
+        fs::remove_dir_all(TMP_DIR).unwrap();
+    }
+
+    #[test]
+    fn numeric_binary() {
+        const TMP_DIR: &str = "/tmp/sensd/numeric_binary";
+
+        let metadata = DeviceMetadata::new("binary", 0, IOKind::Unassigned, IODirection::In);
+
+        let mut log = Log::with_metadata(&metadata)
+            .set_numeric_binary(true)
+            .set_dir(TMP_DIR);
+        log.push(IOEvent::new(RawValue::Binary(true))).unwrap();
+        thread::sleep(Duration::from_nanos(1));
+        log.push(IOEvent::new(RawValue::Binary(false))).unwrap();
+        log.save().unwrap();
+
+        let contents = fs::read_to_string(log.full_path()).unwrap();
+        assert!(contents.contains(r#""type":"binary","value":1"#));
+        assert!(contents.contains(r#""type":"binary","value":0"#));
+        assert!(!contents.contains("true"));
+
+        let mut loaded = Log::with_metadata(&metadata).set_dir(TMP_DIR);
+        loaded.load().unwrap();
+        let values: Vec<RawValue> = loaded.events().map(|event| event.value.clone()).collect();
+        assert_eq!(vec![RawValue::Binary(true), RawValue::Binary(false)], values);
+
+        fs::remove_file(log.full_path()).unwrap();
+    }
+
+    #[test]
+    fn error_source() {
+        use crate::errors::{ErrorType, FilesystemError};
+
+        const TMP_DIR: &str = "/tmp/sensd/error_source";
+
+        let metadata = DeviceMetadata::new("probe", 0, IOKind::PH, IODirection::In);
+        let path = Log::with_metadata(&metadata).set_dir(TMP_DIR).full_path();
+        fs::create_dir_all(TMP_DIR).unwrap();
+        fs::write(&path, b"{\"version\":2,").unwrap();
+
+        let mut log = Log::with_metadata(&metadata).set_dir(TMP_DIR);
+        let error: ErrorType = log.load().unwrap_err();
+
+        // original error is preserved
+        let source = match error.downcast_ref() {
+            Some(FilesystemError::JsonError {source}) => source,
+            _ => panic!("Expected JSON error"),
+        };
+        assert!(source.is_eof());
+        let chained = error.source().unwrap();
+        assert!(chained.downcast_ref::<serde_json::Error>().is_some());
+        assert_eq!(error.to_string(), format!("Error during serialization: {}", chained));
+
+        // I/O errors are distinguished from serialization errors
+        let mut log = Log::with_metadata(&metadata).set_dir(Path::new(TMP_DIR).join("missing"));
+        let error = log.load().unwrap_err();
+        match error.downcast_ref() {
+            Some(FilesystemError::IoError {source}) => {
+                assert_eq!(std::io::ErrorKind::NotFound, source.kind())
+            }
+            _ => panic!("Expected I/O error"),
+        }
+
+        fs::remove_dir_all(TMP_DIR).unwrap();
+    }
+
+    #[test]
+    fn text_values() {
+        const TMP_DIR: &str = "/tmp/sensd/text_values";
+
+        let metadata = DeviceMetadata::new("gps", 0, IOKind::Unassigned, IODirection::In);
+
+        let mut log = Log::with_metadata(&metadata).set_dir(TMP_DIR);
+        log.push(IOEvent::new(RawValue::from("$GPGGA,123519,4807.038,N"))).unwrap();
+        log.save().unwrap();
+
+        let mut loaded = Log::with_metadata(&metadata).set_dir(TMP_DIR);
+        loaded.load().unwrap();
+        assert_eq!(Some(&RawValue::from("$GPGGA,123519,4807.038,N")),
+                   loaded.latest().map(|event| &event.value));
+
+        fs::remove_file(log.full_path()).unwrap();
+    }
+
+    #[test]
+    fn format_version() {
+        const TMP_DIR: &str = "/tmp/sensd/format_version";
+
+        let metadata = DeviceMetadata::new("version", 0, IOKind::Unassigned, IODirection::In);
+        let log = generate_log(4, &metadata).set_dir(TMP_DIR);
+        log.save().unwrap();
+
+        let contents: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(log.full_path()).unwrap()).unwrap();
+        assert_eq!(crate::settings::LOG_VERSION, contents["version"].as_u64().unwrap());
+        assert_eq!(4, contents["events"].as_array().unwrap().len());
+
+        // unversioned files are loaded as version 1
+        fs::write(log.full_path(), serde_json::to_string(&log).unwrap()).unwrap();
+        let mut loaded = Log::with_metadata(&metadata).set_dir(TMP_DIR);
+        loaded.load().unwrap();
+        assert_eq!(4, loaded.iter().count());
+
+        // version 2 files store values in legacy form
+        let legacy = r#"{"version": 2, "events": [{"timestamp": "2023-01-01T00:00:00Z", "value": {"Float": 1.5}}]}"#;
+        fs::write(log.full_path(), legacy).unwrap();
+        let mut loaded = Log::with_metadata(&metadata).set_dir(TMP_DIR);
+        loaded.load().unwrap();
+        assert_eq!(Some(&RawValue::Float(1.5)), loaded.latest().map(|event| &event.value));
+
+        // unknown versions are rejected
+        fs::write(log.full_path(), "{\"version\": 99, \"events\": []}").unwrap();
+        let mut loaded = Log::with_metadata(&metadata).set_dir(TMP_DIR);
+        assert!(loaded.load().is_err());
+
+        fs::remove_dir_all(TMP_DIR).unwrap();
+    }
+
     #[test]
     fn set_dir() {
         let mut log = Log::default();
@@ -365,4 +1244,280 @@ mod tests {
 
         assert_eq!(100, orig.iter().count())
     }
+
+    #[test]
+    fn events_are_sorted() {
+        let now = Utc::now();
+        let mut log = Log::default();
+
+        assert!(log.latest().is_none());
+        assert!(log.first().is_none());
+
+        for offset in [3, 1, 4, 2, 5] {
+            let event = IOEvent::with_timestamp(
+                now - chrono::Duration::seconds(offset),
+                RawValue::Int(offset as i32));
+            log.push(event).unwrap();
+        }
+
+        let values: Vec<RawValue> = log.events().map(|event| event.value.clone()).collect();
+        let expected: Vec<RawValue> = [5, 4, 3, 2, 1].into_iter().map(RawValue::Int).collect();
+        assert_eq!(expected, values);
+
+        assert_eq!(RawValue::Int(1), log.latest().unwrap().value);
+        assert_eq!(RawValue::Int(5), log.first().unwrap().value);
+    }
+
+    #[test]
+    fn get_mut_and_entry() {
+        let now = Utc::now();
+        let mut log = Log::default();
+        log.push(IOEvent::with_timestamp(now, RawValue::Int(0))).unwrap();
+
+        log.get_mut(&now).unwrap().value = RawValue::Int(1);
+        assert_eq!(RawValue::Int(1), log.latest().unwrap().value);
+        assert!(log.get_mut(&(now + chrono::Duration::seconds(1))).is_none());
+
+        let later = now + chrono::Duration::seconds(1);
+        log.entry(later).or_insert(IOEvent::with_timestamp(later, RawValue::Int(2)));
+        assert_eq!(2, log.iter().count());
+
+        // `push()` still rejects existing timestamps
+        assert!(log.push(IOEvent::with_timestamp(later, RawValue::Int(3))).is_err());
+    }
+
+    #[test]
+    fn circular() {
+        const TMP_DIR: &str = "/tmp/sensd/circular_log";
+
+        let now = Utc::now();
+        let metadata = DeviceMetadata::new("circular", 0, IOKind::Unassigned, IODirection::In);
+        let mut log = Log::circular(&metadata, 3).set_dir(TMP_DIR);
+        assert_eq!(Some(3), log.capacity());
+
+        for offset in 0..5 {
+            let event = IOEvent::with_timestamp(
+                now + chrono::Duration::seconds(offset),
+                RawValue::Int(offset as i32));
+            log.push(event).unwrap();
+        }
+
+        let values: Vec<RawValue> = log.events().map(|event| event.value.clone()).collect();
+        let expected: Vec<RawValue> = [2, 3, 4].into_iter().map(RawValue::Int).collect();
+        assert_eq!(expected, values);
+        assert_eq!(3, log.iter().count());
+
+        // events older than retained window are rejected
+        let stale = IOEvent::with_timestamp(now, RawValue::Int(0));
+        assert!(log.push(stale).is_err());
+        assert_eq!(3, log.iter().count());
+
+        // extending also evicts
+        let mut other = Log::with_metadata(&metadata);
+        other.push(IOEvent::with_timestamp(now + chrono::Duration::seconds(10), RawValue::Int(10))).unwrap();
+        log.extend(&mut other);
+        assert_eq!(3, log.iter().count());
+        assert_eq!(RawValue::Int(10), log.latest().unwrap().value);
+
+        // events inserted by `entry()` are evicted in chronological order
+        let timestamp = now + chrono::Duration::seconds(11);
+        log.entry(timestamp).or_insert(IOEvent::with_timestamp(timestamp, RawValue::Int(11)));
+        log.push(IOEvent::with_timestamp(now + chrono::Duration::seconds(12), RawValue::Int(12))).unwrap();
+        let values: Vec<RawValue> = log.events().map(|event| event.value.clone()).collect();
+        let expected: Vec<RawValue> = [10, 11, 12].into_iter().map(RawValue::Int).collect();
+        assert_eq!(expected, values);
+
+        // log is never persisted
+        log.save().unwrap();
+        assert!(!log.exists());
+        log.load().unwrap();
+        assert_eq!(3, log.iter().count());
+
+        // directory is not required
+        let mut log = Log::circular(&metadata, 3);
+        log.save().unwrap();
+        log.load().unwrap();
+    }
+
+    #[test]
+    fn events_with_tag() {
+        const TMP_DIR: &str = "/tmp/sensd/events_with_tag";
+
+        let metadata = DeviceMetadata::new("tags", 0, IOKind::Unassigned, IODirection::In);
+        let now = Utc::now();
+        let mut log = Log::with_metadata(&metadata).set_dir(TMP_DIR);
+        for offset in 0..4 {
+            let mut event = IOEvent::with_timestamp(
+                now + chrono::Duration::seconds(offset),
+                RawValue::Int(offset as i32));
+            if offset % 2 == 1 {
+                event.tag("override");
+            }
+            log.push(event).unwrap();
+        }
+        log.get_mut(&now).unwrap().tag("calibration");
+        log.save().unwrap();
+
+        let mut loaded = Log::with_metadata(&metadata).set_dir(TMP_DIR);
+        loaded.load().unwrap();
+        let values: Vec<RawValue> = loaded.events_with_tag("override").map(|event| event.value.clone()).collect();
+        assert_eq!(vec![RawValue::Int(1), RawValue::Int(3)], values);
+        assert_eq!(1, loaded.events_with_tag("calibration").count());
+        assert_eq!(0, loaded.events_with_tag("missing").count());
+
+        fs::remove_dir_all(TMP_DIR).unwrap();
+    }
+
+    #[test]
+    fn enforce_retention() {
+        let now = Utc::now();
+        let mut log = Log::default();
+        for offset in 0..10 {
+            log.push(IOEvent::with_timestamp(
+                now - chrono::Duration::hours(offset),
+                RawValue::Int(offset as i32))).unwrap();
+        }
+
+        // no policy
+        assert_eq!(0, log.enforce_retention());
+
+        let mut log = log.set_retention(Some(chrono::Duration::minutes(330)), None);
+        assert_eq!(4, log.enforce_retention());
+        assert_eq!(6, log.iter().count());
+
+        log.set_retention_ref(None, Some(2));
+        assert_eq!((None, Some(2)), log.retention());
+        assert_eq!(4, log.enforce_retention());
+        let values: Vec<RawValue> = log.events().map(|event| event.value.clone()).collect();
+        assert_eq!(vec![RawValue::Int(1), RawValue::Int(0)], values);
+    }
+
+    #[test]
+    fn statistics() {
+        let now = Utc::now();
+        let mut log = Log::default();
+
+        assert_eq!(LogStats::default(), log.statistics(None));
+
+        for (offset, value) in [(3, 5.9), (2, 6.3), (1, 6.1)] {
+            let event = IOEvent::with_timestamp(
+                now - chrono::Duration::hours(offset),
+                RawValue::Float(value));
+            log.push(event).unwrap();
+        }
+        log.push(IOEvent::with_timestamp(now, RawValue::Binary(true))).unwrap();
+        log.push(IOEvent::with_timestamp(now + chrono::Duration::seconds(1), RawValue::from("fix"))).unwrap();
+
+        let stats = log.statistics(None);
+        assert_eq!(3, stats.count);
+        assert_eq!(2, stats.non_numeric);
+        assert!((stats.min.unwrap() - 5.9).abs() < 1e-6);
+        assert!((stats.max.unwrap() - 6.3).abs() < 1e-6);
+        assert!((stats.mean.unwrap() - 6.1).abs() < 1e-6);
+        assert_eq!(
+            Some((now - chrono::Duration::hours(3), now - chrono::Duration::hours(1))),
+            stats.span);
+
+        // only consider the last 150 minutes
+        let stats = log.statistics(Some(chrono::Duration::minutes(150)));
+        assert_eq!(2, stats.count);
+        assert!((stats.min.unwrap() - 6.1).abs() < 1e-6);
+        assert!((stats.mean.unwrap() - 6.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn downsample() {
+        let origin = chrono::DateTime::<Utc>::from_utc(
+            chrono::NaiveDateTime::from_timestamp_opt(1_699_999_980, 0).unwrap(), Utc);
+        let at = |seconds: i64| origin + chrono::Duration::seconds(seconds);
+
+        let mut log = Log::default();
+        for (offset, value) in [(0, 1.0), (10, 3.0), (59, 8.0), (185, 4.0)] {
+            log.push(IOEvent::with_timestamp(at(offset), RawValue::Float(value))).unwrap();
+        }
+
+        let bucket = chrono::Duration::minutes(1);
+        let events = log.downsample(bucket, Aggregation::Mean);
+        // origin is aligned to minute; empty buckets are skipped
+        assert_eq!(vec![at(0), at(180)], events.iter().map(|e| e.timestamp).collect::<Vec<_>>());
+        assert_eq!(RawValue::Float(4.0), events[0].value);
+        assert_eq!(RawValue::Float(4.0), events[1].value);
+
+        assert_eq!(RawValue::Float(8.0), log.downsample(bucket, Aggregation::Max)[0].value);
+        assert_eq!(RawValue::Float(8.0), log.downsample(bucket, Aggregation::Last)[0].value);
+
+        // text carries last value
+        log.push(IOEvent::with_timestamp(at(190), RawValue::from("fault"))).unwrap();
+        let events = log.downsample(bucket, Aggregation::Mean);
+        assert_eq!(RawValue::from("fault"), events[1].value);
+
+        assert!(Log::default().downsample(bucket, Aggregation::Mean).is_empty());
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode_backend() {
+        use crate::storage::LogBackend;
+
+        const TMP_DIR: &str = "/tmp/sensd/bincode_backend";
+
+        let metadata = DeviceMetadata::new("probe", 0, IOKind::PH, IODirection::In);
+        let mut log = generate_log(20, &metadata).set_dir(TMP_DIR);
+        let mut event = IOEvent::new(RawValue::from("recalibrated"));
+        event.tag("calibration");
+        log.push(event).unwrap();
+        log.push(IOEvent::new(RawValue::Binary(true))).unwrap();
+
+        log.save().unwrap();
+        let json_size = fs::metadata(log.full_path()).unwrap().len();
+
+        let log = log.set_backend(LogBackend::Bincode);
+        assert_eq!("log_probe_0.bin", log.filename());
+        log.save().unwrap();
+        let binary_size = fs::metadata(log.full_path()).unwrap().len();
+        assert!(binary_size < json_size);
+
+        let mut loaded = Log::with_metadata(&metadata)
+            .set_dir(TMP_DIR)
+            .set_backend(LogBackend::Bincode);
+        loaded.load().unwrap();
+
+        let expected: Vec<_> = log.events().map(|e| (e.timestamp, e.value.clone(), e.tags.clone())).collect();
+        let actual: Vec<_> = loaded.events().map(|e| (e.timestamp, e.value.clone(), e.tags.clone())).collect();
+        assert_eq!(expected, actual);
+
+        fs::remove_dir_all(TMP_DIR).unwrap();
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode_rejects_unknown_format() {
+        use crate::errors::FilesystemError;
+        use crate::storage::{LogBackend, BINARY_MAGIC};
+
+        const TMP_DIR: &str = "/tmp/sensd/bincode_rejects_unknown_format";
+
+        let metadata = DeviceMetadata::new("probe", 0, IOKind::PH, IODirection::In);
+        let load = || {
+            let mut log = Log::with_metadata(&metadata)
+                .set_dir(TMP_DIR)
+                .set_backend(LogBackend::Bincode);
+            log.load().unwrap_err()
+        };
+        let path = Log::with_metadata(&metadata).set_backend(LogBackend::Bincode)
+            .set_dir(TMP_DIR).full_path();
+        fs::create_dir_all(TMP_DIR).unwrap();
+
+        // JSON file
+        fs::write(&path, b"{\"version\":2,\"events\":[]}").unwrap();
+        assert!(matches!(load().downcast_ref(), Some(FilesystemError::UnrecognizedFormat {..})));
+
+        // unknown version
+        let mut contents = BINARY_MAGIC.to_vec();
+        contents.push(99);
+        fs::write(&path, contents).unwrap();
+        assert!(matches!(load().downcast_ref(), Some(FilesystemError::UnsupportedVersion {version: 99})));
+
+        fs::remove_dir_all(TMP_DIR).unwrap();
+    }
 }
@@ -1,8 +1,12 @@
 //! Datalogging of `IOEvent` objects
+mod backend;
 mod chronicle;
 mod log;
+mod stats;
 mod types;
 
+pub use backend::{LogBackend, BINARY_FILETYPE, BINARY_MAGIC, BINARY_VERSION};
 pub use chronicle::Chronicle;
 pub use log::*;
+pub use stats::LogStats;
 pub use types::*;
\ No newline at end of file
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+
+/// Summary of numeric values stored in a [`crate::storage::Log`]
+///
+/// Only numeric [`crate::io::RawValue`] variants contribute to `min`, `max`, and `mean`. Non-numeric
+/// values (ie: [`crate::io::RawValue::Binary`]) are excluded from these fields but are tallied in
+/// `non_numeric` so that the total number of events can still be determined.
+///
+/// # See Also
+///
+/// - [`crate::storage::Log::statistics()`] for generating a summary
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LogStats {
+    /// Number of numeric events
+    pub count: usize,
+
+    /// Number of excluded non-numeric events
+    pub non_numeric: usize,
+
+    /// Smallest numeric value. `None` if there are no numeric events.
+    pub min: Option<f64>,
+
+    /// Largest numeric value. `None` if there are no numeric events.
+    pub max: Option<f64>,
+
+    /// Arithmetic mean of numeric values. `None` if there are no numeric events.
+    pub mean: Option<f64>,
+
+    /// Timestamps of earliest and latest numeric events. `None` if there are no numeric events.
+    pub span: Option<(DateTime<Utc>, DateTime<Utc>)>,
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+use crate::io::{IdType, IODirection, IOEvent};
+
+/// Single [`IOEvent`] tagged with the device which generated it
+///
+/// [`IOEvent`] does not store any information about its source device since events are normally
+/// stored in a per-device [`crate::storage::Log`]. When events from multiple devices are combined
+/// into a single stream, device direction and id are attached.
+///
+/// When serialized, fields of `event` are flattened alongside `direction` and `id`.
+///
+/// # See Also
+///
+/// - [`crate::storage::Group::merged_log()`] for generating a merged stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergedEvent {
+    /// I/O direction of source device
+    pub direction: IODirection,
+
+    /// Id of source device
+    pub id: IdType,
+
+    /// Original event
+    #[serde(flatten)]
+    pub event: IOEvent,
+}
@@ -1,15 +1,34 @@
 //! Data structures and interfaces to store data
 //!
+mod audit;
+mod config;
+mod dump;
 mod group;
+mod health;
+mod latency;
 mod logging;
+mod merged;
+mod observer;
 mod persistent;
 mod directory;
 mod root;
+mod sink;
 mod document;
+mod watchdog;
 
+pub use audit::{AuditAction, AuditEvent};
+pub use config::{ConfigChange, GroupConfig};
+pub use dump::{DeviceState, RoutineState, StateDump};
 pub use document::*;
-pub use group::Group;
+pub use group::{Group, PollPolicy};
+pub use health::GroupHealth;
+pub use latency::ReadLatency;
 pub use logging::*;
+pub use merged::MergedEvent;
+pub use observer::{FaultObserver, PollObserver, PollStartObserver, RoutineObserver};
+pub(crate) use observer::Observers;
 pub use persistent::{Persistent, FILETYPE};
 pub use directory::*;
 pub use root::*;
+pub use sink::{BoxedSink, FileSink, NullSink, Sink};
+pub use watchdog::Watchdog;
@@ -0,0 +1,44 @@
+use crate::errors::ErrorType;
+use crate::io::{IdType, IOEvent};
+
+/// Callback invoked before a [`crate::storage::Group`] polls its devices
+pub type PollStartObserver = Box<dyn Fn() + Send + Sync>;
+
+/// Callback invoked with the result of every read after a [`crate::storage::Group`] polls its
+/// devices
+pub type PollObserver = Box<dyn Fn(&[Result<IOEvent, ErrorType>]) + Send + Sync>;
+
+/// Callback invoked with the id of an input device which has become faulted
+pub type FaultObserver = Box<dyn Fn(IdType) + Send + Sync>;
+
+/// Callback invoked with the id of an input device and the number of routines that were executed
+pub type RoutineObserver = Box<dyn Fn(IdType, usize) + Send + Sync>;
+
+/// Lifecycle callbacks registered on a [`crate::storage::Group`]
+///
+/// Observers of each kind are called in registration order.
+#[derive(Default)]
+pub(crate) struct Observers {
+    pub poll_start: Vec<PollStartObserver>,
+    pub poll_complete: Vec<PollObserver>,
+    pub device_fault: Vec<FaultObserver>,
+    pub routine_fired: Vec<RoutineObserver>,
+}
+
+impl Observers {
+    pub fn poll_start(&self) {
+        self.poll_start.iter().for_each(|observer| observer());
+    }
+
+    pub fn poll_complete(&self, results: &[Result<IOEvent, ErrorType>]) {
+        self.poll_complete.iter().for_each(|observer| observer(results));
+    }
+
+    pub fn device_fault(&self, id: IdType) {
+        self.device_fault.iter().for_each(|observer| observer(id));
+    }
+
+    pub fn routine_fired(&self, id: IdType, count: usize) {
+        self.routine_fired.iter().for_each(|observer| observer(id, count));
+    }
+}
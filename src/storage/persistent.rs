@@ -1,4 +1,5 @@
-use crate::errors::ErrorType;
+use std::path::Path;
+use crate::errors::{ErrorType, FilesystemError};
 
 /// Default filetype suffix.
 ///
@@ -6,10 +7,41 @@ use crate::errors::ErrorType;
 pub const FILETYPE: &str = ".json";
 
 /// Expresses an interface to save or load from disk
+///
+/// [`Persistent::save()`] and [`Persistent::load()`] use the default location of the object (ie:
+/// the directory derived from the root path of a [`crate::storage::Group`]).
+/// [`Persistent::save_to()`] and [`Persistent::load_from()`] are their explicit counterparts,
+/// where `path` takes the place of the default directory while filenames and any nested directory
+/// structure are kept. Therefore, the default forms behave as the explicit forms called with the
+/// default directory. Explicit forms do not change the default location.
 pub trait Persistent {
     /// save data to disk
     fn save(&self) -> Result<(), ErrorType>;
 
     /// load from disk
     fn load(&mut self) -> Result<(), ErrorType>;
+
+    /// Save data to disk within an explicit directory
+    ///
+    /// By default, explicit paths are not supported and [`FilesystemError::UnsupportedPath`] is
+    /// returned.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: Directory used in place of default directory
+    fn save_to(&self, path: &Path) -> Result<(), ErrorType> {
+        Err(Box::new(FilesystemError::UnsupportedPath {path: path.display().to_string()}))
+    }
+
+    /// Load from disk within an explicit directory
+    ///
+    /// By default, explicit paths are not supported and [`FilesystemError::UnsupportedPath`] is
+    /// returned.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: Directory used in place of default directory
+    fn load_from(&mut self, path: &Path) -> Result<(), ErrorType> {
+        Err(Box::new(FilesystemError::UnsupportedPath {path: path.display().to_string()}))
+    }
 }
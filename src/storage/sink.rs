@@ -0,0 +1,165 @@
+use std::fs::{create_dir_all, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::errors::ErrorType;
+use crate::io::IOEvent;
+
+/// Destination for events generated when a [`crate::storage::Group`] is polled
+///
+/// Sinks decouple event consumption from in-memory logging: events may be forwarded to a
+/// database or over a network in addition to being stored by the device [`crate::storage::Log`].
+/// Sinks are additive and do not affect device logs.
+///
+/// # See Also
+///
+/// - [`crate::storage::Group::add_sink()`]
+/// - [`FileSink`] and [`NullSink`] for provided implementations
+pub trait Sink {
+    /// Handle a single event
+    ///
+    /// Errors should be handled internally since polling is not interrupted by a faulty sink.
+    ///
+    /// # Parameters
+    ///
+    /// - `event`: Event generated by an input device
+    fn consume(&mut self, event: &IOEvent);
+
+    /// Flush any buffered events
+    ///
+    /// This is called after all events of a poll have been consumed. By default, nothing is done.
+    fn flush(&mut self) -> Result<(), ErrorType> {
+        Ok(())
+    }
+}
+
+pub type BoxedSink = Box<dyn Sink + Send + Sync>;
+
+/// [`Sink`] which appends events to a file as newline delimited JSON
+///
+/// # Example
+///
+/// ```
+/// use sensd::io::{IOEvent, RawValue};
+/// use sensd::storage::{FileSink, Sink};
+///
+/// let path = "/tmp/sensd_doctests/file_sink/events.jsonl";
+/// let mut sink = FileSink::new(path).unwrap();
+///
+/// sink.consume(&IOEvent::new(RawValue::Float(1.5)));
+/// sink.flush().unwrap();
+///
+/// assert_eq!(1, std::fs::read_to_string(path).unwrap().lines().count());
+/// # std::fs::remove_file(path).unwrap();
+/// ```
+pub struct FileSink {
+    path: PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl FileSink {
+    /// Constructor for [`FileSink`]
+    ///
+    /// Existing files are appended to. Parent directories are created when missing.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: Path of file to write events to
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with initialized sink
+    /// - `Err` if directory or file cannot be created
+    pub fn new<P>(path: P) -> Result<Self, ErrorType>
+    where
+        P: AsRef<Path>
+    {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        let file = File::options()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+
+        Ok(Self { path, writer: BufWriter::new(file) })
+    }
+
+    /// Path of file which events are written to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Sink for FileSink {
+    /// Write event as a single line of JSON
+    ///
+    /// Errors are emitted as warnings via the [`log`] facade.
+    fn consume(&mut self, event: &IOEvent) {
+        let result = serde_json::to_writer(&mut self.writer, event)
+            .map_err(|e| e.to_string())
+            .and_then(|_| self.writer.write_all(b"\n").map_err(|e| e.to_string()));
+
+        if let Err(msg) = result {
+            log::warn!("Could not write event to {}: {}", self.path.display(), msg);
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), ErrorType> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+/// [`Sink`] which discards all events
+///
+/// This is useful as a placeholder or for benchmarking polling without storage overhead.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NullSink;
+
+impl Sink for NullSink {
+    #[inline]
+    fn consume(&mut self, _event: &IOEvent) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::io::{IOEvent, RawValue};
+    use crate::storage::{FileSink, NullSink, Sink};
+
+    #[test]
+    fn file_sink() {
+        const PATH: &str = "/tmp/sensd_tests/file_sink/events.jsonl";
+        let _ = fs::remove_file(PATH);
+
+        let mut sink = FileSink::new(PATH).unwrap();
+        sink.consume(&IOEvent::new(RawValue::Int(1)));
+        sink.consume(&IOEvent::new(RawValue::from("a")));
+        sink.flush().unwrap();
+        drop(sink);
+
+        // existing files are appended to
+        let mut sink = FileSink::new(PATH).unwrap();
+        sink.consume(&IOEvent::new(RawValue::Binary(true)));
+        sink.flush().unwrap();
+
+        let values: Vec<RawValue> = fs::read_to_string(PATH).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<IOEvent>(line).unwrap().value)
+            .collect();
+        assert_eq!(vec![RawValue::Int(1), RawValue::from("a"), RawValue::Binary(true)], values);
+
+        fs::remove_file(PATH).unwrap();
+    }
+
+    #[test]
+    fn null_sink() {
+        let mut sink = NullSink;
+        sink.consume(&IOEvent::new(RawValue::Int(1)));
+        assert!(sink.flush().is_ok());
+    }
+}
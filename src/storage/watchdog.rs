@@ -0,0 +1,170 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Instant;
+
+use chrono::Duration;
+
+/// Background monitor which detects a stalled polling loop
+///
+/// The driving loop calls [`Watchdog::heartbeat()`] after every iteration. A background thread
+/// checks that heartbeats arrive within `timeout`, and calls `on_stall` once when they stop. The
+/// watchdog is re-armed by the next heartbeat.
+///
+/// The watchdog only shares its own heartbeat state with the loop and never locks a
+/// [`crate::storage::Group`] or any devices. Therefore, a deadlocked device is detected instead of
+/// also blocking the watchdog.
+///
+/// The background thread is stopped when [`Watchdog`] is dropped.
+///
+/// # Example
+///
+/// ```
+/// use chrono::Duration;
+/// use sensd::storage::Watchdog;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+///
+/// let stalled = Arc::new(AtomicBool::new(false));
+/// let flag = stalled.clone();
+/// let watchdog = Watchdog::new(Duration::milliseconds(20), move || flag.store(true, Ordering::SeqCst));
+///
+/// watchdog.heartbeat();
+/// std::thread::sleep(std::time::Duration::from_millis(100));
+///
+/// assert!(watchdog.is_stalled());
+/// assert!(stalled.load(Ordering::SeqCst));
+/// ```
+pub struct Watchdog {
+    timeout: Duration,
+    state: Arc<WatchdogState>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// State shared between [`Watchdog`] and its background thread
+struct WatchdogState {
+    last_heartbeat: Mutex<Instant>,
+    stalled: AtomicBool,
+    stopped: AtomicBool,
+}
+
+impl Watchdog {
+    /// Constructor for [`Watchdog`] which starts background thread
+    ///
+    /// The timeout begins immediately, so the first heartbeat is expected within `timeout`.
+    ///
+    /// # Parameters
+    ///
+    /// - `timeout`: Maximum time allowed between heartbeats
+    /// - `on_stall`: Callback executed on background thread when a heartbeat is missed. This is
+    ///   typically used to restart the loop or to raise an alert.
+    ///
+    /// # Panics
+    ///
+    /// If `timeout` is not positive
+    pub fn new<F>(timeout: Duration, mut on_stall: F) -> Self
+    where
+        F: FnMut() + Send + 'static
+    {
+        let period = timeout.to_std().expect("Watchdog timeout must be positive");
+        assert!(!period.is_zero(), "Watchdog timeout must be positive");
+
+        let state = Arc::new(WatchdogState {
+            last_heartbeat: Mutex::new(Instant::now()),
+            stalled: AtomicBool::new(false),
+            stopped: AtomicBool::new(false),
+        });
+
+        let shared = state.clone();
+        let thread = thread::spawn(move || {
+            // check several times per timeout so that stalls are detected promptly
+            let check = period / 4;
+            while !shared.stopped.load(Ordering::Acquire) {
+                thread::park_timeout(check);
+
+                let elapsed = shared.last_heartbeat.lock().unwrap().elapsed();
+                if elapsed > period && !shared.stalled.swap(true, Ordering::AcqRel) {
+                    log::warn!("Watchdog: no heartbeat for {}ms", elapsed.as_millis());
+                    on_stall();
+                }
+            }
+        });
+
+        Self { timeout, state, thread: Some(thread) }
+    }
+
+    /// Signal that the loop is progressing
+    ///
+    /// Re-arms the watchdog if a stall was previously detected.
+    pub fn heartbeat(&self) {
+        *self.state.last_heartbeat.lock().unwrap() = Instant::now();
+        self.state.stalled.store(false, Ordering::Release);
+    }
+
+    /// Check if a heartbeat has been missed since the last heartbeat
+    pub fn is_stalled(&self) -> bool {
+        self.state.stalled.load(Ordering::Acquire)
+    }
+
+    /// Getter for maximum time allowed between heartbeats
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.state.stopped.store(true, Ordering::Release);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread::sleep;
+
+    use crate::storage::Watchdog;
+
+    const TIMEOUT_MS: u64 = 40;
+
+    #[test]
+    fn heartbeat_prevents_stall() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+        let watchdog = Watchdog::new(Duration::milliseconds(TIMEOUT_MS as i64), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        for _ in 0..10 {
+            sleep(std::time::Duration::from_millis(TIMEOUT_MS / 4));
+            watchdog.heartbeat();
+        }
+        assert!(!watchdog.is_stalled());
+        assert_eq!(0, count.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn stall_fires_once_until_rearmed() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+        let watchdog = Watchdog::new(Duration::milliseconds(TIMEOUT_MS as i64), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        sleep(std::time::Duration::from_millis(TIMEOUT_MS * 4));
+        assert!(watchdog.is_stalled());
+        assert_eq!(1, count.load(Ordering::SeqCst));
+
+        watchdog.heartbeat();
+        assert!(!watchdog.is_stalled());
+
+        sleep(std::time::Duration::from_millis(TIMEOUT_MS * 4));
+        assert_eq!(2, count.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,248 @@
+//! Typed physical quantities
+//!
+//! Each unit is a thin wrapper around an `f32` which is associated with the [`IOKind`] that
+//! produces it. Units are used by [`crate::io::TypedInput`] to convert [`RawValue`] readings into
+//! values with compile-time unit safety.
+
+use crate::errors::ValueError;
+use crate::io::{kind_defaults, IOKind, RawValue, RawValueKind};
+
+/// Interface for a physical quantity produced by a specific kind of device
+pub trait Unit: Sized {
+    /// Kind of device which produces values of this unit
+    const KIND: IOKind;
+
+    /// Abbreviated symbol used when displaying values
+    const SYMBOL: &'static str;
+
+    /// Construct from a scalar value
+    fn new(value: f32) -> Self;
+
+    /// Getter for scalar value
+    fn value(&self) -> f32;
+
+    /// Convert a [`RawValue`] to this unit
+    ///
+    /// # Returns
+    ///
+    /// `None` if `value` is not numeric (ie: [`RawValue::Binary`])
+    fn from_raw(value: RawValue) -> Option<Self> {
+        value.as_f64().map(|value| Self::new(value as f32))
+    }
+
+    /// Construct from a scalar value which is validated against the default range of
+    /// [`Unit::KIND`]
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing:
+    ///
+    /// - `Ok` with constructed value if value is within range, or if kind has no default range
+    /// - `Err` with [`ValueError::UnitRange`] if value is out of range or is not finite
+    ///
+    /// # See Also
+    ///
+    /// - [`kind_defaults()`] for default ranges
+    fn validated(value: f32) -> Result<Self, ValueError> {
+        let in_range = match kind_defaults(Self::KIND) {
+            Some((min, max)) => value.is_finite() && min <= value && value <= max,
+            None => value.is_finite(),
+        };
+        match in_range {
+            true => Ok(Self::new(value)),
+            false => Err(ValueError::UnitRange {value: RawValue::Float(value), unit: Self::SYMBOL}),
+        }
+    }
+}
+
+/// Acidity or alkalinity of a solution
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Ph(pub f32);
+
+impl Unit for Ph {
+    const KIND: IOKind = IOKind::PH;
+    const SYMBOL: &'static str = "pH";
+
+    fn new(value: f32) -> Self {
+        Self(value)
+    }
+
+    fn value(&self) -> f32 {
+        self.0
+    }
+}
+
+impl From<Ph> for RawValue {
+    fn from(value: Ph) -> Self {
+        RawValue::Float(value.0)
+    }
+}
+
+/// Conversion is validated against the default range of [`IOKind::PH`] (0–14). Non-finite values
+/// (ie: `NaN`) are rejected.
+impl TryFrom<RawValue> for Ph {
+    type Error = ValueError;
+
+    fn try_from(value: RawValue) -> Result<Self, Self::Error> {
+        let ph = Ph::from_raw(value.clone())
+            .ok_or_else(|| ValueError::ConversionError {value: value.clone(), unit: Self::SYMBOL})?;
+        Ph::validated(ph.0).map_err(|_| ValueError::UnitRange {value, unit: Self::SYMBOL})
+    }
+}
+
+/// Temperature in degrees Celsius
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Celsius(pub f32);
+
+impl Unit for Celsius {
+    const KIND: IOKind = IOKind::Temperature;
+    const SYMBOL: &'static str = "°C";
+
+    fn new(value: f32) -> Self {
+        Self(value)
+    }
+
+    fn value(&self) -> f32 {
+        self.0
+    }
+}
+
+/// Relative humidity as a percentage
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Humidity(pub f32);
+
+impl Unit for Humidity {
+    const KIND: IOKind = IOKind::RelativeHumidity;
+    const SYMBOL: &'static str = "%";
+
+    fn new(value: f32) -> Self {
+        Self(value)
+    }
+
+    fn value(&self) -> f32 {
+        self.0
+    }
+}
+
+/// Electrical conductivity in millisiemens per centimeter
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Conductivity(pub f32);
+
+impl Unit for Conductivity {
+    const KIND: IOKind = IOKind::EC;
+    const SYMBOL: &'static str = "mS/cm";
+
+    fn new(value: f32) -> Self {
+        Self(value)
+    }
+
+    fn value(&self) -> f32 {
+        self.0
+    }
+}
+
+impl IOKind {
+    /// Symbol of the [`Unit`] produced by devices of this kind
+    ///
+    /// | Kind | Unit |
+    /// |------|------|
+    /// | [`IOKind::PH`] | [`Ph`] |
+    /// | [`IOKind::Temperature`] | [`Celsius`] |
+    /// | [`IOKind::RelativeHumidity`] | [`Humidity`] |
+    /// | [`IOKind::EC`] | [`Conductivity`] |
+    ///
+    /// # Returns
+    ///
+    /// `Some` with [`Unit::SYMBOL`], or `None` if kind has no associated unit
+    pub fn unit(&self) -> Option<&'static str> {
+        match self {
+            IOKind::PH => Some(Ph::SYMBOL),
+            IOKind::Temperature => Some(Celsius::SYMBOL),
+            IOKind::RelativeHumidity => Some(Humidity::SYMBOL),
+            IOKind::EC => Some(Conductivity::SYMBOL),
+            _ => None,
+        }
+    }
+
+    /// Format a raw value with the unit symbol of this kind
+    ///
+    /// Non-numeric values and kinds without a unit are formatted as the plain value.
+    /// [`RawValue::Percent`] is always formatted with `%`.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: Value to format
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sensd::io::{IOKind, RawValue};
+    ///
+    /// assert_eq!("6.5 pH", IOKind::PH.display_value(RawValue::Float(6.5)));
+    /// assert_eq!("3", IOKind::Flow.display_value(RawValue::Int(3)));
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`IOKind::unit()`] for mapping of kinds to units
+    pub fn display_value(&self, value: RawValue) -> String {
+        if value.kind() == RawValueKind::Percent {
+            return value.to_string();
+        }
+        match (self.unit(), value.as_f64()) {
+            (Some(symbol), Some(_)) => format!("{} {}", value, symbol),
+            _ => value.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::ValueError;
+    use crate::io::{IOKind, RawValue};
+    use crate::units::{Celsius, Conductivity, Humidity, Ph, Unit};
+
+    #[test]
+    fn from_raw() {
+        assert_eq!(Some(Ph(6.5)), Ph::from_raw(RawValue::Float(6.5)));
+        assert_eq!(Some(Celsius(21.0)), Celsius::from_raw(RawValue::Int(21)));
+        assert!(Ph::from_raw(RawValue::Binary(true)).is_none());
+    }
+
+    #[test]
+    fn ph_conversion() {
+        assert_eq!(RawValue::Float(6.5), RawValue::from(Ph(6.5)));
+
+        assert_eq!(Ph(7.0), Ph::try_from(RawValue::Float(7.0)).unwrap());
+        assert_eq!(Ph(14.0), Ph::try_from(RawValue::Int(14)).unwrap());
+        assert!(matches!(Ph::try_from(RawValue::Float(14.5)), Err(ValueError::UnitRange {..})));
+        assert!(matches!(Ph::try_from(RawValue::Float(-0.1)), Err(ValueError::UnitRange {..})));
+        assert!(matches!(Ph::try_from(RawValue::Float(f32::NAN)), Err(ValueError::UnitRange {..})));
+        assert!(matches!(Ph::try_from(RawValue::Float(f32::INFINITY)), Err(ValueError::UnitRange {..})));
+        assert!(matches!(Ph::try_from(RawValue::Binary(true)), Err(ValueError::ConversionError {..})));
+    }
+
+    #[test]
+    fn validated() {
+        assert_eq!(Ph(7.0), Ph::validated(7.0).unwrap());
+        assert_eq!(Celsius(-40.0), Celsius::validated(-40.0).unwrap());
+        assert!(matches!(Ph::validated(1000.0), Err(ValueError::UnitRange {..})));
+        assert!(matches!(Humidity::validated(100.5), Err(ValueError::UnitRange {..})));
+        assert!(matches!(Conductivity::validated(f32::NAN), Err(ValueError::UnitRange {..})));
+    }
+
+    #[test]
+    fn display_value() {
+        assert_eq!(Some(Ph::SYMBOL), IOKind::PH.unit());
+        assert_eq!(Some(Celsius::SYMBOL), IOKind::Temperature.unit());
+        assert_eq!(Some(Conductivity::SYMBOL), IOKind::EC.unit());
+        assert!(IOKind::Flow.unit().is_none());
+
+        assert_eq!("6.5 pH", IOKind::PH.display_value(RawValue::Float(6.5)));
+        assert_eq!("21 °C", IOKind::Temperature.display_value(RawValue::Int(21)));
+        assert_eq!("1.2 mS/cm", IOKind::EC.display_value(RawValue::Float(1.2)));
+        assert_eq!("40", IOKind::Unassigned.display_value(RawValue::PosInt8(40)));
+        assert_eq!("true", IOKind::PH.display_value(RawValue::Binary(true)));
+        assert_eq!("45%", IOKind::RelativeHumidity.display_value(RawValue::percent(45.0)));
+    }
+}
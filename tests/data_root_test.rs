@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+use sensd::settings::DATA_ROOT_ENV;
+use sensd::storage::{Directory, Group, RootDirectory, RootPath};
+
+#[test]
+/// Environment variable is honored when no explicit root is given
+///
+/// This is an integration test since it mutates the process-wide environment.
+fn env_data_root() {
+    const PATH: &str = "/tmp/sensd_tests/env_data_root";
+    std::env::set_var(DATA_ROOT_ENV, PATH);
+
+    let group = Group::new("group");
+    assert_eq!(RootPath::from(PATH), group.root_dir());
+    assert_eq!(PathBuf::from(PATH).join("group"), group.full_path());
+
+    // explicit root takes precedence
+    let group = Group::with_root("group", "/tmp/sensd_tests/explicit_root");
+    assert_eq!(PathBuf::from("/tmp/sensd_tests/explicit_root/group"), group.full_path());
+
+    std::env::remove_var(DATA_ROOT_ENV);
+}
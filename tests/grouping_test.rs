@@ -8,7 +8,7 @@ use sensd::storage::{Chronicle, Group, Persistent, RootDirectory};
 #[test]
 /// Test builder pattern for adding devices
 fn test_builder_pattern() {
-    let command = IOCommand::Input(move || RawValue::default());
+    let command = IOCommand::input(move || RawValue::default());
 
     let mut group = Group::new("main");
     group
@@ -17,19 +17,19 @@ fn test_builder_pattern() {
                 "test name",
                 0,
                 IOKind::PH,
-            ).set_command(command.clone()))
+            ).with_command(command.clone()).unwrap())
         .push_input(
             Input::new(
                 "second sensor",
                 1,
                 IOKind::EC,
-            ).set_command(command.clone()))
+            ).with_command(command.clone()).unwrap())
         .push_output(
             Output::new(
                 "output device",
                 2,
                 IOKind::Flow
-            ).set_command(IOCommand::Output(|_| Ok(())))
+            ).with_command(IOCommand::output(|_| Ok(()))).unwrap()
         );
 
     assert_eq!(group.inputs.len(), 2);
@@ -38,7 +38,7 @@ fn test_builder_pattern() {
 
 #[test]
 fn test_poll() {
-    let command = IOCommand::Input(move || RawValue::default());
+    let command = IOCommand::input(move || RawValue::default());
 
     let mut group = Group::with_interval("main", Duration::nanoseconds(1));
     group
@@ -48,9 +48,9 @@ fn test_poll() {
                 "test name",
                 0,
                 IOKind::PH,
-            ).set_command(
+            ).with_command(
                 command.clone()
-            ).init_log()
+            ).unwrap().init_log()
 
         ).push_input(
 
@@ -58,9 +58,9 @@ fn test_poll() {
                 "second sensor",
                 1,
                 IOKind::EC,
-            ).set_command(
+            ).with_command(
                 command.clone()
-            ).init_log()
+            ).unwrap().init_log()
 
         );
 
@@ -93,44 +93,44 @@ fn test_directory_hierarchy() {
     const TMP_DIR: &str = "/tmp/sensd/hierarchy_testing";
     const INTERVAL: i64 = 1;
 
-    let in_command = IOCommand::Input(move || RawValue::default());
+    let in_command = IOCommand::input(move || RawValue::default());
 
     let input1 =
         Input::new(
             "i1",
             0,
             IOKind::PH,
-        ).set_command(
+        ).with_command(
             in_command.clone()
-        ).init_log();
+        ).unwrap().init_log();
     let input2 =
         Input::new(
             "i2",
             1,
             IOKind::EC,
-        ).set_command(
+        ).with_command(
             in_command.clone()
-        ).init_log();
+        ).unwrap().init_log();
 
-    let out_command = IOCommand::Output(|_| Ok(()));
+    let out_command = IOCommand::output(|_| Ok(()));
 
     let output1 =
         Output::new(
             "o1",
             0,
             IOKind::RotationVector,
-        ).set_command(
+        ).with_command(
             out_command.clone()
-        ).init_log();
+        ).unwrap().init_log();
 
     let output2 =
         Output::new(
             "o2",
             1,
             IOKind::RotationVector,
-        ).set_command(
+        ).with_command(
             out_command.clone()
-        ).init_log();
+        ).unwrap().init_log();
 
     // Build `Group` and create directories
     let mut group =